@@ -53,6 +53,7 @@ function makeIssue(overrides: Record<string, unknown> = {}) {
 function mockGitHubFetch(
   reviewPRs: unknown[],
   assignedIssues: unknown[],
+  totals: { review?: number; assigned?: number } = {},
 ) {
   globalThis.fetch = vi.fn().mockImplementation((url: string) => {
     const urlStr = typeof url === "string" ? url : String(url);
@@ -63,7 +64,7 @@ function mockGitHubFetch(
         status: 200,
         json: () =>
           Promise.resolve({
-            total_count: reviewPRs.length,
+            total_count: totals.review ?? reviewPRs.length,
             items: reviewPRs,
           }),
       });
@@ -75,7 +76,7 @@ function mockGitHubFetch(
         status: 200,
         json: () =>
           Promise.resolve({
-            total_count: assignedIssues.length,
+            total_count: totals.assigned ?? assignedIssues.length,
             items: assignedIssues,
           }),
       });
@@ -123,6 +124,7 @@ describe("GitHub plugin — fetch()", () => {
     expect(item.title).toBe("[owner/repo#42] Add feature X");
     expect(item.url).toBe("https://github.com/owner/repo/pull/42");
     expect(item.author).toBe("alice");
+    expect(item.signals).toEqual(["review_requested"]);
   });
 
   it("maps assigned issue (no pull_request field) → type 'issue'", async () => {
@@ -171,6 +173,67 @@ describe("GitHub plugin — fetch()", () => {
     expect(result.items[0].metadata.repo).toBe("owner/repo");
   });
 
+  it("returns a cursor and polls incrementally with it", async () => {
+    mockGitHubFetch([makePR()], []);
+    const first = JSON.parse(await fetch(configJson()));
+    expect(typeof first.cursor).toBe("string");
+    const firstUrl = String(vi.mocked(globalThis.fetch).mock.calls[0][0]);
+    expect(decodeURIComponent(firstUrl)).not.toContain("updated:");
+
+    vi.setSystemTime(new Date("2026-02-19T12:10:00Z"));
+    mockGitHubFetch([], []);
+    await fetch(configJson(), first.cursor);
+    const secondUrl = String(vi.mocked(globalThis.fetch).mock.calls[0][0]);
+    // Previous poll time minus the 2 min overlap.
+    expect(decodeURIComponent(secondUrl)).toContain("updated:>=2026-02-19T11:58:00Z");
+  });
+
+  it("falls back to a full fetch on an unreadable cursor", async () => {
+    mockGitHubFetch([], []);
+    await fetch(configJson(), "not-a-cursor");
+    const url = String(vi.mocked(globalThis.fetch).mock.calls[0][0]);
+    expect(decodeURIComponent(url)).not.toContain("updated:");
+  });
+
+  it("full fetch is complete unless a search was capped", async () => {
+    mockGitHubFetch([makePR()], [makeIssue()]);
+    expect(JSON.parse(await fetch(configJson())).partial).toBe(false);
+
+    // 120 review requests, only the first page returned: the rest must not
+    // be archived as if they were gone.
+    mockGitHubFetch([makePR()], [makeIssue()], { review: 120 });
+    expect(JSON.parse(await fetch(configJson())).partial).toBe(true);
+  });
+
+  it("reports a 403 with rate-limit headers as rate limited", async () => {
+    const reset = Date.parse("2026-02-19T12:05:00Z") / 1000;
+    globalThis.fetch = vi.fn().mockResolvedValue({
+      ok: false,
+      status: 403,
+      statusText: "Forbidden",
+      headers: new Headers({ "X-RateLimit-Remaining": "0", "X-RateLimit-Reset": String(reset) }),
+    });
+    await expect(fetch(configJson())).rejects.toMatchObject({ kind: "rate_limited", retryAfter: 300 });
+
+    globalThis.fetch = vi.fn().mockResolvedValue({
+      ok: false,
+      status: 403,
+      statusText: "Forbidden",
+      headers: new Headers({ "Retry-After": "60" }),
+    });
+    await expect(fetch(configJson())).rejects.toMatchObject({ kind: "rate_limited", retryAfter: 60 });
+  });
+
+  it("reports a 403 without rate-limit headers as an auth failure", async () => {
+    globalThis.fetch = vi.fn().mockResolvedValue({
+      ok: false,
+      status: 403,
+      statusText: "Forbidden",
+      headers: new Headers({ "X-RateLimit-Remaining": "4999" }),
+    });
+    await expect(fetch(configJson())).rejects.toMatchObject({ kind: "auth" });
+  });
+
   it("empty search results → {items:[], notifications:[]}", async () => {
     mockGitHubFetch([], []);
     const result = JSON.parse(await fetch(configJson()));
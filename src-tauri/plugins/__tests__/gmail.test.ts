@@ -46,6 +46,7 @@ function makeMessage(overrides: Record<string, unknown> = {}) {
 function mockGmailFetch(
   messages: unknown[],
   messageList?: Array<{ id: string; threadId: string }>,
+  nextPageToken?: string,
 ) {
   // Derive message list from messages if not explicitly provided
   const list =
@@ -80,6 +81,7 @@ function mockGmailFetch(
         json: () =>
           Promise.resolve({
             messages: list.length > 0 ? list : undefined,
+            nextPageToken,
           }),
       });
     }
@@ -315,6 +317,15 @@ describe("Gmail plugin — fetch()", () => {
     expect(result.notifications).toHaveLength(0);
   });
 
+  it("full fetch past the 30-message cap is partial", async () => {
+    const msg = makeMessage();
+    mockGmailFetch([msg]);
+    expect(JSON.parse(await fetch(configJson())).partial).toBe(false);
+
+    mockGmailFetch([msg], undefined, "page-2");
+    expect(JSON.parse(await fetch(configJson())).partial).toBe(true);
+  });
+
   it("empty message list → {items:[], notifications:[]}", async () => {
     mockGmailFetch([], []);
     const result = JSON.parse(await fetch(configJson()));
@@ -1,5 +1,6 @@
 // Tests for Jira plugin — src-tauri/plugins/jira.ts
 import { fetch, validateConnection } from "../jira.ts";
+import { PluginFailure } from "../plugin_interface.ts";
 
 const BASE_CONFIG = {
   baseUrl: "https://test.atlassian.net",
@@ -41,11 +42,11 @@ function makeIssue(overrides: Record<string, unknown> = {}) {
   };
 }
 
-function mockFetchOk(issues: unknown[]) {
+function mockFetchOk(issues: unknown[], page: Record<string, unknown> = {}) {
   globalThis.fetch = vi.fn().mockResolvedValue({
     ok: true,
     status: 200,
-    json: () => Promise.resolve({ issues }),
+    json: () => Promise.resolve({ issues, ...page }),
   });
 }
 
@@ -119,7 +120,7 @@ describe("Jira plugin — fetch()", () => {
     expect(result.items[0].summary).toBeNull();
   });
 
-  it("medium urgency: assigned_to_you only (weight 3)", async () => {
+  it("medium urgency: assigned_to_me only (weight 3)", async () => {
     const issue = makeIssue();
     // priority "Medium" id "3" — not P1/blocker, no due date
     issue.fields.priority = { name: "Medium", id: "3" };
@@ -130,7 +131,7 @@ describe("Jira plugin — fetch()", () => {
     const notif = result.notifications[0];
 
     expect(notif.urgency).toBe("medium");
-    expect(notif.reason).toBe("assigned_to_you");
+    expect(notif.reason).toBe("assigned_to_me");
   });
 
   it("high urgency: assigned + P1 (weight 7)", async () => {
@@ -143,7 +144,7 @@ describe("Jira plugin — fetch()", () => {
     const notif = result.notifications[0];
 
     expect(notif.urgency).toBe("high");
-    expect(notif.reason).toBe("assigned_to_you,priority_p1_blocker");
+    expect(notif.reason).toBe("assigned_to_me,priority_p1_blocker");
   });
 
   it("critical urgency: assigned + P1 + deadline_24h (weight 10)", async () => {
@@ -160,6 +161,20 @@ describe("Jira plugin — fetch()", () => {
     expect(notif.reason).toContain("deadline_24h");
   });
 
+  it("emits raw signals on the item for host-side scoring", async () => {
+    const issue = makeIssue();
+    issue.fields.priority = { name: "Highest", id: "1" };
+    issue.fields.duedate = "2026-02-20T00:00:00.000Z";
+    mockFetchOk([issue]);
+
+    const result = JSON.parse(await fetch(configJson()));
+    expect(result.items[0].signals).toEqual([
+      "assigned_to_me",
+      "priority_p1_blocker",
+      "deadline_24h",
+    ]);
+  });
+
   it("past due date does NOT trigger deadline_24h", async () => {
     const issue = makeIssue();
     issue.fields.priority = { name: "Highest", id: "1" };
@@ -189,6 +204,17 @@ describe("Jira plugin — fetch()", () => {
     expect(notif.reason).not.toContain("deadline_24h");
   });
 
+  it("full fetch with more issues than maxResults is partial", async () => {
+    mockFetchOk([makeIssue()], { isLast: true });
+    expect(JSON.parse(await fetch(configJson())).partial).toBe(false);
+
+    mockFetchOk([makeIssue()], { isLast: false, nextPageToken: "next" });
+    expect(JSON.parse(await fetch(configJson())).partial).toBe(true);
+
+    mockFetchOk([makeIssue()], { total: 75 });
+    expect(JSON.parse(await fetch(configJson())).partial).toBe(true);
+  });
+
   it("empty issues array → {items:[], notifications:[]}", async () => {
     mockFetchOk([]);
     const result = JSON.parse(await fetch(configJson()));
@@ -218,6 +244,39 @@ describe("Jira plugin — fetch()", () => {
 
     await expect(fetch(configJson())).rejects.toThrow("Jira API error: 401 Unauthorized");
   });
+
+  it("reports 401 as an auth failure", async () => {
+    globalThis.fetch = vi.fn().mockResolvedValue({
+      ok: false,
+      status: 401,
+      statusText: "Unauthorized",
+    });
+
+    await expect(fetch(configJson())).rejects.toMatchObject({ kind: "auth" });
+  });
+
+  it("reports 429 as rate limited with Retry-After", async () => {
+    globalThis.fetch = vi.fn().mockResolvedValue({
+      ok: false,
+      status: 429,
+      statusText: "Too Many Requests",
+      headers: new Headers({ "Retry-After": "120" }),
+    });
+
+    const err = await fetch(configJson()).catch((e) => e);
+    expect(err).toBeInstanceOf(PluginFailure);
+    expect(err.kind).toBe("rate_limited");
+    expect(err.retryAfter).toBe(120);
+  });
+
+  it("reports unreachable hosts as network failures", async () => {
+    globalThis.fetch = vi.fn().mockRejectedValue(new TypeError("fetch failed"));
+
+    await expect(fetch(configJson())).rejects.toMatchObject({
+      kind: "network",
+      message: "Request failed: fetch failed",
+    });
+  });
 });
 
 describe("Jira plugin — validateConnection()", () => {
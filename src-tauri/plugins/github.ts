@@ -1,7 +1,13 @@
 // GitHub Plugin for Nexus Hub
 // Fetches PRs needing review and assigned issues via GitHub REST API v3
 
-import { computeUrgency, fetchWithTimeout, parseCredentials } from "./plugin_interface.ts";
+import {
+  computeUrgency,
+  fetchWithTimeout,
+  httpFailure,
+  parseCredentials,
+  syncWindow,
+} from "./plugin_interface.ts";
 //
 // Config JSON shape:
 //   { "token": "ghp_xxxx" }
@@ -45,33 +51,48 @@ async function githubGet(token: string, path: string): Promise<unknown> {
     },
   });
   if (!res.ok) {
-    throw new Error(`GitHub API error: ${res.status} ${res.statusText} — ${path}`);
+    throw httpFailure(res, `GitHub API error: ${res.status} ${res.statusText} — ${path}`);
   }
   return res.json();
 }
 
+/** Heuristic signals for a search hit; weights mirror the host defaults. */
+function signalsFor(isReviewRequest: boolean): Array<{ reason: string; weight: number }> {
+  if (isReviewRequest) {
+    return [{ reason: "review_requested", weight: 4 }];
+  }
+  // Assigned issue — only a signal, no standalone notification (weight 2 < 3)
+  return [{ reason: "assigned_issue", weight: 2 }];
+}
+
 /** Extract "owner/repo" from a repository_url like https://api.github.com/repos/owner/repo */
 function repoFromUrl(repositoryUrl: string): string {
   const parts = repositoryUrl.split("/");
   return `${parts[parts.length - 2]}/${parts[parts.length - 1]}`;
 }
 
-export async function fetch(configJson: string): Promise<string> {
+export async function fetch(configJson: string, cursor: string | null = null): Promise<string> {
   const config = parseCredentials<GitHubConfig>(configJson, "GitHub");
   const { token } = config;
 
   const now = Math.floor(Date.now() / 1000);
 
+  // Incremental poll: only hits updated since the previous one.
+  const { since, next } = syncWindow(cursor, now);
+  const updated = since === null
+    ? ""
+    : "+" + encodeURIComponent(`updated:>=${new Date(since * 1000).toISOString().replace(/\.\d{3}Z$/, "Z")}`);
+
   // 1. PRs where review is requested from me
   const reviewPRs = (await githubGet(
     token,
-    "/search/issues?q=is:pr+is:open+review-requested:@me&per_page=50&sort=updated",
+    `/search/issues?q=is:pr+is:open+review-requested:@me${updated}&per_page=50&sort=updated`,
   )) as SearchResult;
 
   // 2. Issues assigned to me
   const assignedIssues = (await githubGet(
     token,
-    "/search/issues?q=is:issue+is:open+assignee:@me&per_page=50&sort=updated",
+    `/search/issues?q=is:issue+is:open+assignee:@me${updated}&per_page=50&sort=updated`,
   )) as SearchResult;
 
   // Merge, deduplicate by id
@@ -91,7 +112,7 @@ export async function fetch(configJson: string): Promise<string> {
     }
   }
 
-  const items = all.map(({ issue }) => {
+  const items = all.map(({ issue, isReviewRequest }) => {
     const repo = repoFromUrl(issue.repository_url);
     const isPR = !!issue.pull_request;
     return {
@@ -111,20 +132,13 @@ export async function fetch(configJson: string): Promise<string> {
         isPR,
       },
       tags: issue.labels.map((l) => l.name),
+      signals: signalsFor(isReviewRequest).map((s) => s.reason),
     };
   });
 
   const notifications = all
     .map(({ issue, isReviewRequest }) => {
-      const signals: Array<{ reason: string; weight: number }> = [];
-
-      if (isReviewRequest) {
-        signals.push({ reason: "review_requested", weight: 4 });
-      } else {
-        // Assigned issue — only a signal, no standalone notification (weight 2 < 3)
-        signals.push({ reason: "assigned_issue", weight: 2 });
-      }
-
+      const signals = signalsFor(isReviewRequest);
       const urgency = computeUrgency(signals);
 
       return {
@@ -135,7 +149,11 @@ export async function fetch(configJson: string): Promise<string> {
     })
     .filter((n) => n.urgency !== "low");
 
-  return JSON.stringify({ items, notifications });
+  // Either search capped at 50 hits leaves the rest unlisted, not gone.
+  const truncated = [reviewPRs, assignedIssues].some(
+    (r) => (r.total_count ?? 0) > (r.items ?? []).length,
+  );
+  return JSON.stringify({ items, notifications, cursor: next, partial: since !== null || truncated });
 }
 
 export async function validateConnection(configJson: string): Promise<string> {
@@ -1,7 +1,14 @@
 // Gmail Plugin for Nexus Hub
 // Fetches unread emails from INBOX via Gmail API using OAuth2 refresh token
 
-import { computeUrgency, fetchWithTimeout, parseCredentials } from "./plugin_interface.ts";
+import {
+  computeUrgency,
+  fetchWithTimeout,
+  httpFailure,
+  parseCredentials,
+  PluginFailure,
+  syncWindow,
+} from "./plugin_interface.ts";
 //
 // Config JSON shape:
 //   {
@@ -75,8 +82,10 @@ async function getAccessToken(config: GmailConfig): Promise<string> {
   if (!res.ok) {
     const body = await res.text();
     let hint = "";
+    let revoked = false;
     try {
       const err = JSON.parse(body) as { error?: string; error_description?: string };
+      revoked = ["unauthorized_client", "invalid_client", "invalid_grant"].includes(err.error ?? "");
       if (err.error === "unauthorized_client") {
         hint = " — The refresh token is bound to a different Client ID. You must generate the token with the SAME credentials used in Nexus Hub. Steps: 1) In Google Cloud Console → Credentials, create an OAuth client of type 'Web application'. 2) Add 'https://developers.google.com/oauthplayground' to Authorized redirect URIs. 3) In OAuth Playground, gear icon ⚙ → 'Use your own OAuth credentials' → paste your Client ID & Secret. 4) Authorize scope 'https://www.googleapis.com/auth/gmail.readonly' → Exchange → copy the Refresh token. 5) Use the same Client ID + Secret + Refresh token in Nexus Hub.";
       } else if (err.error === "redirect_uri_mismatch") {
@@ -85,7 +94,8 @@ async function getAccessToken(config: GmailConfig): Promise<string> {
         hint = " — The refresh token has been revoked or expired. Re-generate it in OAuth Playground with the same Client ID & Secret configured in Nexus Hub.";
       }
     } catch { /* ignore parse errors */ }
-    throw new Error(`Failed to refresh Gmail access token: ${res.status}${hint}`);
+    const message = `Failed to refresh Gmail access token: ${res.status}${hint}`;
+    throw revoked ? new PluginFailure("auth", message) : httpFailure(res, message);
   }
   const data = await res.json() as { access_token: string };
   return data.access_token;
@@ -112,7 +122,31 @@ function hasAttachment(part: MessagePart): boolean {
   return false;
 }
 
-export async function fetch(configJson: string): Promise<string> {
+/** Heuristic signals for a mapped email item. */
+function signalsFor(
+  item: { timestamp: number; metadata: { fromEmail: string; hasAttachment: boolean } },
+  vipSet: Set<string>,
+  now: number,
+): Array<{ reason: string; weight: number }> {
+  const signals: Array<{ reason: string; weight: number }> = [];
+
+  if (vipSet.has(item.metadata.fromEmail)) {
+    signals.push({ reason: "vip_sender", weight: 3 });
+  }
+
+  const ageSeconds = now - item.timestamp;
+  if (ageSeconds > 4 * 3600) {
+    signals.push({ reason: "unread_over_4h", weight: 1 });
+  }
+
+  if (item.metadata.hasAttachment) {
+    signals.push({ reason: "has_attachment", weight: 1 });
+  }
+
+  return signals;
+}
+
+export async function fetch(configJson: string, cursor: string | null = null): Promise<string> {
   const config = parseCredentials<GmailConfig>(configJson, "Gmail");
   const vipSet = new Set((config.vipSenders ?? []).map((s) => s.toLowerCase()));
 
@@ -120,15 +154,19 @@ export async function fetch(configJson: string): Promise<string> {
 
   const authHeader = { Authorization: `Bearer ${accessToken}` };
 
+  // Incremental poll: only messages that arrived since the previous one.
+  const { since, next } = syncWindow(cursor, Math.floor(Date.now() / 1000));
+  const query = since === null ? "is:unread" : `is:unread after:${since}`;
+
   // List up to 30 unread messages in INBOX (keep request count low).
   const listRes = await fetchWithTimeout(
-    "https://gmail.googleapis.com/gmail/v1/users/me/messages?labelIds=INBOX&q=is:unread&maxResults=30",
+    `https://gmail.googleapis.com/gmail/v1/users/me/messages?labelIds=INBOX&q=${encodeURIComponent(query)}&maxResults=30`,
     { headers: authHeader },
   );
   if (!listRes.ok) {
-    throw new Error(`Gmail list error: ${listRes.status} ${listRes.statusText}`);
+    throw httpFailure(listRes, `Gmail list error: ${listRes.status} ${listRes.statusText}`);
   }
-  const listData = await listRes.json() as { messages?: MessageListItem[] };
+  const listData = await listRes.json() as { messages?: MessageListItem[]; nextPageToken?: string };
   const messageList: MessageListItem[] = listData.messages ?? [];
 
   // Fetch metadata in batches of 5 to avoid Gmail 429 rate limiting.
@@ -146,7 +184,7 @@ export async function fetch(configJson: string): Promise<string> {
           `https://gmail.googleapis.com/gmail/v1/users/me/messages/${m.id}?${params}`,
           { headers: authHeader },
         );
-        if (!res.ok) throw new Error(`Gmail message fetch error: ${res.status}`);
+        if (!res.ok) throw httpFailure(res, `Gmail message fetch error: ${res.status}`);
         return res.json() as Promise<GmailMessage>;
       }),
     );
@@ -168,6 +206,13 @@ export async function fetch(configJson: string): Promise<string> {
     const fromName = from.replace(/<[^>]+>/, "").trim() || fromEmail;
 
     const attachment = msg.payload?.parts ? msg.payload.parts.some(hasAttachment) : false;
+    const metadata = {
+      from,
+      fromEmail,
+      date,
+      hasAttachment: attachment,
+      threadId: msg.threadId,
+    };
 
     return {
       id: `gmail-${msg.id}`,
@@ -179,36 +224,15 @@ export async function fetch(configJson: string): Promise<string> {
       url: `https://mail.google.com/mail/u/0/#inbox/${msg.threadId}`,
       author: fromName,
       timestamp,
-      metadata: {
-        from,
-        fromEmail,
-        date,
-        hasAttachment: attachment,
-        threadId: msg.threadId,
-      },
+      metadata,
       tags: [] as string[],
+      signals: signalsFor({ timestamp, metadata }, vipSet, now).map((s) => s.reason),
     };
   });
 
-  const notifications = messages
-    .map((msg, i) => {
-      const item = items[i];
-      const signals: Array<{ reason: string; weight: number }> = [];
-
-      const fromEmail = (item.metadata as { fromEmail: string }).fromEmail;
-      if (vipSet.has(fromEmail)) {
-        signals.push({ reason: "vip_sender", weight: 3 });
-      }
-
-      const ageSeconds = now - item.timestamp;
-      if (ageSeconds > 4 * 3600) {
-        signals.push({ reason: "unread_over_4h", weight: 1 });
-      }
-
-      if ((item.metadata as { hasAttachment: boolean }).hasAttachment) {
-        signals.push({ reason: "has_attachment", weight: 1 });
-      }
-
+  const notifications = items
+    .map((item) => {
+      const signals = signalsFor(item, vipSet, now);
       const urgency = computeUrgency(signals);
 
       return {
@@ -219,7 +243,9 @@ export async function fetch(configJson: string): Promise<string> {
     })
     .filter((n) => n.urgency !== "low");
 
-  return JSON.stringify({ items, notifications });
+  // Past the 30-message cap the list says nothing about older messages.
+  const truncated = !!listData.nextPageToken;
+  return JSON.stringify({ items, notifications, cursor: next, partial: since !== null || truncated });
 }
 
 export async function validateConnection(configJson: string): Promise<string> {
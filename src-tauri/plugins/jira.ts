@@ -1,7 +1,13 @@
 // Jira Plugin for Nexus Hub
 // Fetches issues assigned to the configured user from Jira REST API v3
 
-import { computeUrgency, fetchWithTimeout, parseCredentials } from "./plugin_interface.ts";
+import {
+  computeUrgency,
+  fetchWithTimeout,
+  httpFailure,
+  parseCredentials,
+  syncWindow,
+} from "./plugin_interface.ts";
 
 interface JiraConfig {
   baseUrl: string;   // e.g. "https://mycompany.atlassian.net"
@@ -42,13 +48,43 @@ function adfToText(node: AdfNode | null | undefined): string {
   return "";
 }
 
+/** Heuristic signals for an issue; names match the host's heuristic_weights rows. */
+function signalsFor(issue: JiraIssue, now: number): Array<{ reason: string; weight: number }> {
+  const signals: Array<{ reason: string; weight: number }> = [];
+
+  // Signal: assigned to me (always true since JQL filters for it)
+  signals.push({ reason: "assigned_to_me", weight: 3 });
+
+  // Signal: high priority (P1 or Blocker — priorityId "1" or "2")
+  const pId = issue.fields.priority?.id;
+  if (pId === "1" || pId === "2") {
+    signals.push({ reason: "priority_p1_blocker", weight: 4 });
+  }
+
+  // Signal: deadline within 24h
+  if (issue.fields.duedate) {
+    const due = Math.floor(new Date(issue.fields.duedate).getTime() / 1000);
+    if (due - now < 86400 && due > now) {
+      signals.push({ reason: "deadline_24h", weight: 3 });
+    }
+  }
+
+  return signals;
+}
+
 // Entry point — called by Rust plugin runtime
-export async function fetch(configJson: string): Promise<string> {
+export async function fetch(configJson: string, cursor: string | null = null): Promise<string> {
   const config = parseCredentials<JiraConfig>(configJson, "Jira");
   const baseUrl = config.baseUrl.replace(/\/+$/, "");
   const auth = btoa(`${config.email}:${config.apiToken}`);
 
-  const jql = "assignee = currentUser() AND status != Done ORDER BY updated DESC";
+  const now = Math.floor(Date.now() / 1000);
+  const { since, next } = syncWindow(cursor, now);
+
+  // JQL dates are in the Jira user's timezone, so an incremental poll uses a
+  // relative window ("-15m") instead of an absolute timestamp.
+  const updated = since === null ? "" : ` AND updated >= "-${Math.ceil((now - since) / 60)}m"`;
+  const jql = `assignee = currentUser() AND status != Done${updated} ORDER BY updated DESC`;
 
   // Use POST /rest/api/3/search/jql (the current Jira Cloud search endpoint).
   const response = await fetchWithTimeout(`${baseUrl}/rest/api/3/search/jql`, {
@@ -65,14 +101,12 @@ export async function fetch(configJson: string): Promise<string> {
   });
 
   if (!response.ok) {
-    throw new Error(`Jira API error: ${response.status} ${response.statusText}`);
+    throw httpFailure(response, `Jira API error: ${response.status} ${response.statusText}`);
   }
 
   const data = await response.json();
   const issues: JiraIssue[] = data.issues || [];
 
-  const now = Math.floor(Date.now() / 1000);
-
   const items = issues.map((issue) => ({
     id: `jira-${issue.key}`,
     source: "jira",
@@ -93,29 +127,12 @@ export async function fetch(configJson: string): Promise<string> {
       assignee: issue.fields.assignee?.displayName ?? null,
     },
     tags: issue.fields.labels,
+    signals: signalsFor(issue, now).map((s) => s.reason),
   }));
 
   const notifications = issues
     .map((issue) => {
-      const signals: Array<{ reason: string; weight: number }> = [];
-
-      // Signal: assigned to me (always true since JQL filters for it)
-      signals.push({ reason: "assigned_to_you", weight: 3 });
-
-      // Signal: high priority (P1 or Blocker — priorityId "1" or "2")
-      const pId = issue.fields.priority?.id;
-      if (pId === "1" || pId === "2") {
-        signals.push({ reason: "priority_p1_blocker", weight: 4 });
-      }
-
-      // Signal: deadline within 24h
-      if (issue.fields.duedate) {
-        const due = Math.floor(new Date(issue.fields.duedate).getTime() / 1000);
-        if (due - now < 86400 && due > now) {
-          signals.push({ reason: "deadline_24h", weight: 3 });
-        }
-      }
-
+      const signals = signalsFor(issue, now);
       const urgency = computeUrgency(signals);
 
       return {
@@ -126,7 +143,10 @@ export async function fetch(configJson: string): Promise<string> {
     })
     .filter((n) => n.urgency !== "low"); // Only notify for medium+
 
-  return JSON.stringify({ items, notifications });
+  // More matching issues than the 50 returned: the rest are still assigned.
+  const truncated = data.isLast === false || !!data.nextPageToken ||
+    (typeof data.total === "number" && data.total > issues.length);
+  return JSON.stringify({ items, notifications, cursor: next, partial: since !== null || truncated });
 }
 
 // Validate connection — called by Settings panel before saving credentials
@@ -19,6 +19,8 @@ export interface NexusItem {
   timestamp: number; // Unix timestamp (seconds)
   metadata: Record<string, unknown>;
   tags: string[];
+  /** Raw heuristic signal names; the host scores them against stored weights. */
+  signals?: string[];
 }
 
 export interface NexusNotification {
@@ -30,6 +32,17 @@ export interface NexusNotification {
 export interface PluginResult {
   items: NexusItem[];
   notifications: NexusNotification[];
+  /**
+   * Opaque sync state; the host stores it and passes it to the next `fetch`
+   * so the plugin can ask only for changes. Omit it to get a full fetch every poll.
+   */
+  cursor?: string | null;
+  /**
+   * True when `items` only holds changes since `cursor`, or was cut short by
+   * an API page limit. A complete result (the default) archives stored items
+   * the plugin no longer returns.
+   */
+  partial?: boolean;
 }
 
 /** Signal used in urgency computation. */
@@ -47,6 +60,100 @@ export function computeUrgency(signals: Signal[]): "low" | "medium" | "high" | "
   return "low";
 }
 
+/** Re-fetch this much before the previous poll to cover clock skew and indexing lag. */
+const CURSOR_OVERLAP_SECS = 120;
+
+/**
+ * Do a full fetch at least this often, so time-based signals (`unread_over_4h`,
+ * `deadline_24h`) are re-evaluated on items that haven't changed.
+ */
+const FULL_SYNC_INTERVAL_SECS = 3600;
+
+/**
+ * Sync window for the bundled plugins, from the cursor the previous poll
+ * returned. `since` is `null` when this poll must fetch everything; `next` is
+ * the cursor to return with the result.
+ */
+export function syncWindow(
+  cursor: string | null | undefined,
+  now: number,
+): { since: number | null; next: string } {
+  let since: number | null = null;
+  let fullSyncAt = now;
+  try {
+    const prev = JSON.parse(cursor ?? "null") as { since?: unknown; fullSyncAt?: unknown } | null;
+    if (
+      typeof prev?.since === "number" &&
+      typeof prev.fullSyncAt === "number" &&
+      now - prev.fullSyncAt < FULL_SYNC_INTERVAL_SECS
+    ) {
+      since = prev.since;
+      fullSyncAt = prev.fullSyncAt;
+    }
+  } catch {
+    // Unknown cursor format — fall back to a full fetch.
+  }
+  return { since, next: JSON.stringify({ since: now - CURSOR_OVERLAP_SECS, fullSyncAt }) };
+}
+
+/**
+ * Failure categories the host understands. The scheduler reacts to the kind:
+ * `auth` disables the plugin until credentials are re-entered, `rate_limited`
+ * waits `retryAfter` seconds, everything else backs off as usual.
+ */
+export type FailureKind = "auth" | "rate_limited" | "network" | "bad_output" | "failed";
+
+/** Error a plugin throws to report a typed failure to the host. */
+export class PluginFailure extends Error {
+  constructor(
+    public kind: FailureKind,
+    message: string,
+    /** Seconds to wait before the next attempt (rate limits only). */
+    public retryAfter?: number,
+  ) {
+    super(message);
+    this.name = "PluginFailure";
+  }
+}
+
+/** Parse a Retry-After header (delta seconds or HTTP date) into seconds. */
+export function parseRetryAfter(value: string | null | undefined): number | undefined {
+  if (!value) return undefined;
+  const secs = Number(value);
+  if (Number.isFinite(secs)) return Math.max(0, Math.ceil(secs));
+  const date = Date.parse(value);
+  if (Number.isNaN(date)) return undefined;
+  return Math.max(0, Math.ceil((date - Date.now()) / 1000));
+}
+
+/** Seconds until an `X-RateLimit-Reset` time (epoch seconds). */
+function secondsUntilReset(value: string | null | undefined): number | undefined {
+  const reset = Number(value);
+  if (!value || !Number.isFinite(reset)) return undefined;
+  return Math.max(0, Math.ceil(reset - Date.now() / 1000));
+}
+
+/**
+ * Build the failure for a non-OK HTTP response. GitHub answers an exhausted
+ * rate limit with 403 too, so a 403 carrying rate-limit headers is not `auth`.
+ */
+export function httpFailure(res: Response, message: string): PluginFailure {
+  const header = (name: string) => res.headers?.get(name) ?? null;
+  const retryAfter = parseRetryAfter(header("Retry-After"));
+  const exhausted = header("X-RateLimit-Remaining") === "0";
+  if (res.status === 429 || (res.status === 403 && (exhausted || retryAfter !== undefined))) {
+    return new PluginFailure(
+      "rate_limited",
+      message,
+      retryAfter ?? secondsUntilReset(header("X-RateLimit-Reset")),
+    );
+  }
+  if (res.status === 401 || res.status === 403) {
+    return new PluginFailure("auth", message);
+  }
+  return new PluginFailure(res.status >= 500 ? "network" : "failed", message);
+}
+
 /** Fetch with a configurable timeout using AbortController. */
 export async function fetchWithTimeout(
   url: string,
@@ -57,6 +164,8 @@ export async function fetchWithTimeout(
   const timer = setTimeout(() => controller.abort(), timeoutMs);
   try {
     return await globalThis.fetch(url, { ...options, signal: controller.signal });
+  } catch (e) {
+    throw new PluginFailure("network", `Request failed: ${e instanceof Error ? e.message : e}`);
   } finally {
     clearTimeout(timer);
   }
@@ -1,34 +1,204 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use tauri::State;
+use tauri::{Emitter, State};
 
 use crate::db::Database;
-use crate::models::{NexusItem, Notification, PluginConfig};
-use crate::scheduler::Scheduler;
+use crate::models::{
+    HeuristicWeight, ItemFlags, ItemPage, ItemQuery, MaintenanceRun, Notification,
+    NotificationRule, PluginConfig, RuleAction, SavedView, SearchFilters, SearchHit,
+};
+use crate::notifications;
+use crate::plugin_host::PluginHosts;
+use crate::plugin_registry::{PluginManifest, PluginRegistry};
+use crate::plugin_runtime::ConnectionStatus;
+use crate::quiet_hours::QuietSchedule;
+use crate::scheduler::{ActivePolls, Scheduler};
+use crate::scoring;
 
 pub struct AppState {
     pub db: Arc<Mutex<Database>>,
-    pub plugins_dir: PathBuf,
+    pub registry: Arc<Mutex<PluginRegistry>>,
+    pub polls: Arc<ActivePolls>,
+    pub hosts: Arc<PluginHosts>,
 }
 
+/// One page of feed items; see `ItemQuery` for the filters and `ItemPage`
+/// for paging.
 #[tauri::command]
-pub fn get_items(
+pub fn get_items(state: State<AppState>, query: Option<ItemQuery>) -> Result<ItemPage, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_items(&query.unwrap_or_default())
+        .map_err(|e| e.to_string())
+}
+
+/// Full-text search across all sources, best matches first.
+#[tauri::command]
+pub fn search_items(
     state: State<AppState>,
-    source: Option<String>,
-    unread_only: bool,
-    limit: Option<i64>,
-) -> Result<Vec<NexusItem>, String> {
+    query: String,
+    filters: Option<SearchFilters>,
+) -> Result<Vec<SearchHit>, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.get_items(source.as_deref(), unread_only, limit.unwrap_or(100))
+    db.search_items(&query, &filters.unwrap_or_default())
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn get_views(state: State<AppState>) -> Result<Vec<SavedView>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_views().map_err(|e| e.to_string())
+}
+
+/// Create (empty `id`) or update a saved view; returns it as stored.
+#[tauri::command]
+pub fn save_view(state: State<AppState>, view: SavedView) -> Result<SavedView, String> {
+    let name = view.name.trim();
+    if name.is_empty() {
+        return Err("A view needs a name".to_string());
+    }
+    let now = chrono::Utc::now().timestamp();
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let existing = if view.id.is_empty() {
+        None
+    } else {
+        db.get_view(&view.id).map_err(|e| e.to_string())?
+    };
+    let mut stored = SavedView {
+        id: existing
+            .as_ref()
+            .map_or_else(|| uuid::Uuid::new_v4().to_string(), |v| v.id.clone()),
+        name: name.to_string(),
+        created_at: existing.as_ref().map_or(now, |v| v.created_at),
+        updated_at: now,
+        ..view
+    };
+    stored.query.cursor = None;
+    stored.query.limit = None;
+    db.upsert_view(&stored).map_err(|e| e.to_string())?;
+    Ok(stored)
+}
+
+#[tauri::command]
+pub fn delete_view(state: State<AppState>, view_id: String) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.delete_view(&view_id).map_err(|e| e.to_string())
+}
+
+/// One page of the items matching a saved view, evaluated now.
+#[tauri::command]
+pub fn get_view_items(
+    state: State<AppState>,
+    view_id: String,
+    limit: Option<i64>,
+    cursor: Option<String>,
+) -> Result<ItemPage, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let view = db
+        .get_view(&view_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("No saved view '{}'", view_id))?;
+    let query = ItemQuery {
+        limit,
+        cursor,
+        ..view.resolve(chrono::Utc::now().timestamp())
+    };
+    db.get_items(&query).map_err(|e| e.to_string())
+}
+
+/// Unread item count of every saved view, by view id (sidebar badges).
+#[tauri::command]
+pub fn get_view_unread_counts(state: State<AppState>) -> Result<HashMap<String, usize>, String> {
+    let now = chrono::Utc::now().timestamp();
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let views = db.get_views().map_err(|e| e.to_string())?;
+    views
+        .into_iter()
+        .map(|view| {
+            let query = ItemQuery {
+                unread_only: true,
+                ..view.resolve(now)
+            };
+            let count = db.count_items(&query).map_err(|e| e.to_string())?;
+            Ok((view.id, count))
+        })
+        .collect()
+}
+
 #[tauri::command]
 pub fn mark_read(state: State<AppState>, item_id: String, read: bool) -> Result<(), String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
     db.mark_item_read(&item_id, read).map_err(|e| e.to_string())
 }
 
+/// Hide an item and its notifications until `until` (Unix seconds); the
+/// scheduler brings them back and notifies again. `None` un-snoozes.
+#[tauri::command]
+pub fn snooze_item(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    item_id: String,
+    until: Option<i64>,
+) -> Result<(), String> {
+    check_snooze_time(until)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.snooze_item(&item_id, until).map_err(|e| e.to_string())?;
+    notifications::refresh_badge(&app, &db);
+    Ok(())
+}
+
+/// Hide a notification until `until` (Unix seconds); `None` un-snoozes.
+#[tauri::command]
+pub fn snooze_notification(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    notif_id: String,
+    until: Option<i64>,
+) -> Result<(), String> {
+    check_snooze_time(until)?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.snooze_notification(&notif_id, until)
+        .map_err(|e| e.to_string())?;
+    notifications::refresh_badge(&app, &db);
+    Ok(())
+}
+
+fn check_snooze_time(until: Option<i64>) -> Result<(), String> {
+    match until {
+        Some(until) if until <= chrono::Utc::now().timestamp() => {
+            Err("Snooze time must be in the future".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Replace the star, pin, note and labels of an item. These are user-owned:
+/// re-polls never touch them and retention keeps starred and pinned items.
+#[tauri::command]
+pub fn set_item_flags(
+    state: State<AppState>,
+    item_id: String,
+    flags: ItemFlags,
+) -> Result<(), String> {
+    let note = flags
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    let mut labels: Vec<String> = Vec::new();
+    for label in flags.labels.iter().map(|l| l.trim()) {
+        if !label.is_empty() && !labels.iter().any(|l| l == label) {
+            labels.push(label.to_string());
+        }
+    }
+    let flags = ItemFlags {
+        note,
+        labels,
+        ..flags
+    };
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.set_item_flags(&item_id, &flags)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_notifications(state: State<AppState>) -> Result<Vec<Notification>, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
@@ -36,9 +206,16 @@ pub fn get_notifications(state: State<AppState>) -> Result<Vec<Notification>, St
 }
 
 #[tauri::command]
-pub fn dismiss_notification(state: State<AppState>, notif_id: String) -> Result<(), String> {
+pub fn dismiss_notification(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    notif_id: String,
+) -> Result<(), String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.dismiss_notification(&notif_id).map_err(|e| e.to_string())
+    db.dismiss_notification(&notif_id)
+        .map_err(|e| e.to_string())?;
+    notifications::refresh_badge(&app, &db);
+    Ok(())
 }
 
 #[tauri::command]
@@ -47,29 +224,179 @@ pub fn get_plugin_config(
     plugin_id: String,
 ) -> Result<Option<PluginConfig>, String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.get_plugin_config(&plugin_id).map_err(|e| e.to_string())
+    let mut config = db.get_plugin_config(&plugin_id).map_err(|e| e.to_string())?;
+    // The Settings form pre-fills from these, so hand back plaintext.
+    if let Some(c) = config.as_mut() {
+        if let Some(sealed) = c.credentials.as_deref() {
+            c.credentials = Some(db.reveal_credentials(sealed)?);
+        }
+    }
+    Ok(config)
 }
 
 #[tauri::command]
 pub fn save_plugin_config(state: State<AppState>, config: PluginConfig) -> Result<(), String> {
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.upsert_plugin_config(&config).map_err(|e| e.to_string())
+    let stored = db
+        .get_plugin_config(&config.plugin_id)
+        .map_err(|e| e.to_string())?
+        .and_then(|c| c.credentials);
+    let old_credentials = match stored.as_deref() {
+        Some(sealed) => Some(db.reveal_credentials(sealed)?),
+        None => None,
+    };
+    db.save_plugin_config(&config).map_err(|e| e.to_string())?;
+    // New credentials may point at another account or instance; start over
+    // with a full fetch instead of resuming the old sync cursor, and give the
+    // plugin a fresh start if the old ones had tripped the circuit breaker.
+    if old_credentials != config.credentials {
+        db.set_sync_cursor(&config.plugin_id, None, chrono::Utc::now().timestamp())
+            .map_err(|e| e.to_string())?;
+        db.reset_circuit_breaker(&config.plugin_id)
+            .map_err(|e| e.to_string())?;
+    }
+    // Disabling a plugin also stops a poll that is already running.
+    if !config.is_enabled {
+        state.polls.cancel(&config.plugin_id);
+    }
+    Ok(())
 }
 
 #[tauri::command]
-pub fn refresh_plugin(
+pub async fn refresh_plugin(
     app: tauri::AppHandle,
-    state: State<AppState>,
+    state: State<'_, AppState>,
     plugin_id: String,
 ) -> Result<usize, String> {
-    let scheduler = Scheduler::new(state.plugins_dir.clone());
-    scheduler.poll_plugin(&plugin_id, &state.db, &app)
+    // A manual refresh is the user saying "try again now": clear any backoff
+    // and re-enable the plugin if the circuit breaker had tripped.
+    state
+        .db
+        .lock()
+        .map_err(|e| e.to_string())?
+        .reset_circuit_breaker(&plugin_id)
+        .map_err(|e| e.to_string())?;
+
+    // The plugin subprocess blocks, so keep it off the async runtime.
+    let scheduler = Scheduler::new(
+        Arc::clone(&state.registry),
+        Arc::clone(&state.polls),
+        Arc::clone(&state.hosts),
+    );
+    let db = Arc::clone(&state.db);
+    tauri::async_runtime::spawn_blocking(move || scheduler.poll_plugin(&plugin_id, &db, &app))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(String::from)
 }
 
+/// Installed plugins, re-scanned on every call so a plugin dropped into the
+/// plugins directory shows up in Settings without a restart.
 #[tauri::command]
-pub fn dismiss_all_notifications(state: State<AppState>) -> Result<(), String> {
+pub fn list_plugins(state: State<AppState>) -> Result<Vec<PluginManifest>, String> {
+    let mut registry = state.registry.lock().map_err(|e| e.to_string())?;
+    registry.rescan();
     let db = state.db.lock().map_err(|e| e.to_string())?;
-    db.dismiss_all_notifications().map_err(|e| e.to_string())
+    registry.seed_weights(&db).map_err(|e| e.to_string())?;
+    Ok(registry.manifests())
+}
+
+/// Check credentials before saving them — Settings calls this with the form
+/// contents, so a bad token is caught up front instead of on the next poll.
+#[tauri::command]
+pub async fn test_plugin_connection(
+    state: State<'_, AppState>,
+    plugin_id: String,
+    credentials: String,
+) -> Result<ConnectionStatus, String> {
+    // The plugin call blocks on the network, so keep it off the main thread.
+    let scheduler = Scheduler::new(
+        Arc::clone(&state.registry),
+        Arc::clone(&state.polls),
+        Arc::clone(&state.hosts),
+    );
+    let db = Arc::clone(&state.db);
+    tauri::async_runtime::spawn_blocking(move || {
+        scheduler.test_connection(&plugin_id, &credentials, &db)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn dismiss_all_notifications(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.dismiss_all_notifications().map_err(|e| e.to_string())?;
+    notifications::refresh_badge(&app, &db);
+    Ok(())
+}
+
+/// The quiet-hours schedule in effect, including one still kept in the
+/// older `quiet_hours_start` / `quiet_hours_end` settings.
+#[tauri::command]
+pub fn get_quiet_hours(state: State<AppState>) -> Result<QuietSchedule, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(QuietSchedule::load(&db))
+}
+
+#[tauri::command]
+pub fn set_quiet_hours(state: State<AppState>, schedule: QuietSchedule) -> Result<(), String> {
+    schedule.validate()?;
+    let json = serde_json::to_string(&schedule).map_err(|e| e.to_string())?;
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.set_app_setting("quiet_hours_schedule", &json)
+        .map_err(|e| e.to_string())
+}
+
+/// Notification routing rules in the order they are applied.
+#[tauri::command]
+pub fn get_notification_rules(state: State<AppState>) -> Result<Vec<NotificationRule>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_rules().map_err(|e| e.to_string())
+}
+
+/// Create (empty `id`) or update a routing rule; returns it as stored.
+#[tauri::command]
+pub fn save_notification_rule(
+    state: State<AppState>,
+    rule: NotificationRule,
+) -> Result<NotificationRule, String> {
+    let name = rule.name.trim();
+    if name.is_empty() {
+        return Err("A rule needs a name".to_string());
+    }
+    if let RuleAction::OverrideUrgency { urgency } = &rule.action {
+        if !["low", "medium", "high", "critical"].contains(&urgency.as_str()) {
+            return Err(format!("Unknown urgency '{}'", urgency));
+        }
+    }
+    let now = chrono::Utc::now().timestamp();
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    let existing = if rule.id.is_empty() {
+        None
+    } else {
+        db.get_rule(&rule.id).map_err(|e| e.to_string())?
+    };
+    let stored = NotificationRule {
+        id: existing
+            .as_ref()
+            .map_or_else(|| uuid::Uuid::new_v4().to_string(), |r| r.id.clone()),
+        name: name.to_string(),
+        created_at: existing.as_ref().map_or(now, |r| r.created_at),
+        updated_at: now,
+        ..rule
+    };
+    db.upsert_rule(&stored).map_err(|e| e.to_string())?;
+    Ok(stored)
+}
+
+#[tauri::command]
+pub fn delete_notification_rule(state: State<AppState>, rule_id: String) -> Result<(), String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.delete_rule(&rule_id).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -83,3 +410,56 @@ pub fn set_app_setting(state: State<AppState>, key: String, value: String) -> Re
     let db = state.db.lock().map_err(|e| e.to_string())?;
     db.set_app_setting(&key, &value).map_err(|e| e.to_string())
 }
+
+/// Recent database maintenance runs (rows pruned, whether it vacuumed), newest first.
+#[tauri::command]
+pub fn get_maintenance_runs(
+    state: State<AppState>,
+    limit: Option<i64>,
+) -> Result<Vec<MaintenanceRun>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_maintenance_runs(limit.unwrap_or(10))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_heuristic_weights(
+    state: State<AppState>,
+    source: String,
+) -> Result<Vec<HeuristicWeight>, String> {
+    let db = state.db.lock().map_err(|e| e.to_string())?;
+    db.get_weights(&source).map_err(|e| e.to_string())
+}
+
+/// Persist a signal weight and re-score the source's stored items, so the
+/// feed and notifications reflect the change without waiting for the next
+/// poll.
+#[tauri::command]
+pub fn set_heuristic_weight(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    source: String,
+    signal: String,
+    weight: i32,
+) -> Result<usize, String> {
+    let rescored = {
+        let db = state.db.lock().map_err(|e| e.to_string())?;
+        db.upsert_weight(&HeuristicWeight {
+            id: format!("{}-{}", source, signal),
+            source: source.clone(),
+            signal,
+            weight,
+        })
+        .map_err(|e| e.to_string())?;
+        let now = chrono::Utc::now().timestamp();
+        let rescored = scoring::rescore_source(&db, &source, now).map_err(|e| e.to_string())?;
+        notifications::flush_pending(&app, &db, now);
+        notifications::refresh_badge(&app, &db);
+        rescored
+    };
+
+    if let Err(e) = app.emit("items-updated", source.as_str()) {
+        eprintln!("[commands] Failed to emit items-updated: {}", e);
+    }
+    Ok(rescored)
+}
@@ -0,0 +1,203 @@
+//! Credential encryption — seals plugin credentials before they reach SQLite.
+//!
+//! Sealed values look like `enc:v1:<base64(nonce || ciphertext)>` and are
+//! encrypted with ChaCha20-Poly1305. The 256-bit key comes from one of:
+//!   - `NEXUS_HUB_PASSPHRASE` env var, stretched with Argon2id and a per-install
+//!     salt stored in `credentials.salt`
+//!   - a random key stored in `credentials.key` (created on first launch)
+//!
+//! Both files live next to `nexus-hub.db`; copying the database alone is not
+//! enough to recover the tokens.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+const SEALED_PREFIX: &str = "enc:v1:";
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+
+pub const KEY_FILE: &str = "credentials.key";
+pub const SALT_FILE: &str = "credentials.salt";
+pub const PASSPHRASE_ENV: &str = "NEXUS_HUB_PASSPHRASE";
+
+pub struct CredentialCipher {
+    cipher: ChaCha20Poly1305,
+}
+
+impl CredentialCipher {
+    pub fn from_key(key: &[u8; KEY_LEN]) -> Self {
+        Self {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(key)),
+        }
+    }
+
+    /// Derive the key from a passphrase and salt with Argon2id.
+    pub fn from_passphrase(passphrase: &str, salt: &[u8]) -> Result<Self, String> {
+        let mut key = [0u8; KEY_LEN];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|e| format!("Key derivation failed: {}", e))?;
+        Ok(Self::from_key(&key))
+    }
+
+    /// Resolve the cipher for an app data directory: passphrase if
+    /// `NEXUS_HUB_PASSPHRASE` is set, otherwise the local key file.
+    pub fn load(app_dir: &Path) -> Result<Self, String> {
+        match std::env::var(PASSPHRASE_ENV) {
+            Ok(passphrase) if !passphrase.is_empty() => {
+                let salt = read_or_create_secret(&app_dir.join(SALT_FILE), SALT_LEN)?;
+                Self::from_passphrase(&passphrase, &salt)
+            }
+            _ => {
+                let bytes = read_or_create_secret(&app_dir.join(KEY_FILE), KEY_LEN)?;
+                let key: [u8; KEY_LEN] = bytes
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| format!("{} is corrupt (expected {} bytes)", KEY_FILE, KEY_LEN))?;
+                Ok(Self::from_key(&key))
+            }
+        }
+    }
+
+    pub fn seal(&self, plaintext: &str) -> Result<String, String> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|e| format!("Credential encryption failed: {}", e))?;
+
+        let mut blob = nonce.to_vec();
+        blob.extend_from_slice(&ciphertext);
+        Ok(format!("{}{}", SEALED_PREFIX, BASE64.encode(blob)))
+    }
+
+    pub fn open(&self, sealed: &str) -> Result<String, String> {
+        let encoded = sealed
+            .strip_prefix(SEALED_PREFIX)
+            .ok_or("Credentials are not sealed")?;
+        let blob = BASE64
+            .decode(encoded)
+            .map_err(|e| format!("Sealed credentials are corrupt: {}", e))?;
+        if blob.len() < NONCE_LEN {
+            return Err("Sealed credentials are truncated".to_string());
+        }
+
+        let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| "Cannot decrypt credentials — wrong key or passphrase".to_string())?;
+        String::from_utf8(plaintext).map_err(|e| format!("Decrypted credentials are not UTF-8: {}", e))
+    }
+}
+
+pub fn is_sealed(value: &str) -> bool {
+    value.starts_with(SEALED_PREFIX)
+}
+
+/// Read a secret file, or create it with `len` random bytes (owner-only on Unix).
+fn read_or_create_secret(path: &Path, len: usize) -> Result<Vec<u8>, String> {
+    if path.exists() {
+        return fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e));
+    }
+
+    // Reuse the AEAD's CSPRNG so we don't pull in a second RNG crate.
+    let mut secret = Vec::with_capacity(len);
+    while secret.len() < len {
+        secret.extend_from_slice(&ChaCha20Poly1305::generate_key(&mut OsRng));
+    }
+    secret.truncate(len);
+
+    // Create the file with its final mode so the key is never readable by others.
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+    file.write_all(&secret)
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_cipher() -> CredentialCipher {
+        CredentialCipher::from_key(&[7u8; KEY_LEN])
+    }
+
+    #[test]
+    fn seal_open_roundtrip() {
+        let cipher = test_cipher();
+        let sealed = cipher.seal(r#"{"token":"ghp_secret"}"#).unwrap();
+
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.contains("ghp_secret"));
+        assert_eq!(cipher.open(&sealed).unwrap(), r#"{"token":"ghp_secret"}"#);
+    }
+
+    #[test]
+    fn seal_uses_fresh_nonce() {
+        let cipher = test_cipher();
+        assert_ne!(cipher.seal("same").unwrap(), cipher.seal("same").unwrap());
+    }
+
+    #[test]
+    fn open_with_wrong_key_fails() {
+        let sealed = test_cipher().seal("secret").unwrap();
+        let other = CredentialCipher::from_key(&[8u8; KEY_LEN]);
+        assert!(other.open(&sealed).unwrap_err().contains("wrong key"));
+    }
+
+    #[test]
+    fn open_rejects_plaintext_and_garbage() {
+        let cipher = test_cipher();
+        assert!(cipher.open(r#"{"token":"x"}"#).is_err());
+        assert!(cipher.open("enc:v1:!!!").is_err());
+        assert!(cipher.open("enc:v1:AAAA").is_err());
+    }
+
+    #[test]
+    fn secret_file_is_created_once() {
+        let path = std::env::temp_dir().join(format!("nexus-key-{}", uuid::Uuid::new_v4()));
+        let first = read_or_create_secret(&path, KEY_LEN).unwrap();
+        let second = read_or_create_secret(&path, KEY_LEN).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(first.len(), KEY_LEN);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn passphrase_derivation_is_deterministic() {
+        let salt = [1u8; SALT_LEN];
+        let a = CredentialCipher::from_passphrase("hunter2", &salt).unwrap();
+        let b = CredentialCipher::from_passphrase("hunter2", &salt).unwrap();
+        let sealed = a.seal("secret").unwrap();
+        assert_eq!(b.open(&sealed).unwrap(), "secret");
+
+        let c = CredentialCipher::from_passphrase("hunter3", &salt).unwrap();
+        assert!(c.open(&sealed).is_err());
+    }
+}
@@ -1,146 +1,137 @@
 use rusqlite::{params, Connection, Result};
+use std::collections::HashSet;
 use std::path::PathBuf;
 
-use crate::models::{HeuristicWeight, NexusItem, Notification, PluginConfig};
+use crate::crypto::{self, CredentialCipher};
+use crate::migrations;
+use crate::models::{
+    HeuristicWeight, ItemFlags, ItemPage, ItemQuery, ItemSort, MaintenanceRun, NexusItem,
+    Notification, NotificationRule, PluginConfig, SavedView, SearchFilters, SearchHit,
+};
+
+/// Number of maintenance runs kept in `maintenance_runs`.
+const MAINTENANCE_RUNS_KEPT: i64 = 50;
+
+/// Default page size of `get_items`.
+const ITEM_PAGE_SIZE: i64 = 100;
+
+/// Default number of hits returned by `search_items`.
+const SEARCH_LIMIT: i64 = 20;
+
+/// `items` joined with their user flags, as read by `item_from_row`.
+const ITEM_COLUMNS: &str =
+    "items.*, coalesce(f.starred, 0), coalesce(f.pinned, 0), f.note, f.labels";
+const ITEM_FLAGS_JOIN: &str = "LEFT JOIN item_flags f ON f.item_id = items.id";
+
+/// Retention never deletes starred or pinned items.
+const NOT_KEPT_BY_USER: &str =
+    "id NOT IN (SELECT item_id FROM item_flags WHERE starred = 1 OR pinned = 1)";
+
+/// Markers around matched terms in `SearchHit` highlights (ASCII STX / ETX).
+/// Not HTML, so the frontend never has to render item text as markup.
+const HIGHLIGHT_START: &str = "\u{2}";
+const HIGHLIGHT_END: &str = "\u{3}";
+
+/// Items and notifications whose snooze ended, from `wake_snoozed`.
+pub struct WokenSnoozes {
+    pub items: Vec<NexusItem>,
+    /// Each with the title of its item.
+    pub notifications: Vec<(Notification, String)>,
+}
+
+/// A notification waiting for native delivery, from `get_undelivered_notifications`.
+pub struct PendingDelivery {
+    pub notification: Notification,
+    pub title: String,  // of its item
+    pub source: String, // of its item
+}
 
 pub struct Database {
     conn: Connection,
+    /// Seals `plugin_config.credentials` on write. `None` only in tests.
+    cipher: Option<CredentialCipher>,
 }
 
 impl Database {
     pub fn new(db_path: PathBuf) -> Result<Self> {
         let conn = Connection::open(db_path)?;
-        let db = Self { conn };
+        let db = Self { conn, cipher: None };
         db.run_migrations()?;
         Ok(db)
     }
 
-    fn run_migrations(&self) -> Result<()> {
-        // Enable foreign key enforcement (off by default in SQLite).
-        self.conn.execute_batch("PRAGMA foreign_keys = ON;")?;
-
-        self.conn.execute_batch(
-            "
-            CREATE TABLE IF NOT EXISTS items (
-                id TEXT PRIMARY KEY,
-                source TEXT NOT NULL,
-                source_id TEXT NOT NULL,
-                item_type TEXT NOT NULL,
-                title TEXT NOT NULL,
-                summary TEXT,
-                url TEXT NOT NULL,
-                author TEXT,
-                timestamp INTEGER NOT NULL,
-                priority INTEGER DEFAULT 0,
-                metadata TEXT,
-                tags TEXT,
-                is_read INTEGER DEFAULT 0,
-                created_at INTEGER NOT NULL,
-                updated_at INTEGER NOT NULL,
-                UNIQUE(source, source_id)
-            );
-
-            CREATE TABLE IF NOT EXISTS notifications (
-                id TEXT PRIMARY KEY,
-                item_id TEXT NOT NULL REFERENCES items(id) ON DELETE CASCADE,
-                reason TEXT NOT NULL,
-                urgency TEXT NOT NULL,
-                is_dismissed INTEGER DEFAULT 0,
-                created_at INTEGER NOT NULL
-            );
-
-            CREATE TABLE IF NOT EXISTS plugin_config (
-                plugin_id TEXT PRIMARY KEY,
-                is_enabled INTEGER DEFAULT 1,
-                credentials TEXT,
-                poll_interval_secs INTEGER DEFAULT 600,
-                last_poll_at INTEGER,
-                last_error TEXT,
-                error_count INTEGER DEFAULT 0,
-                settings TEXT
-            );
-
-            CREATE TABLE IF NOT EXISTS app_settings (
-                key TEXT PRIMARY KEY,
-                value TEXT NOT NULL
-            );
-
-            CREATE TABLE IF NOT EXISTS heuristic_weights (
-                id TEXT PRIMARY KEY,
-                source TEXT NOT NULL,
-                signal TEXT NOT NULL,
-                weight INTEGER NOT NULL,
-                UNIQUE(source, signal)
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_items_source ON items(source);
-            CREATE INDEX IF NOT EXISTS idx_items_timestamp ON items(timestamp DESC);
-            CREATE INDEX IF NOT EXISTS idx_items_priority ON items(priority DESC);
-            CREATE INDEX IF NOT EXISTS idx_items_is_read ON items(is_read);
-            CREATE INDEX IF NOT EXISTS idx_notifications_urgency ON notifications(urgency);
-            CREATE INDEX IF NOT EXISTS idx_notifications_dismissed ON notifications(is_dismissed);
-        ",
-        )?;
-
-        // Migration: recreate notifications table with ON DELETE CASCADE if it exists
-        // without it. This handles existing dev databases created before the CASCADE was added.
-        self.migrate_notifications_cascade()?;
-
-        Ok(())
+    /// Install the credential cipher and seal any credentials still stored as
+    /// plaintext (rows written before encryption existed). Returns the number
+    /// of rows migrated.
+    pub fn enable_encryption(&mut self, cipher: CredentialCipher) -> Result<usize> {
+        self.cipher = Some(cipher);
+        self.encrypt_plaintext_credentials()
     }
 
-    /// Recreate the notifications table with ON DELETE CASCADE if the FK lacks it.
-    fn migrate_notifications_cascade(&self) -> Result<()> {
-        // Check if the table's FK already has CASCADE by inspecting the schema SQL.
-        let schema: String = self
-            .conn
-            .query_row(
-                "SELECT sql FROM sqlite_master WHERE type='table' AND name='notifications'",
-                [],
-                |row| row.get(0),
-            )
-            .unwrap_or_default();
+    fn encrypt_plaintext_credentials(&self) -> Result<usize> {
+        let plaintext: Vec<(String, String)> = {
+            let mut stmt = self.conn.prepare(
+                "SELECT plugin_id, credentials FROM plugin_config WHERE credentials IS NOT NULL",
+            )?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<Vec<(String, String)>>>()?;
+            rows.into_iter()
+                .filter(|(_, creds)| !crypto::is_sealed(creds))
+                .collect()
+        };
 
-        if schema.contains("ON DELETE CASCADE") {
-            return Ok(()); // already migrated
+        for (plugin_id, creds) in &plaintext {
+            self.conn.execute(
+                "UPDATE plugin_config SET credentials = ?1 WHERE plugin_id = ?2",
+                params![self.seal_credentials(creds)?, plugin_id],
+            )?;
         }
+        Ok(plaintext.len())
+    }
 
-        // Recreate via the standard SQLite migration pattern: rename → create → copy → drop.
-        self.conn.execute_batch(
-            "
-            ALTER TABLE notifications RENAME TO _notifications_old;
-
-            CREATE TABLE notifications (
-                id TEXT PRIMARY KEY,
-                item_id TEXT NOT NULL REFERENCES items(id) ON DELETE CASCADE,
-                reason TEXT NOT NULL,
-                urgency TEXT NOT NULL,
-                is_dismissed INTEGER DEFAULT 0,
-                created_at INTEGER NOT NULL
-            );
-
-            INSERT INTO notifications SELECT * FROM _notifications_old;
-            DROP TABLE _notifications_old;
+    fn seal_credentials(&self, creds: &str) -> Result<String> {
+        match &self.cipher {
+            Some(cipher) if !crypto::is_sealed(creds) => cipher
+                .seal(creds)
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into())),
+            _ => Ok(creds.to_string()),
+        }
+    }
 
-            CREATE INDEX IF NOT EXISTS idx_notifications_urgency ON notifications(urgency);
-            CREATE INDEX IF NOT EXISTS idx_notifications_dismissed ON notifications(is_dismissed);
-        ",
-        )?;
+    /// Decrypt credentials read from `plugin_config`. Plaintext values (only
+    /// possible when no cipher is installed) are passed through unchanged.
+    pub fn reveal_credentials(&self, stored: &str) -> std::result::Result<String, String> {
+        if !crypto::is_sealed(stored) {
+            return Ok(stored.to_string());
+        }
+        match &self.cipher {
+            Some(cipher) => cipher.open(stored),
+            None => Err("Credentials are encrypted but no key is loaded".to_string()),
+        }
+    }
 
-        Ok(())
+    fn run_migrations(&self) -> Result<()> {
+        // Enable foreign key enforcement (off by default in SQLite).
+        self.conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        migrations::run(&self.conn)
     }
 
     // -- Items --
 
+    /// Insert or update an item and re-index it in `items_fts`.
     pub fn upsert_item(&self, item: &NexusItem) -> Result<()> {
-        self.conn.execute(
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
             "INSERT INTO items (id, source, source_id, item_type, title, summary, url, author,
-                               timestamp, priority, metadata, tags, is_read, created_at, updated_at)
-             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15)
+                               timestamp, priority, metadata, tags, is_read, created_at, updated_at,
+                               signals, archived_at, snoozed_until)
+             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18)
              ON CONFLICT(source, source_id) DO UPDATE SET
                 title=excluded.title, summary=excluded.summary, url=excluded.url,
                 author=excluded.author, timestamp=excluded.timestamp, priority=excluded.priority,
-                metadata=excluded.metadata, tags=excluded.tags, updated_at=excluded.updated_at",
+                metadata=excluded.metadata, tags=excluded.tags, updated_at=excluded.updated_at,
+                signals=excluded.signals, archived_at=excluded.archived_at",
             params![
                 item.id,
                 item.source,
@@ -157,56 +148,182 @@ impl Database {
                 item.is_read as i32,
                 item.created_at,
                 item.updated_at,
+                item.signals,
+                item.archived_at,
+                item.snoozed_until,
             ],
         )?;
-        Ok(())
+        // On conflict the stored row keeps its original id, so look it up.
+        // The fts row is replaced by its rowid from `items_fts_keys`.
+        tx.execute(
+            "INSERT OR IGNORE INTO items_fts_keys (item_id)
+                SELECT id FROM items WHERE source = ?1 AND source_id = ?2",
+            params![item.source, item.source_id],
+        )?;
+        tx.execute(
+            "DELETE FROM items_fts WHERE rowid =
+                (SELECT k.fts_rowid FROM items_fts_keys k JOIN items i ON i.id = k.item_id
+                 WHERE i.source = ?1 AND i.source_id = ?2)",
+            params![item.source, item.source_id],
+        )?;
+        tx.execute(
+            &format!(
+                "INSERT INTO items_fts (rowid, item_id, title, summary, author, tags, metadata)
+                 SELECT k.fts_rowid, r.* FROM ({} WHERE i.source = ?1 AND i.source_id = ?2) r
+                 JOIN items_fts_keys k ON k.item_id = r.id",
+                migrations::ITEMS_FTS_ROW
+            ),
+            params![item.source, item.source_id],
+        )?;
+        tx.commit()
     }
 
-    pub fn get_items(
-        &self,
-        source: Option<&str>,
-        unread_only: bool,
-        limit: i64,
-    ) -> Result<Vec<NexusItem>> {
-        let mut sql = String::from("SELECT * FROM items WHERE 1=1");
-        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![];
+    /// One page of live items (archived ones only if `include_archived`)
+    /// matching `query`: pinned items first, then in `query.sort` order.
+    /// Pass the returned `next_cursor` back as `query.cursor` for the
+    /// following page.
+    pub fn get_items(&self, query: &ItemQuery) -> Result<ItemPage> {
+        let (filter, mut param_values) = item_filter(query);
+        let mut sql = format!(
+            "SELECT {} FROM items {} {}",
+            ITEM_COLUMNS, ITEM_FLAGS_JOIN, filter
+        );
+        let column = query.sort.column();
+        if let Some(cursor) = &query.cursor {
+            let (pinned, value, id) = decode_cursor(query.sort, cursor)?;
+            sql.push_str(&format!(
+                " AND (coalesce(f.pinned, 0), {}, id) < (?, ?, ?)",
+                column
+            ));
+            param_values.push(Box::new(pinned));
+            param_values.push(Box::new(value));
+            param_values.push(Box::new(id));
+        }
+        // One extra row tells whether there is a next page.
+        let limit = query.limit.unwrap_or(ITEM_PAGE_SIZE).max(1);
+        sql.push_str(&format!(
+            " ORDER BY coalesce(f.pinned, 0) DESC, {} DESC, id DESC LIMIT ?",
+            column
+        ));
+        param_values.push(Box::new(limit + 1));
+
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+            param_values.iter().map(|p| p.as_ref()).collect();
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut items = stmt
+            .query_map(params_refs.as_slice(), item_from_row)?
+            .collect::<Result<Vec<_>>>()?;
+        let next_cursor = if items.len() as i64 > limit {
+            items.truncate(limit as usize);
+            items.last().map(|last| encode_cursor(query.sort, last))
+        } else {
+            None
+        };
+        Ok(ItemPage { items, next_cursor })
+    }
+
+    /// Number of items matching the filters of `query`.
+    pub fn count_items(&self, query: &ItemQuery) -> Result<usize> {
+        let (filter, param_values) = item_filter(query);
+        let params_refs: Vec<&dyn rusqlite::types::ToSql> =
+            param_values.iter().map(|p| p.as_ref()).collect();
+        self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM items {} {}", ITEM_FLAGS_JOIN, filter),
+            params_refs.as_slice(),
+            |row| row.get::<_, i64>(0).map(|n| n as usize),
+        )
+    }
+
+    /// Full-text search over title, summary, author, tags and metadata,
+    /// best matches first. Every word of `query` is matched as a prefix; an
+    /// empty query returns no hits.
+    pub fn search_items(&self, query: &str, filters: &SearchFilters) -> Result<Vec<SearchHit>> {
+        let Some(expression) = fts_match_expression(query) else {
+            return Ok(Vec::new());
+        };
+
+        // Column weights for bm25(): item_id, title, summary, author, tags, metadata.
+        let mut sql = format!(
+            "SELECT {},
+                    highlight(items_fts, 1, ?1, ?2),
+                    snippet(items_fts, -1, ?1, ?2, '…', 16),
+                    -bm25(items_fts, 0.0, 10.0, 4.0, 2.0, 2.0, 1.0) AS score
+             FROM items_fts JOIN items ON items.id = items_fts.item_id {}
+             WHERE items_fts MATCH ?3",
+            ITEM_COLUMNS, ITEM_FLAGS_JOIN
+        );
+        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![
+            Box::new(HIGHLIGHT_START),
+            Box::new(HIGHLIGHT_END),
+            Box::new(expression),
+        ];
 
-        if let Some(s) = source {
-            sql.push_str(" AND source = ?");
-            param_values.push(Box::new(s.to_string()));
+        if !filters.sources.is_empty() {
+            let placeholders = vec!["?"; filters.sources.len()].join(", ");
+            sql.push_str(&format!(" AND items.source IN ({})", placeholders));
+            for source in &filters.sources {
+                param_values.push(Box::new(source.clone()));
+            }
         }
-        if unread_only {
-            sql.push_str(" AND is_read = 0");
+        if filters.unread_only {
+            sql.push_str(" AND items.is_read = 0");
         }
-        sql.push_str(" ORDER BY timestamp DESC LIMIT ?");
-        param_values.push(Box::new(limit));
+        if !filters.include_archived {
+            sql.push_str(" AND items.archived_at IS NULL");
+        }
+        sql.push_str(" ORDER BY score DESC, items.timestamp DESC LIMIT ?");
+        param_values.push(Box::new(filters.limit.unwrap_or(SEARCH_LIMIT)));
 
         let params_refs: Vec<&dyn rusqlite::types::ToSql> =
             param_values.iter().map(|p| p.as_ref()).collect();
 
         let mut stmt = self.conn.prepare(&sql)?;
-        let items = stmt
+        let hits = stmt
             .query_map(params_refs.as_slice(), |row| {
-                Ok(NexusItem {
-                    id: row.get(0)?,
-                    source: row.get(1)?,
-                    source_id: row.get(2)?,
-                    item_type: row.get(3)?,
-                    title: row.get(4)?,
-                    summary: row.get(5)?,
-                    url: row.get(6)?,
-                    author: row.get(7)?,
-                    timestamp: row.get(8)?,
-                    priority: row.get(9)?,
-                    metadata: row.get(10)?,
-                    tags: row.get(11)?,
-                    is_read: row.get::<_, i32>(12)? != 0,
-                    created_at: row.get(13)?,
-                    updated_at: row.get(14)?,
+                Ok(SearchHit {
+                    item: item_from_row(row)?,
+                    title_highlight: row.get(22)?,
+                    snippet: row.get(23)?,
+                    score: row.get(24)?,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
-        Ok(items)
+        Ok(hits)
+    }
+
+    pub fn get_item(&self, item_id: &str) -> Result<Option<NexusItem>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM items {} WHERE id = ?1",
+            ITEM_COLUMNS, ITEM_FLAGS_JOIN
+        ))?;
+        let mut rows = stmt.query_map(params![item_id], item_from_row)?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Return `(id, signals, is_archived)` for every item of a source, for
+    /// re-scoring.
+    pub fn get_item_signals(&self, source: &str) -> Result<Vec<(String, Option<String>, bool)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, signals, archived_at IS NOT NULL FROM items WHERE source = ?1")?;
+        let rows = stmt
+            .query_map(params![source], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    pub fn set_item_priority(&self, item_id: &str, priority: i32) -> Result<()> {
+        self.conn.execute(
+            "UPDATE items SET priority = ?1 WHERE id = ?2",
+            params![priority, item_id],
+        )?;
+        Ok(())
     }
 
     pub fn mark_item_read(&self, item_id: &str, read: bool) -> Result<()> {
@@ -217,12 +334,254 @@ impl Database {
         Ok(())
     }
 
+    /// Hide an item, and its active notifications, until `until` (`None`
+    /// shows them again now).
+    pub fn snooze_item(&self, item_id: &str, until: Option<i64>) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE items SET snoozed_until = ?1 WHERE id = ?2",
+            params![until, item_id],
+        )?;
+        tx.execute(
+            "UPDATE notifications SET snoozed_until = ?1 WHERE item_id = ?2 AND is_dismissed = 0",
+            params![until, item_id],
+        )?;
+        tx.commit()
+    }
+
+    /// Replace the user flags of an item; clearing every flag drops the row.
+    pub fn set_item_flags(&self, item_id: &str, flags: &ItemFlags) -> Result<()> {
+        if flags.is_empty() {
+            self.conn.execute(
+                "DELETE FROM item_flags WHERE item_id = ?1",
+                params![item_id],
+            )?;
+            return Ok(());
+        }
+        let labels = serde_json::to_string(&flags.labels)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+        self.conn.execute(
+            "INSERT INTO item_flags (item_id, starred, pinned, note, labels)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(item_id) DO UPDATE SET
+                starred = excluded.starred,
+                pinned = excluded.pinned,
+                note = excluded.note,
+                labels = excluded.labels",
+            params![
+                item_id,
+                flags.starred as i32,
+                flags.pinned as i32,
+                flags.note,
+                labels
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Clear every snooze that ended at or before `now`, returning the live
+    /// items and undismissed notifications that woke up.
+    pub fn wake_snoozed(&self, now: i64) -> Result<WokenSnoozes> {
+        let tx = self.conn.unchecked_transaction()?;
+        let items = {
+            let mut stmt = tx.prepare(&format!(
+                "SELECT {} FROM items {}
+                 WHERE snoozed_until <= ?1 AND archived_at IS NULL",
+                ITEM_COLUMNS, ITEM_FLAGS_JOIN
+            ))?;
+            let rows = stmt.query_map(params![now], item_from_row)?;
+            rows.collect::<Result<Vec<_>>>()?
+        };
+        let notifications = {
+            let mut stmt = tx.prepare(
+                "SELECT n.*, i.title FROM notifications n JOIN items i ON i.id = n.item_id
+                 WHERE n.snoozed_until <= ?1 AND n.is_dismissed = 0",
+            )?;
+            let rows = stmt.query_map(params![now], |row| {
+                Ok((notification_from_row(row)?, row.get(9)?))
+            })?;
+            rows.collect::<Result<Vec<_>>>()?
+        };
+        tx.execute(
+            "UPDATE items SET snoozed_until = NULL WHERE snoozed_until <= ?1",
+            params![now],
+        )?;
+        tx.execute(
+            // Waking announces the notification (again), so it counts as
+            // delivered and the escalation timer restarts.
+            "UPDATE notifications SET snoozed_until = NULL, escalated_at = ?1, delivered_at = ?1
+             WHERE snoozed_until <= ?1",
+            params![now],
+        )?;
+        tx.commit()?;
+        Ok(WokenSnoozes {
+            items,
+            notifications,
+        })
+    }
+
+    /// Archive the live items of `source` whose `source_id` is not in `seen`
+    /// (they disappeared upstream: merged, closed, reassigned, read) and
+    /// dismiss their active notifications. Returns the archived item IDs.
+    pub fn archive_missing_items(
+        &self,
+        source: &str,
+        seen: &HashSet<&str>,
+        now: i64,
+    ) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, source_id FROM items WHERE source = ?1 AND archived_at IS NULL")?;
+        let missing: Vec<String> = stmt
+            .query_map(params![source], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|(_, source_id)| !seen.contains(source_id.as_str()))
+            .map(|(id, _)| id)
+            .collect();
+
+        let tx = self.conn.unchecked_transaction()?;
+        for id in &missing {
+            tx.execute(
+                "UPDATE items SET archived_at = ?1 WHERE id = ?2",
+                params![now, id],
+            )?;
+            tx.execute(
+                "UPDATE notifications SET is_dismissed = 1 WHERE item_id = ?1 AND is_dismissed = 0",
+                params![id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(missing)
+    }
+
+    // -- Saved views --
+
+    pub fn upsert_view(&self, view: &SavedView) -> Result<()> {
+        let mut query = view.query.clone();
+        query.cursor = None;
+        query.limit = None;
+        let query_json = serde_json::to_string(&query)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+        self.conn.execute(
+            "INSERT INTO saved_views (id, name, query, min_age_secs, max_age_secs, position,
+                                      created_at, updated_at)
+             VALUES (?1,?2,?3,?4,?5,?6,?7,?8)
+             ON CONFLICT(id) DO UPDATE SET
+                name=excluded.name, query=excluded.query, min_age_secs=excluded.min_age_secs,
+                max_age_secs=excluded.max_age_secs, position=excluded.position,
+                updated_at=excluded.updated_at",
+            params![
+                view.id,
+                view.name,
+                query_json,
+                view.min_age_secs,
+                view.max_age_secs,
+                view.position,
+                view.created_at,
+                view.updated_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// All saved views in sidebar order.
+    pub fn get_views(&self) -> Result<Vec<SavedView>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM saved_views ORDER BY position, name")?;
+        let views = stmt
+            .query_map([], view_from_row)?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(views)
+    }
+
+    pub fn get_view(&self, view_id: &str) -> Result<Option<SavedView>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM saved_views WHERE id = ?1")?;
+        let mut rows = stmt.query_map(params![view_id], view_from_row)?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn delete_view(&self, view_id: &str) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM saved_views WHERE id = ?1", params![view_id])?;
+        Ok(())
+    }
+
+    // -- Notification rules --
+
+    pub fn upsert_rule(&self, rule: &NotificationRule) -> Result<()> {
+        let conditions = serde_json::to_string(&rule.conditions)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+        let action = serde_json::to_string(&rule.action)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+        self.conn.execute(
+            "INSERT INTO notification_rules (id, name, is_enabled, position, conditions, action,
+                                             created_at, updated_at)
+             VALUES (?1,?2,?3,?4,?5,?6,?7,?8)
+             ON CONFLICT(id) DO UPDATE SET
+                name=excluded.name, is_enabled=excluded.is_enabled, position=excluded.position,
+                conditions=excluded.conditions, action=excluded.action,
+                updated_at=excluded.updated_at",
+            params![
+                rule.id,
+                rule.name,
+                rule.is_enabled as i32,
+                rule.position,
+                conditions,
+                action,
+                rule.created_at,
+                rule.updated_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// All routing rules in evaluation order.
+    pub fn get_rules(&self) -> Result<Vec<NotificationRule>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM notification_rules ORDER BY position, created_at, id")?;
+        let rules = stmt
+            .query_map([], rule_from_row)?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(rules)
+    }
+
+    pub fn get_rule(&self, rule_id: &str) -> Result<Option<NotificationRule>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM notification_rules WHERE id = ?1")?;
+        let mut rows = stmt.query_map(params![rule_id], rule_from_row)?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn delete_rule(&self, rule_id: &str) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM notification_rules WHERE id = ?1",
+            params![rule_id],
+        )?;
+        Ok(())
+    }
+
     // -- Notifications --
 
     pub fn insert_notification(&self, notif: &Notification) -> Result<()> {
         self.conn.execute(
-            "INSERT OR IGNORE INTO notifications (id, item_id, reason, urgency, is_dismissed, created_at)
-             VALUES (?1,?2,?3,?4,?5,?6)",
+            "INSERT OR IGNORE INTO notifications (id, item_id, reason, urgency, is_dismissed,
+                                                  created_at, snoozed_until, escalated_at,
+                                                  delivered_at)
+             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9)",
             params![
                 notif.id,
                 notif.item_id,
@@ -230,28 +589,144 @@ impl Database {
                 notif.urgency,
                 notif.is_dismissed as i32,
                 notif.created_at,
+                notif.snoozed_until,
+                notif.escalated_at,
+                notif.delivered_at,
             ],
         )?;
         Ok(())
     }
 
+    /// Undismissed notifications that aren't snoozed, newest first.
     pub fn get_active_notifications(&self) -> Result<Vec<Notification>> {
         let mut stmt = self.conn.prepare(
-            "SELECT * FROM notifications WHERE is_dismissed = 0 ORDER BY created_at DESC",
+            "SELECT * FROM notifications WHERE is_dismissed = 0 AND snoozed_until IS NULL
+             ORDER BY created_at DESC",
         )?;
         let notifs = stmt
+            .query_map([], notification_from_row)?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(notifs)
+    }
+
+    /// Active notifications of `urgency` on live items that were last
+    /// announced (created, woken or re-escalated) at or before `before`, with
+    /// their item's title.
+    pub fn get_overdue_notifications(
+        &self,
+        urgency: &str,
+        before: i64,
+    ) -> Result<Vec<(Notification, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT n.*, i.title FROM notifications n JOIN items i ON i.id = n.item_id
+             WHERE n.urgency = ?1 AND n.is_dismissed = 0 AND n.snoozed_until IS NULL
+               AND i.archived_at IS NULL AND coalesce(n.escalated_at, n.created_at) <= ?2
+             ORDER BY n.created_at",
+        )?;
+        let rows = stmt
+            .query_map(params![urgency, before], |row| {
+                Ok((notification_from_row(row)?, row.get(9)?))
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+
+    /// Active notifications not yet shown natively, oldest first.
+    pub fn get_undelivered_notifications(&self) -> Result<Vec<PendingDelivery>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT n.*, i.title, i.source FROM notifications n JOIN items i ON i.id = n.item_id
+             WHERE n.delivered_at IS NULL AND n.is_dismissed = 0 AND n.snoozed_until IS NULL
+             ORDER BY n.created_at, n.id",
+        )?;
+        let rows = stmt
             .query_map([], |row| {
-                Ok(Notification {
-                    id: row.get(0)?,
-                    item_id: row.get(1)?,
-                    reason: row.get(2)?,
-                    urgency: row.get(3)?,
-                    is_dismissed: row.get::<_, i32>(4)? != 0,
-                    created_at: row.get(5)?,
+                Ok(PendingDelivery {
+                    notification: notification_from_row(row)?,
+                    title: row.get(9)?,
+                    source: row.get(10)?,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
-        Ok(notifs)
+        Ok(rows)
+    }
+
+    pub fn mark_notifications_delivered(&self, notif_ids: &[&str], at: i64) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for id in notif_ids {
+            tx.execute(
+                "UPDATE notifications SET delivered_at = ?1 WHERE id = ?2",
+                params![at, id],
+            )?;
+        }
+        tx.commit()
+    }
+
+    pub fn set_notification_urgency(&self, notif_id: &str, urgency: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE notifications SET urgency = ?1 WHERE id = ?2",
+            params![urgency, notif_id],
+        )?;
+        Ok(())
+    }
+
+    /// The urgency `item_id` was last notified (or quietly downgraded) at.
+    pub fn get_notified_urgency(&self, item_id: &str) -> Result<Option<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT urgency FROM notified_urgency WHERE item_id = ?1")?;
+        let mut rows = stmt.query_map(params![item_id], |row| row.get(0))?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn set_notified_urgency(&self, item_id: &str, urgency: &str, now: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO notified_urgency (item_id, urgency, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(item_id) DO UPDATE SET
+                urgency = excluded.urgency, updated_at = excluded.updated_at",
+            params![item_id, urgency, now],
+        )?;
+        Ok(())
+    }
+
+    /// Move the item's active notifications down to `to`, whatever urgency
+    /// they were stored at, without delivering anything again. Returns how
+    /// many changed.
+    pub fn downgrade_notifications(&self, item_id: &str, to: &str) -> Result<usize> {
+        self.conn.execute(
+            "UPDATE notifications SET urgency = ?2
+             WHERE item_id = ?1 AND urgency != ?2 AND is_dismissed = 0",
+            params![item_id, to],
+        )
+    }
+
+    /// Dismiss every active notification of an item, e.g. when a more urgent
+    /// one supersedes them.
+    pub fn dismiss_item_notifications(&self, item_id: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE notifications SET is_dismissed = 1 WHERE item_id = ?1 AND is_dismissed = 0",
+            params![item_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn mark_notification_escalated(&self, notif_id: &str, at: i64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE notifications SET escalated_at = ?1 WHERE id = ?2",
+            params![at, notif_id],
+        )?;
+        Ok(())
+    }
+
+    /// Hide a notification until `until` (`None` shows it again now).
+    pub fn snooze_notification(&self, notif_id: &str, until: Option<i64>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE notifications SET snoozed_until = ?1 WHERE id = ?2",
+            params![until, notif_id],
+        )?;
+        Ok(())
     }
 
     pub fn dismiss_notification(&self, notif_id: &str) -> Result<()> {
@@ -319,6 +794,10 @@ impl Database {
                 last_error: row.get(5)?,
                 error_count: row.get(6)?,
                 settings: row.get(7)?,
+                last_error_at: row.get(8)?,
+                auto_disabled_at: row.get(9)?,
+                last_error_kind: row.get(10)?,
+                retry_at: row.get(11)?,
             })
         })?;
         match rows.next() {
@@ -327,52 +806,308 @@ impl Database {
         }
     }
 
+    /// Insert or update a plugin config. Credentials are sealed before they are
+    /// written; already-sealed values are stored as-is.
     pub fn upsert_plugin_config(&self, config: &PluginConfig) -> Result<()> {
+        let credentials = config
+            .credentials
+            .as_deref()
+            .map(|c| self.seal_credentials(c))
+            .transpose()?;
         self.conn.execute(
             "INSERT INTO plugin_config (plugin_id, is_enabled, credentials, poll_interval_secs,
-                                        last_poll_at, last_error, error_count, settings)
-             VALUES (?1,?2,?3,?4,?5,?6,?7,?8)
+                                        last_poll_at, last_error, error_count, settings,
+                                        last_error_at, auto_disabled_at, last_error_kind, retry_at)
+             VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12)
              ON CONFLICT(plugin_id) DO UPDATE SET
                 is_enabled=excluded.is_enabled, credentials=excluded.credentials,
                 poll_interval_secs=excluded.poll_interval_secs, last_poll_at=excluded.last_poll_at,
                 last_error=excluded.last_error, error_count=excluded.error_count,
-                settings=excluded.settings",
+                settings=excluded.settings, last_error_at=excluded.last_error_at,
+                auto_disabled_at=excluded.auto_disabled_at,
+                last_error_kind=excluded.last_error_kind, retry_at=excluded.retry_at",
             params![
                 config.plugin_id,
                 config.is_enabled as i32,
-                config.credentials,
+                credentials,
                 config.poll_interval_secs,
                 config.last_poll_at,
                 config.last_error,
                 config.error_count,
                 config.settings,
+                config.last_error_at,
+                config.auto_disabled_at,
+                config.last_error_kind,
+                config.retry_at,
             ],
         )?;
         Ok(())
     }
 
-    /// Return full configs for enabled plugins with credentials.
-    pub fn get_enabled_plugin_configs(&self) -> Result<Vec<PluginConfig>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT * FROM plugin_config WHERE is_enabled = 1 AND credentials IS NOT NULL",
+    /// Save what the user edits in Settings: credentials, enabled flag, poll
+    /// interval and settings (kept as they are when `None`). Poll and
+    /// circuit-breaker state are left alone.
+    pub fn save_plugin_config(&self, config: &PluginConfig) -> Result<()> {
+        let credentials = config
+            .credentials
+            .as_deref()
+            .map(|c| self.seal_credentials(c))
+            .transpose()?;
+        self.conn.execute(
+            "INSERT INTO plugin_config (plugin_id, is_enabled, credentials, poll_interval_secs, settings)
+             VALUES (?1,?2,?3,?4,?5)
+             ON CONFLICT(plugin_id) DO UPDATE SET
+                is_enabled=excluded.is_enabled, credentials=excluded.credentials,
+                poll_interval_secs=excluded.poll_interval_secs,
+                settings=coalesce(excluded.settings, settings)",
+            params![
+                config.plugin_id,
+                config.is_enabled as i32,
+                credentials,
+                config.poll_interval_secs,
+                config.settings,
+            ],
         )?;
-        let configs = stmt
-            .query_map([], |row| {
-                Ok(PluginConfig {
-                    plugin_id: row.get(0)?,
-                    is_enabled: row.get::<_, i32>(1)? != 0,
-                    credentials: row.get(2)?,
-                    poll_interval_secs: row.get(3)?,
+        Ok(())
+    }
+
+    /// Persist a failed poll: store the error, its kind and time, bump
+    /// `error_count`. Returns the new consecutive failure count (0 if the
+    /// plugin has no row).
+    pub fn record_poll_failure(
+        &self,
+        plugin_id: &str,
+        error: &str,
+        kind: &str,
+        now: i64,
+    ) -> Result<i32> {
+        self.conn.execute(
+            "UPDATE plugin_config
+             SET last_error = ?1, last_error_kind = ?2, last_error_at = ?3,
+                 error_count = error_count + 1, retry_at = NULL
+             WHERE plugin_id = ?4",
+            params![error, kind, now, plugin_id],
+        )?;
+        self.error_count(plugin_id)
+    }
+
+    /// Persist a rate-limited poll: store the error and pause the plugin until
+    /// `retry_at`. Being throttled isn't a malfunction, so `error_count` (and
+    /// with it the circuit breaker) is left alone. Returns the current count.
+    pub fn record_rate_limit(
+        &self,
+        plugin_id: &str,
+        error: &str,
+        retry_at: i64,
+        now: i64,
+    ) -> Result<i32> {
+        self.conn.execute(
+            "UPDATE plugin_config
+             SET last_error = ?1, last_error_kind = 'rate_limited', last_error_at = ?2,
+                 retry_at = ?3
+             WHERE plugin_id = ?4",
+            params![error, now, retry_at, plugin_id],
+        )?;
+        self.error_count(plugin_id)
+    }
+
+    fn error_count(&self, plugin_id: &str) -> Result<i32> {
+        // A plugin without a row has no failures; anything else is a real error.
+        self.conn.query_row(
+            "SELECT coalesce((SELECT error_count FROM plugin_config WHERE plugin_id = ?1), 0)",
+            params![plugin_id],
+            |row| row.get(0),
+        )
+    }
+
+    /// Trip the circuit breaker: disable the plugin and remember that it was
+    /// the scheduler, not the user, who turned it off.
+    pub fn auto_disable_plugin(&self, plugin_id: &str, reason: &str, now: i64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE plugin_config SET is_enabled = 0, auto_disabled_at = ?1, last_error = ?2
+             WHERE plugin_id = ?3",
+            params![now, reason, plugin_id],
+        )?;
+        Ok(())
+    }
+
+    /// Reset the failure counter and re-enable the plugin if (and only if) the
+    /// circuit breaker disabled it. A plugin the user turned off stays off.
+    pub fn reset_circuit_breaker(&self, plugin_id: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE plugin_config
+             SET error_count = 0, retry_at = NULL,
+                 is_enabled = CASE WHEN auto_disabled_at IS NOT NULL THEN 1 ELSE is_enabled END,
+                 auto_disabled_at = NULL
+             WHERE plugin_id = ?1",
+            params![plugin_id],
+        )?;
+        Ok(())
+    }
+
+    /// Return full configs for enabled plugins with credentials.
+    pub fn get_enabled_plugin_configs(&self) -> Result<Vec<PluginConfig>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT * FROM plugin_config WHERE is_enabled = 1 AND credentials IS NOT NULL",
+        )?;
+        let configs = stmt
+            .query_map([], |row| {
+                Ok(PluginConfig {
+                    plugin_id: row.get(0)?,
+                    is_enabled: row.get::<_, i32>(1)? != 0,
+                    credentials: row.get(2)?,
+                    poll_interval_secs: row.get(3)?,
                     last_poll_at: row.get(4)?,
                     last_error: row.get(5)?,
                     error_count: row.get(6)?,
                     settings: row.get(7)?,
+                    last_error_at: row.get(8)?,
+                    auto_disabled_at: row.get(9)?,
+                    last_error_kind: row.get(10)?,
+                    retry_at: row.get(11)?,
                 })
             })?
             .collect::<Result<Vec<PluginConfig>>>()?;
         Ok(configs)
     }
 
+    // -- Sync State --
+
+    /// Opaque incremental-sync cursor a plugin returned from its last poll.
+    pub fn get_sync_cursor(&self, plugin_id: &str) -> Result<Option<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT cursor FROM plugin_sync_state WHERE plugin_id = ?1")?;
+        let mut rows = stmt.query_map(params![plugin_id], |row| row.get::<_, String>(0))?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Store the cursor for the next poll; `None` forgets it so the next poll
+    /// is a full fetch.
+    pub fn set_sync_cursor(&self, plugin_id: &str, cursor: Option<&str>, now: i64) -> Result<()> {
+        match cursor {
+            Some(cursor) => self.conn.execute(
+                "INSERT INTO plugin_sync_state (plugin_id, cursor, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(plugin_id) DO UPDATE SET
+                    cursor = excluded.cursor, updated_at = excluded.updated_at",
+                params![plugin_id, cursor, now],
+            )?,
+            None => self.conn.execute(
+                "DELETE FROM plugin_sync_state WHERE plugin_id = ?1",
+                params![plugin_id],
+            )?,
+        };
+        Ok(())
+    }
+
+    // -- Maintenance --
+
+    /// Delete read items not seen upstream since `read_before` and archived
+    /// items archived before `archived_before` (`None` keeps them). Their
+    /// notifications go with them (ON DELETE CASCADE). Returns rows deleted.
+    pub fn prune_items(
+        &self,
+        read_before: Option<i64>,
+        archived_before: Option<i64>,
+    ) -> Result<usize> {
+        let mut deleted = 0;
+        if let Some(cutoff) = read_before {
+            deleted += self.conn.execute(
+                &format!(
+                    "DELETE FROM items WHERE is_read = 1 AND updated_at < ?1 AND {}",
+                    NOT_KEPT_BY_USER
+                ),
+                params![cutoff],
+            )?;
+        }
+        if let Some(cutoff) = archived_before {
+            deleted += self.conn.execute(
+                &format!(
+                    "DELETE FROM items WHERE archived_at IS NOT NULL AND archived_at < ?1 AND {}",
+                    NOT_KEPT_BY_USER
+                ),
+                params![cutoff],
+            )?;
+        }
+        Ok(deleted)
+    }
+
+    /// Delete dismissed notifications created before `before`.
+    pub fn prune_dismissed_notifications(&self, before: i64) -> Result<usize> {
+        self.conn.execute(
+            "DELETE FROM notifications WHERE is_dismissed = 1 AND created_at < ?1",
+            params![before],
+        )
+    }
+
+    /// Share of the file's pages left free by deletes, which only VACUUM
+    /// gives back.
+    pub fn free_page_ratio(&self) -> Result<f64> {
+        let free: i64 = self
+            .conn
+            .query_row("PRAGMA freelist_count", [], |row| row.get(0))?;
+        let total: i64 = self
+            .conn
+            .query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        Ok(if total > 0 {
+            free as f64 / total as f64
+        } else {
+            0.0
+        })
+    }
+
+    /// Refresh query planner statistics and, if asked, rebuild the file to
+    /// reclaim the space freed by pruning.
+    pub fn optimize(&self, vacuum: bool) -> Result<()> {
+        self.conn.execute_batch("ANALYZE;")?;
+        if vacuum {
+            self.conn.execute_batch("VACUUM;")?;
+        }
+        Ok(())
+    }
+
+    /// Log a maintenance run, keeping only the most recent ones.
+    pub fn record_maintenance_run(&self, run: &MaintenanceRun) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO maintenance_runs (ran_at, items_pruned, notifications_pruned, vacuumed)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                run.ran_at,
+                run.items_pruned,
+                run.notifications_pruned,
+                run.vacuumed as i32,
+            ],
+        )?;
+        self.conn.execute(
+            "DELETE FROM maintenance_runs WHERE id NOT IN
+                (SELECT id FROM maintenance_runs ORDER BY id DESC LIMIT ?1)",
+            params![MAINTENANCE_RUNS_KEPT],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent maintenance runs, newest first.
+    pub fn get_maintenance_runs(&self, limit: i64) -> Result<Vec<MaintenanceRun>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT ran_at, items_pruned, notifications_pruned, vacuumed
+             FROM maintenance_runs ORDER BY id DESC LIMIT ?1",
+        )?;
+        let runs = stmt
+            .query_map(params![limit], |row| {
+                Ok(MaintenanceRun {
+                    ran_at: row.get(0)?,
+                    items_pruned: row.get(1)?,
+                    notifications_pruned: row.get(2)?,
+                    vacuumed: row.get::<_, i32>(3)? != 0,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(runs)
+    }
+
     // -- Heuristic Weights --
 
     pub fn get_weights(&self, source: &str) -> Result<Vec<HeuristicWeight>> {
@@ -402,21 +1137,15 @@ impl Database {
         Ok(())
     }
 
-    pub fn seed_default_weights(&self) -> Result<()> {
-        let defaults = vec![
-            ("jira", "assigned_to_me", 3),
-            ("jira", "priority_p1_blocker", 4),
-            ("jira", "mentioned_in_comment", 2),
-            ("jira", "deadline_24h", 3),
-        ];
-        for (source, signal, weight) in defaults {
-            let hw = HeuristicWeight {
-                id: format!("{}-{}", source, signal),
-                source: source.to_string(),
-                signal: signal.to_string(),
-                weight,
-            };
-            self.upsert_weight(&hw)?;
+    /// Insert default weights for a source's signals.
+    /// Seeding must not clobber weights the user has already tuned.
+    pub fn seed_weights(&self, source: &str, defaults: &[(String, i32)]) -> Result<()> {
+        for (signal, weight) in defaults {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO heuristic_weights (id, source, signal, weight)
+                 VALUES (?1,?2,?3,?4)",
+                params![format!("{}-{}", source, signal), source, signal, weight],
+            )?;
         }
         Ok(())
     }
@@ -424,16 +1153,263 @@ impl Database {
     #[cfg(test)]
     pub fn new_in_memory() -> Result<Self> {
         let conn = Connection::open_in_memory()?;
-        let db = Self { conn };
+        let db = Self { conn, cipher: None };
         db.run_migrations()?;
         Ok(db)
     }
 }
 
+/// Map a `SELECT *` row of `saved_views`.
+fn view_from_row(row: &rusqlite::Row) -> Result<SavedView> {
+    let query_json: String = row.get(2)?;
+    let query = serde_json::from_str(&query_json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, e.into())
+    })?;
+    Ok(SavedView {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        query,
+        min_age_secs: row.get(3)?,
+        max_age_secs: row.get(4)?,
+        position: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+fn rule_from_row(row: &rusqlite::Row) -> Result<NotificationRule> {
+    let conditions_json: String = row.get(4)?;
+    let conditions = serde_json::from_str(&conditions_json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, e.into())
+    })?;
+    let action_json: String = row.get(5)?;
+    let action = serde_json::from_str(&action_json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Text, e.into())
+    })?;
+    Ok(NotificationRule {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        is_enabled: row.get::<_, i32>(2)? != 0,
+        position: row.get(3)?,
+        conditions,
+        action,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+/// Map a row selected as `ITEM_COLUMNS` (`items.*`, then the user flags).
+fn item_from_row(row: &rusqlite::Row) -> Result<NexusItem> {
+    Ok(NexusItem {
+        id: row.get(0)?,
+        source: row.get(1)?,
+        source_id: row.get(2)?,
+        item_type: row.get(3)?,
+        title: row.get(4)?,
+        summary: row.get(5)?,
+        url: row.get(6)?,
+        author: row.get(7)?,
+        timestamp: row.get(8)?,
+        priority: row.get(9)?,
+        metadata: row.get(10)?,
+        tags: row.get(11)?,
+        is_read: row.get::<_, i32>(12)? != 0,
+        created_at: row.get(13)?,
+        updated_at: row.get(14)?,
+        signals: row.get(15)?,
+        archived_at: row.get(16)?,
+        snoozed_until: row.get(17)?,
+        flags: ItemFlags {
+            starred: row.get::<_, i32>(18)? != 0,
+            pinned: row.get::<_, i32>(19)? != 0,
+            note: row.get(20)?,
+            labels: match row.get::<_, Option<String>>(21)? {
+                Some(labels) => serde_json::from_str(&labels).map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        21,
+                        rusqlite::types::Type::Text,
+                        e.into(),
+                    )
+                })?,
+                None => Vec::new(),
+            },
+        },
+    })
+}
+
+/// Map a `SELECT *` row of `notifications`.
+fn notification_from_row(row: &rusqlite::Row) -> Result<Notification> {
+    Ok(Notification {
+        id: row.get(0)?,
+        item_id: row.get(1)?,
+        reason: row.get(2)?,
+        urgency: row.get(3)?,
+        is_dismissed: row.get::<_, i32>(4)? != 0,
+        created_at: row.get(5)?,
+        snoozed_until: row.get(6)?,
+        escalated_at: row.get(7)?,
+        delivered_at: row.get(8)?,
+    })
+}
+
+/// `WHERE` clause and parameters for the filters of `query` (everything but
+/// sort, cursor and limit).
+fn item_filter(query: &ItemQuery) -> (String, Vec<Box<dyn rusqlite::types::ToSql>>) {
+    let mut sql = String::from("WHERE 1 = 1");
+    let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![];
+
+    if !query.include_archived {
+        sql.push_str(" AND archived_at IS NULL");
+    }
+    if !query.include_snoozed {
+        sql.push_str(" AND snoozed_until IS NULL");
+    }
+    for (column, values) in [("source", &query.sources), ("item_type", &query.item_types)] {
+        if !values.is_empty() {
+            let placeholders = vec!["?"; values.len()].join(", ");
+            sql.push_str(&format!(" AND {} IN ({})", column, placeholders));
+            for value in values {
+                param_values.push(Box::new(value.clone()));
+            }
+        }
+    }
+    // `tags`, `signals` and `labels` are JSON arrays.
+    for (column, values) in [
+        ("tags", &query.tags),
+        ("signals", &query.signals),
+        ("f.labels", &query.labels),
+    ] {
+        if !values.is_empty() {
+            let placeholders = vec!["?"; values.len()].join(", ");
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM json_each(CASE WHEN json_valid({0}) THEN {0} END)
+                              WHERE value IN ({1}))",
+                column, placeholders
+            ));
+            for value in values {
+                param_values.push(Box::new(value.clone()));
+            }
+        }
+    }
+    if let Some(author) = &query.author {
+        sql.push_str(" AND author = ? COLLATE NOCASE");
+        param_values.push(Box::new(author.clone()));
+    }
+    if let Some(since) = query.since {
+        sql.push_str(" AND timestamp >= ?");
+        param_values.push(Box::new(since));
+    }
+    if let Some(until) = query.until {
+        sql.push_str(" AND timestamp < ?");
+        param_values.push(Box::new(until));
+    }
+    if let Some(min_priority) = query.min_priority {
+        sql.push_str(" AND priority >= ?");
+        param_values.push(Box::new(min_priority));
+    }
+    for (key, value) in &query.metadata {
+        let extract = "json_extract(CASE WHEN json_valid(metadata) THEN metadata END, ?)";
+        param_values.push(Box::new(metadata_path(key)));
+        match value {
+            serde_json::Value::Null => {
+                sql.push_str(
+                    " AND json_type(CASE WHEN json_valid(metadata) THEN metadata END, ?) = 'null'",
+                );
+            }
+            serde_json::Value::Bool(b) => {
+                sql.push_str(&format!(" AND {} = ?", extract));
+                param_values.push(Box::new(*b as i64));
+            }
+            serde_json::Value::Number(n) => {
+                sql.push_str(&format!(" AND {} = ?", extract));
+                match n.as_i64() {
+                    Some(i) => param_values.push(Box::new(i)),
+                    None => param_values.push(Box::new(n.as_f64())),
+                }
+            }
+            serde_json::Value::String(text) => {
+                sql.push_str(&format!(" AND {} = ?", extract));
+                param_values.push(Box::new(text.clone()));
+            }
+            // Arrays and objects compare as minified JSON text.
+            other => {
+                sql.push_str(&format!(" AND {} = ?", extract));
+                param_values.push(Box::new(other.to_string()));
+            }
+        }
+    }
+    if query.unread_only {
+        sql.push_str(" AND is_read = 0");
+    }
+    if query.starred_only {
+        sql.push_str(" AND f.starred = 1");
+    }
+    if query.pinned_only {
+        sql.push_str(" AND f.pinned = 1");
+    }
+    (sql, param_values)
+}
+
+/// JSON path of a metadata key; `a.b` addresses a nested key.
+fn metadata_path(key: &str) -> String {
+    key.split('.').fold(String::from("$"), |path, segment| {
+        format!("{}.\"{}\"", path, segment)
+    })
+}
+
+/// Page cursors are `{sort}:{pinned}:{sort value}:{item id}` of the last item
+/// returned.
+fn encode_cursor(sort: ItemSort, item: &NexusItem) -> String {
+    let value = match sort {
+        ItemSort::Timestamp => item.timestamp,
+        ItemSort::Priority => item.priority as i64,
+        ItemSort::UpdatedAt => item.updated_at,
+    };
+    format!(
+        "{}:{}:{}:{}",
+        sort.column(),
+        item.flags.pinned as i32,
+        value,
+        item.id
+    )
+}
+
+fn decode_cursor(sort: ItemSort, cursor: &str) -> Result<(i32, i64, String)> {
+    let mut parts = cursor.splitn(4, ':');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(column), Some(pinned), Some(value), Some(id)) if column == sort.column() => pinned
+            .parse::<i32>()
+            .and_then(|pinned| Ok((pinned, value.parse::<i64>()?, id.to_string())))
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into())),
+        _ => Err(rusqlite::Error::ToSqlConversionFailure(
+            format!("Invalid cursor for {} order: {}", sort.column(), cursor).into(),
+        )),
+    }
+}
+
+/// Turn free text into an FTS5 query: every word becomes a quoted prefix term
+/// (so FTS5 operators and punctuation in user input are taken literally), and
+/// all of them must match. `None` if nothing searchable is left.
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{NexusItem, Notification, PluginConfig};
+    use crate::models::{
+        HeuristicWeight, ItemFlags, ItemQuery, MaintenanceRun, NexusItem, Notification,
+        PluginConfig, RuleAction, RuleConditions, SavedView, SearchFilters,
+    };
 
     fn make_item() -> NexusItem {
         NexusItem {
@@ -452,6 +1428,10 @@ mod tests {
             is_read: false,
             created_at: 900,
             updated_at: 950,
+            signals: Some(r#"["assigned_to_me"]"#.to_string()),
+            archived_at: None,
+            snoozed_until: None,
+            flags: ItemFlags::default(),
         }
     }
 
@@ -463,137 +1443,997 @@ mod tests {
             urgency: "medium".to_string(),
             is_dismissed: false,
             created_at: 1000,
+            snoozed_until: None,
+            escalated_at: None,
+            delivered_at: None,
         }
     }
 
     #[test]
-    fn new_creates_tables() {
-        let db = Database::new_in_memory().expect("in-memory db should succeed");
-        let items = db.get_items(None, false, 100).unwrap();
-        assert!(items.is_empty());
+    fn new_creates_tables() {
+        let db = Database::new_in_memory().expect("in-memory db should succeed");
+        let items = db.get_items(&ItemQuery::default()).unwrap().items;
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn upsert_and_get_roundtrip() {
+        let db = Database::new_in_memory().unwrap();
+        let item = make_item();
+        db.upsert_item(&item).unwrap();
+
+        let items = db.get_items(&ItemQuery::default()).unwrap().items;
+        assert_eq!(items.len(), 1);
+        let got = &items[0];
+        assert_eq!(got.id, item.id);
+        assert_eq!(got.source, item.source);
+        assert_eq!(got.source_id, item.source_id);
+        assert_eq!(got.item_type, item.item_type);
+        assert_eq!(got.title, item.title);
+        assert_eq!(got.summary, item.summary);
+        assert_eq!(got.url, item.url);
+        assert_eq!(got.author, item.author);
+        assert_eq!(got.timestamp, item.timestamp);
+        assert_eq!(got.priority, item.priority);
+        assert_eq!(got.metadata, item.metadata);
+        assert_eq!(got.tags, item.tags);
+        assert_eq!(got.is_read, item.is_read);
+        assert_eq!(got.created_at, item.created_at);
+        assert_eq!(got.updated_at, item.updated_at);
+        assert_eq!(got.signals, item.signals);
+    }
+
+    #[test]
+    fn upsert_conflict_preserves_is_read() {
+        let db = Database::new_in_memory().unwrap();
+        let item = make_item();
+        db.upsert_item(&item).unwrap();
+
+        // Mark as read
+        db.mark_item_read(&item.id, true).unwrap();
+
+        // Upsert same source+source_id with updated title
+        let mut updated = item.clone();
+        updated.title = "Updated title".to_string();
+        updated.is_read = false; // plugin sends is_read=false
+        updated.updated_at = 2000;
+        db.upsert_item(&updated).unwrap();
+
+        let items = db.get_items(&ItemQuery::default()).unwrap().items;
+        assert_eq!(items.len(), 1);
+        // is_read should still be true (ON CONFLICT does not update is_read)
+        assert!(items[0].is_read, "is_read should be preserved on conflict");
+        assert_eq!(items[0].title, "Updated title");
+    }
+
+    #[test]
+    fn get_items_filters_by_source() {
+        let db = Database::new_in_memory().unwrap();
+
+        let jira_item = make_item();
+        db.upsert_item(&jira_item).unwrap();
+
+        let mut gh_item = make_item();
+        gh_item.id = "github-PR-42".to_string();
+        gh_item.source = "github".to_string();
+        gh_item.source_id = "PR-42".to_string();
+        db.upsert_item(&gh_item).unwrap();
+
+        let jira_only = db
+            .get_items(&ItemQuery {
+                sources: vec!["jira".to_string()],
+                ..Default::default()
+            })
+            .unwrap()
+            .items;
+        assert_eq!(jira_only.len(), 1);
+        assert_eq!(jira_only[0].source, "jira");
+    }
+
+    #[test]
+    fn get_items_filters_unread_only() {
+        let db = Database::new_in_memory().unwrap();
+
+        let item1 = make_item();
+        db.upsert_item(&item1).unwrap();
+
+        let mut item2 = make_item();
+        item2.id = "jira-TEST-2".to_string();
+        item2.source_id = "TEST-2".to_string();
+        item2.timestamp = 2000;
+        db.upsert_item(&item2).unwrap();
+
+        // Mark item1 as read
+        db.mark_item_read(&item1.id, true).unwrap();
+
+        let unread = db
+            .get_items(&ItemQuery {
+                unread_only: true,
+                ..Default::default()
+            })
+            .unwrap()
+            .items;
+        assert_eq!(unread.len(), 1);
+        assert_eq!(unread[0].id, "jira-TEST-2");
+    }
+
+    #[test]
+    fn get_items_respects_limit() {
+        let db = Database::new_in_memory().unwrap();
+
+        for i in 0..5 {
+            let mut item = make_item();
+            item.id = format!("jira-TEST-{}", i);
+            item.source_id = format!("TEST-{}", i);
+            item.timestamp = 1000 + i;
+            db.upsert_item(&item).unwrap();
+        }
+
+        let items = db
+            .get_items(&ItemQuery {
+                limit: Some(2),
+                ..Default::default()
+            })
+            .unwrap()
+            .items;
+        assert_eq!(items.len(), 2);
+    }
+
+    #[test]
+    fn get_items_orders_by_timestamp_desc() {
+        let db = Database::new_in_memory().unwrap();
+
+        for (i, ts) in [(0, 100i64), (1, 300), (2, 200)] {
+            let mut item = make_item();
+            item.id = format!("jira-TEST-{}", i);
+            item.source_id = format!("TEST-{}", i);
+            item.timestamp = ts;
+            db.upsert_item(&item).unwrap();
+        }
+
+        let items = db.get_items(&ItemQuery::default()).unwrap().items;
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].timestamp, 300);
+        assert_eq!(items[1].timestamp, 200);
+        assert_eq!(items[2].timestamp, 100);
+    }
+
+    fn item_ids(db: &Database, query: &ItemQuery) -> Vec<String> {
+        db.get_items(query)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|item| item.id)
+            .collect()
+    }
+
+    #[test]
+    fn get_items_applies_query_filters() {
+        let db = Database::new_in_memory().unwrap();
+        let ticket = make_item();
+        let mut pr = make_item();
+        pr.id = "github-PR-7".to_string();
+        pr.source = "github".to_string();
+        pr.source_id = "PR-7".to_string();
+        pr.item_type = "pull_request".to_string();
+        pr.author = Some("Bob".to_string());
+        pr.timestamp = 2000;
+        pr.priority = 8;
+        pr.tags = Some(r#"["security"]"#.to_string());
+        pr.metadata =
+            Some(r#"{"draft":false,"reviews":2,"repo":{"name":"nexus-hub"}}"#.to_string());
+        let mut mail = make_item();
+        mail.id = "gmail-1".to_string();
+        mail.source = "gmail".to_string();
+        mail.source_id = "1".to_string();
+        mail.tags = Some("not json".to_string());
+        mail.metadata = Some("not json".to_string());
+        mail.timestamp = 3000;
+        for item in [&ticket, &pr, &mail] {
+            db.upsert_item(item).unwrap();
+        }
+
+        let query = |edit: fn(&mut ItemQuery)| {
+            let mut query = ItemQuery::default();
+            edit(&mut query);
+            item_ids(&db, &query)
+        };
+        assert_eq!(
+            query(|q| q.sources = vec!["jira".to_string(), "gmail".to_string()]),
+            vec!["gmail-1", "jira-TEST-1"]
+        );
+        assert_eq!(
+            query(|q| q.item_types = vec!["pull_request".to_string()]),
+            vec!["github-PR-7"]
+        );
+        assert_eq!(
+            query(|q| q.tags = vec!["auth".to_string(), "security".to_string()]),
+            vec!["github-PR-7", "jira-TEST-1"]
+        );
+        assert_eq!(
+            query(|q| q.author = Some("bob".to_string())),
+            vec!["github-PR-7"]
+        );
+        assert_eq!(
+            query(|q| {
+                q.since = Some(1000);
+                q.until = Some(3000);
+            }),
+            vec!["github-PR-7", "jira-TEST-1"]
+        );
+        assert_eq!(query(|q| q.min_priority = Some(5)), vec!["github-PR-7"]);
+        assert_eq!(
+            query(|q| {
+                q.metadata.insert("draft".to_string(), false.into());
+                q.metadata.insert("reviews".to_string(), 2.into());
+                q.metadata
+                    .insert("repo.name".to_string(), "nexus-hub".into());
+            }),
+            vec!["github-PR-7"]
+        );
+        assert_eq!(
+            query(|q| {
+                q.metadata.insert("status".to_string(), "open".into());
+            }),
+            vec!["jira-TEST-1"]
+        );
+    }
+
+    #[test]
+    fn get_items_sorts_by_priority_and_updated_at() {
+        let db = Database::new_in_memory().unwrap();
+        for (id, priority, updated_at) in [("a", 1, 300), ("b", 9, 100), ("c", 5, 200)] {
+            let mut item = make_item();
+            item.id = id.to_string();
+            item.source_id = id.to_string();
+            item.priority = priority;
+            item.updated_at = updated_at;
+            db.upsert_item(&item).unwrap();
+        }
+
+        let mut query = ItemQuery {
+            sort: ItemSort::Priority,
+            ..Default::default()
+        };
+        assert_eq!(item_ids(&db, &query), vec!["b", "c", "a"]);
+        query.sort = ItemSort::UpdatedAt;
+        assert_eq!(item_ids(&db, &query), vec!["a", "c", "b"]);
+    }
+
+    #[test]
+    fn get_items_pages_with_cursor() {
+        let db = Database::new_in_memory().unwrap();
+        // Equal priorities: pages must still neither skip nor repeat items.
+        for i in 0..5 {
+            let mut item = make_item();
+            item.id = format!("jira-TEST-{}", i);
+            item.source_id = format!("TEST-{}", i);
+            item.priority = if i < 3 { 5 } else { 1 };
+            db.upsert_item(&item).unwrap();
+        }
+
+        let mut query = ItemQuery {
+            sort: ItemSort::Priority,
+            limit: Some(2),
+            ..Default::default()
+        };
+        let mut pages = Vec::new();
+        loop {
+            let page = db.get_items(&query).unwrap();
+            pages.push(page.items.iter().map(|i| i.id.clone()).collect::<Vec<_>>());
+            match page.next_cursor {
+                Some(cursor) => query.cursor = Some(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(
+            pages,
+            vec![
+                vec!["jira-TEST-2", "jira-TEST-1"],
+                vec!["jira-TEST-0", "jira-TEST-4"],
+                vec!["jira-TEST-3"],
+            ]
+        );
+    }
+
+    #[test]
+    fn get_items_rejects_foreign_cursor() {
+        let db = Database::new_in_memory().unwrap();
+        for cursor in [
+            "priority:0:5:jira-TEST-1",
+            "timestamp:0:x:jira-TEST-1",
+            "timestamp:100:jira-TEST-1",
+            "garbage",
+        ] {
+            let query = ItemQuery {
+                cursor: Some(cursor.to_string()),
+                ..Default::default()
+            };
+            assert!(db.get_items(&query).is_err(), "{}", cursor);
+        }
+    }
+
+    #[test]
+    fn item_flags_survive_upsert_and_filter() {
+        let db = Database::new_in_memory().unwrap();
+        let mut other = make_item();
+        other.id = "jira-TEST-2".to_string();
+        other.source_id = "TEST-2".to_string();
+        db.upsert_item(&make_item()).unwrap();
+        db.upsert_item(&other).unwrap();
+
+        let flags = ItemFlags {
+            starred: true,
+            pinned: false,
+            note: Some("ask in standup".to_string()),
+            labels: vec!["review".to_string(), "q3".to_string()],
+        };
+        db.set_item_flags("jira-TEST-1", &flags).unwrap();
+
+        // A re-poll carries no flags and must not clear them.
+        let mut repoll = make_item();
+        repoll.title = "Renamed".to_string();
+        db.upsert_item(&repoll).unwrap();
+        let items = db.get_items(&ItemQuery::default()).unwrap().items;
+        let item = items.iter().find(|i| i.id == "jira-TEST-1").unwrap();
+        assert_eq!(item.title, "Renamed");
+        assert_eq!(item.flags, flags);
+        assert!(items
+            .iter()
+            .find(|i| i.id == "jira-TEST-2")
+            .unwrap()
+            .flags
+            .is_empty());
+
+        let ids = |query: ItemQuery| -> Vec<String> {
+            db.get_items(&query)
+                .unwrap()
+                .items
+                .into_iter()
+                .map(|i| i.id)
+                .collect()
+        };
+        assert_eq!(
+            ids(ItemQuery {
+                starred_only: true,
+                ..Default::default()
+            }),
+            vec!["jira-TEST-1"]
+        );
+        assert_eq!(
+            ids(ItemQuery {
+                labels: vec!["q3".to_string()],
+                ..Default::default()
+            }),
+            vec!["jira-TEST-1"]
+        );
+        assert!(ids(ItemQuery {
+            pinned_only: true,
+            ..Default::default()
+        })
+        .is_empty());
+        assert_eq!(
+            db.count_items(&ItemQuery {
+                starred_only: true,
+                ..Default::default()
+            })
+            .unwrap(),
+            1
+        );
+
+        // Clearing every flag drops the row.
+        db.set_item_flags("jira-TEST-1", &ItemFlags::default())
+            .unwrap();
+        let rows: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM item_flags", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(rows, 0);
+    }
+
+    #[test]
+    fn get_items_lists_pinned_first_across_pages() {
+        let db = Database::new_in_memory().unwrap();
+        for i in 0..4 {
+            let mut item = make_item();
+            item.id = format!("jira-TEST-{}", i);
+            item.source_id = format!("TEST-{}", i);
+            item.timestamp = 1000 + i;
+            db.upsert_item(&item).unwrap();
+        }
+        let pinned = ItemFlags {
+            pinned: true,
+            ..Default::default()
+        };
+        db.set_item_flags("jira-TEST-0", &pinned).unwrap();
+        db.set_item_flags("jira-TEST-2", &pinned).unwrap();
+
+        let mut query = ItemQuery {
+            limit: Some(3),
+            ..Default::default()
+        };
+        let first = db.get_items(&query).unwrap();
+        query.cursor = first.next_cursor;
+        let second = db.get_items(&query).unwrap();
+        let ids: Vec<&str> = first
+            .items
+            .iter()
+            .chain(&second.items)
+            .map(|i| i.id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["jira-TEST-2", "jira-TEST-0", "jira-TEST-3", "jira-TEST-1"]
+        );
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[test]
+    fn count_items_matches_signals_and_unread() {
+        let db = Database::new_in_memory().unwrap();
+        let mut review = make_item();
+        review.signals = Some(r#"["review_requested"]"#.to_string());
+        let mut other = make_item();
+        other.id = "jira-TEST-2".to_string();
+        other.source_id = "TEST-2".to_string();
+        for item in [&review, &other] {
+            db.upsert_item(item).unwrap();
+        }
+
+        let mut query = ItemQuery {
+            signals: vec!["review_requested".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(db.count_items(&query).unwrap(), 1);
+        db.mark_item_read(&review.id, true).unwrap();
+        query.unread_only = true;
+        assert_eq!(db.count_items(&query).unwrap(), 0);
+    }
+
+    fn make_view(id: &str, name: &str, position: i64) -> SavedView {
+        SavedView {
+            id: id.to_string(),
+            name: name.to_string(),
+            query: ItemQuery {
+                sources: vec!["github".to_string()],
+                signals: vec!["review_requested".to_string()],
+                ..Default::default()
+            },
+            min_age_secs: Some(86_400),
+            position,
+            created_at: 1000,
+            updated_at: 1000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn saved_views_round_trip_in_order() {
+        let db = Database::new_in_memory().unwrap();
+        let mut waiting = make_view("v1", "PRs waiting on me", 1);
+        waiting.query.cursor = Some("timestamp:1:x".to_string());
+        db.upsert_view(&waiting).unwrap();
+        db.upsert_view(&make_view("v2", "Blockers", 0)).unwrap();
+
+        let views = db.get_views().unwrap();
+        assert_eq!(
+            views.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(),
+            vec!["v2", "v1"]
+        );
+        let got = db.get_view("v1").unwrap().unwrap();
+        assert_eq!(got.name, "PRs waiting on me");
+        assert_eq!(got.query.signals, vec!["review_requested".to_string()]);
+        assert_eq!(got.query.cursor, None, "page cursors are not stored");
+        assert_eq!(got.min_age_secs, Some(86_400));
+
+        waiting.name = "Reviews".to_string();
+        waiting.updated_at = 2000;
+        db.upsert_view(&waiting).unwrap();
+        let got = db.get_view("v1").unwrap().unwrap();
+        assert_eq!((got.name.as_str(), got.updated_at), ("Reviews", 2000));
+
+        db.delete_view("v1").unwrap();
+        assert!(db.get_view("v1").unwrap().is_none());
+        assert_eq!(db.get_views().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn notification_rules_round_trip_in_order() {
+        let db = Database::new_in_memory().unwrap();
+        let mute = NotificationRule {
+            id: "r1".to_string(),
+            name: "Mute bots".to_string(),
+            position: 1,
+            conditions: RuleConditions {
+                authors: vec!["dependabot".to_string()],
+                ..Default::default()
+            },
+            action: RuleAction::NeverNotify,
+            ..Default::default()
+        };
+        let escalate = NotificationRule {
+            id: "r2".to_string(),
+            name: "Prod incidents".to_string(),
+            conditions: RuleConditions {
+                metadata: [("env".to_string(), serde_json::json!("prod"))].into(),
+                ..Default::default()
+            },
+            action: RuleAction::OverrideUrgency {
+                urgency: "critical".to_string(),
+            },
+            ..Default::default()
+        };
+        db.upsert_rule(&mute).unwrap();
+        db.upsert_rule(&escalate).unwrap();
+
+        let rules = db.get_rules().unwrap();
+        assert_eq!(
+            rules.iter().map(|r| r.id.as_str()).collect::<Vec<_>>(),
+            vec!["r2", "r1"]
+        );
+        assert_eq!(rules[0].action, escalate.action);
+        assert_eq!(rules[0].conditions, escalate.conditions);
+
+        db.upsert_rule(&NotificationRule {
+            is_enabled: false,
+            ..mute
+        })
+        .unwrap();
+        assert!(!db.get_rule("r1").unwrap().unwrap().is_enabled);
+
+        db.delete_rule("r1").unwrap();
+        assert!(db.get_rule("r1").unwrap().is_none());
+        assert_eq!(db.get_rules().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn saved_view_resolves_age_bounds() {
+        let mut view = make_view("v1", "Old", 0);
+        view.max_age_secs = Some(7 * 86_400);
+        view.query.until = Some(500_000);
+        let query = view.resolve(1_000_000);
+        assert_eq!(query.since, Some(1_000_000 - 7 * 86_400));
+        // The tighter of the stored and the relative bound wins.
+        assert_eq!(query.until, Some(500_000));
+
+        view.query.until = None;
+        assert_eq!(view.resolve(1_000_000).until, Some(1_000_000 - 86_400));
+    }
+
+    fn search_ids(db: &Database, query: &str, filters: &SearchFilters) -> Vec<String> {
+        db.search_items(query, filters)
+            .unwrap()
+            .into_iter()
+            .map(|hit| hit.item.id)
+            .collect()
+    }
+
+    #[test]
+    fn search_items_ranks_title_matches_first() {
+        let db = Database::new_in_memory().unwrap();
+        let mut in_title = make_item();
+        in_title.title = "Auth migration to OAuth".to_string();
+        let mut in_summary = make_item();
+        in_summary.id = "jira-TEST-2".to_string();
+        in_summary.source_id = "TEST-2".to_string();
+        in_summary.summary = Some("Blocked on the auth migrations".to_string());
+        db.upsert_item(&in_summary).unwrap();
+        db.upsert_item(&in_title).unwrap();
+
+        let hits = db
+            .search_items("auth migration", &SearchFilters::default())
+            .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].item.id, in_title.id);
+        assert!(hits[0].score > hits[1].score);
+        assert_eq!(
+            hits[0].title_highlight,
+            "\u{2}Auth\u{3} \u{2}migration\u{3} to OAuth"
+        );
+        assert!(hits[1].snippet.contains("\u{2}migrations\u{3}"));
     }
 
     #[test]
-    fn upsert_and_get_roundtrip() {
+    fn search_items_indexes_author_tags_and_metadata() {
         let db = Database::new_in_memory().unwrap();
-        let item = make_item();
+        let mut item = make_item();
+        item.author = Some("Ada Lovelace".to_string());
+        item.metadata = Some(r#"{"status":"open","repo":{"name":"nexus-hub"}}"#.to_string());
         db.upsert_item(&item).unwrap();
 
-        let items = db.get_items(None, false, 100).unwrap();
-        assert_eq!(items.len(), 1);
-        let got = &items[0];
-        assert_eq!(got.id, item.id);
-        assert_eq!(got.source, item.source);
-        assert_eq!(got.source_id, item.source_id);
-        assert_eq!(got.item_type, item.item_type);
-        assert_eq!(got.title, item.title);
-        assert_eq!(got.summary, item.summary);
-        assert_eq!(got.url, item.url);
-        assert_eq!(got.author, item.author);
-        assert_eq!(got.timestamp, item.timestamp);
-        assert_eq!(got.priority, item.priority);
-        assert_eq!(got.metadata, item.metadata);
-        assert_eq!(got.tags, item.tags);
-        assert_eq!(got.is_read, item.is_read);
-        assert_eq!(got.created_at, item.created_at);
-        assert_eq!(got.updated_at, item.updated_at);
+        let filters = SearchFilters::default();
+        for query in ["lovelace", "auth", "nexus-hub", "open"] {
+            assert_eq!(
+                search_ids(&db, query, &filters),
+                vec![item.id.clone()],
+                "{}",
+                query
+            );
+        }
+        // JSON keys are not indexed, only values.
+        assert!(search_ids(&db, "status", &filters).is_empty());
     }
 
     #[test]
-    fn upsert_conflict_preserves_is_read() {
+    fn search_items_follows_upserts_and_deletes() {
         let db = Database::new_in_memory().unwrap();
-        let item = make_item();
+        let mut item = make_item();
+        item.title = "Rotate signing keys".to_string();
         db.upsert_item(&item).unwrap();
 
-        // Mark as read
-        db.mark_item_read(&item.id, true).unwrap();
+        // The plugin sends a new item id for the same source_id: the stored id wins.
+        let mut renamed = item.clone();
+        renamed.id = "jira-renamed".to_string();
+        renamed.title = "Rotate encryption keys".to_string();
+        db.upsert_item(&renamed).unwrap();
 
-        // Upsert same source+source_id with updated title
-        let mut updated = item.clone();
-        updated.title = "Updated title".to_string();
-        updated.is_read = false; // plugin sends is_read=false
-        updated.updated_at = 2000;
-        db.upsert_item(&updated).unwrap();
+        let filters = SearchFilters::default();
+        assert!(search_ids(&db, "signing", &filters).is_empty());
+        assert_eq!(
+            search_ids(&db, "encryption", &filters),
+            vec![item.id.clone()]
+        );
 
-        let items = db.get_items(None, false, 100).unwrap();
-        assert_eq!(items.len(), 1);
-        // is_read should still be true (ON CONFLICT does not update is_read)
-        assert!(items[0].is_read, "is_read should be preserved on conflict");
-        assert_eq!(items[0].title, "Updated title");
+        db.mark_item_read(&item.id, true).unwrap();
+        db.prune_items(Some(2000), None).unwrap();
+        assert!(search_ids(&db, "keys", &filters).is_empty());
     }
 
     #[test]
-    fn get_items_filters_by_source() {
+    fn search_items_applies_filters() {
         let db = Database::new_in_memory().unwrap();
+        let jira = make_item();
+        let mut github = make_item();
+        github.id = "github-1".to_string();
+        github.source = "github".to_string();
+        github.source_id = "1".to_string();
+        let mut archived = make_item();
+        archived.id = "jira-TEST-2".to_string();
+        archived.source_id = "TEST-2".to_string();
+        archived.archived_at = Some(1500);
+        for item in [&jira, &github, &archived] {
+            db.upsert_item(item).unwrap();
+        }
+        db.mark_item_read(&github.id, true).unwrap();
 
-        let jira_item = make_item();
-        db.upsert_item(&jira_item).unwrap();
+        let mut filters = SearchFilters::default();
+        assert_eq!(search_ids(&db, "auth", &filters).len(), 2);
 
-        let mut gh_item = make_item();
-        gh_item.id = "github-PR-42".to_string();
-        gh_item.source = "github".to_string();
-        gh_item.source_id = "PR-42".to_string();
-        db.upsert_item(&gh_item).unwrap();
+        filters.include_archived = true;
+        assert_eq!(search_ids(&db, "auth", &filters).len(), 3);
 
-        let jira_only = db.get_items(Some("jira"), false, 100).unwrap();
-        assert_eq!(jira_only.len(), 1);
-        assert_eq!(jira_only[0].source, "jira");
+        filters.sources = vec!["github".to_string()];
+        assert_eq!(search_ids(&db, "auth", &filters), vec![github.id.clone()]);
+
+        filters.sources.clear();
+        filters.unread_only = true;
+        assert_eq!(search_ids(&db, "auth", &filters).len(), 2);
+
+        filters.limit = Some(1);
+        assert_eq!(search_ids(&db, "auth", &filters).len(), 1);
     }
 
     #[test]
-    fn get_items_filters_unread_only() {
+    fn search_items_takes_query_syntax_literally() {
         let db = Database::new_in_memory().unwrap();
+        db.upsert_item(&make_item()).unwrap();
 
-        let item1 = make_item();
-        db.upsert_item(&item1).unwrap();
-
-        let mut item2 = make_item();
-        item2.id = "jira-TEST-2".to_string();
-        item2.source_id = "TEST-2".to_string();
-        item2.timestamp = 2000;
-        db.upsert_item(&item2).unwrap();
+        let filters = SearchFilters::default();
+        // FTS5 operators, quotes and column filters are just words.
+        for query in ["auth AND", "title:auth", "NEAR(auth", "auth OR -"] {
+            assert!(db.search_items(query, &filters).is_ok(), "{}", query);
+        }
+        for query in ["\"auth", "(auth", "auth:", "bu*"] {
+            assert_eq!(search_ids(&db, query, &filters).len(), 1, "{}", query);
+        }
+        for query in ["", "   ", "-", "\"\""] {
+            assert!(search_ids(&db, query, &filters).is_empty(), "{:?}", query);
+        }
+    }
 
-        // Mark item1 as read
-        db.mark_item_read(&item1.id, true).unwrap();
+    #[test]
+    fn archive_missing_items_hides_and_dismisses() {
+        let db = Database::new_in_memory().unwrap();
+        let kept = make_item();
+        let mut gone = make_item();
+        gone.id = "jira-TEST-2".to_string();
+        gone.source_id = "TEST-2".to_string();
+        let mut other = make_item();
+        other.id = "github-1".to_string();
+        other.source = "github".to_string();
+        other.source_id = "1".to_string();
+        for item in [&kept, &gone, &other] {
+            db.upsert_item(item).unwrap();
+        }
+        let mut notif = make_notification();
+        notif.item_id = gone.id.clone();
+        db.insert_notification(&notif).unwrap();
 
-        let unread = db.get_items(None, true, 100).unwrap();
-        assert_eq!(unread.len(), 1);
-        assert_eq!(unread[0].id, "jira-TEST-2");
+        let seen: HashSet<&str> = ["TEST-1"].into();
+        let archived = db.archive_missing_items("jira", &seen, 2000).unwrap();
+        assert_eq!(archived, vec!["jira-TEST-2".to_string()]);
+
+        let ids: Vec<String> = db
+            .get_items(&ItemQuery::default())
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(ids.len(), 2);
+        assert!(!ids.contains(&gone.id));
+        assert!(db.get_active_notifications().unwrap().is_empty());
+
+        // Already archived: not reported again.
+        assert!(db
+            .archive_missing_items("jira", &seen, 3000)
+            .unwrap()
+            .is_empty());
+
+        // An item that comes back upstream is live again.
+        db.upsert_item(&gone).unwrap();
+        assert_eq!(
+            db.get_items(&ItemQuery {
+                sources: vec!["jira".to_string()],
+                ..Default::default()
+            })
+            .unwrap()
+            .items
+            .len(),
+            2
+        );
     }
 
     #[test]
-    fn get_items_respects_limit() {
+    fn prune_items_respects_cutoffs() {
         let db = Database::new_in_memory().unwrap();
+        let item = |id: &str, is_read: bool, updated_at: i64, archived_at: Option<i64>| {
+            let mut item = make_item();
+            item.id = id.to_string();
+            item.source_id = id.to_string();
+            item.is_read = is_read;
+            item.updated_at = updated_at;
+            item.archived_at = archived_at;
+            item
+        };
+        db.upsert_item(&item("old-read", true, 100, None)).unwrap();
+        db.upsert_item(&item("old-unread", false, 100, None))
+            .unwrap();
+        db.upsert_item(&item("new-read", true, 900, None)).unwrap();
+        db.upsert_item(&item("old-archived", false, 900, Some(100)))
+            .unwrap();
+        db.upsert_item(&item("new-archived", false, 900, Some(900)))
+            .unwrap();
+        let mut notif = make_notification();
+        notif.item_id = "old-read".to_string();
+        db.insert_notification(&notif).unwrap();
 
-        for i in 0..5 {
+        assert_eq!(db.prune_items(None, None).unwrap(), 0);
+        assert_eq!(db.prune_items(Some(500), Some(500)).unwrap(), 2);
+
+        let left: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM items", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(left, 3);
+        // Cascaded with its item.
+        assert!(db.get_active_notifications().unwrap().is_empty());
+    }
+
+    #[test]
+    fn prune_items_keeps_starred_and_pinned() {
+        let db = Database::new_in_memory().unwrap();
+        for (id, flags) in [
+            (
+                "starred",
+                ItemFlags {
+                    starred: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "pinned",
+                ItemFlags {
+                    pinned: true,
+                    ..Default::default()
+                },
+            ),
+            (
+                "noted",
+                ItemFlags {
+                    note: Some("n".to_string()),
+                    ..Default::default()
+                },
+            ),
+        ] {
             let mut item = make_item();
-            item.id = format!("jira-TEST-{}", i);
-            item.source_id = format!("TEST-{}", i);
-            item.timestamp = 1000 + i;
+            item.id = id.to_string();
+            item.source_id = id.to_string();
+            item.is_read = true;
+            item.updated_at = 100;
+            item.archived_at = Some(100);
             db.upsert_item(&item).unwrap();
+            db.set_item_flags(id, &flags).unwrap();
         }
 
-        let items = db.get_items(None, false, 2).unwrap();
-        assert_eq!(items.len(), 2);
+        assert_eq!(db.prune_items(Some(500), Some(500)).unwrap(), 1);
+        let ids: Vec<String> = db
+            .get_items(&ItemQuery {
+                include_archived: true,
+                ..Default::default()
+            })
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(ids, vec!["pinned", "starred"]);
+        // The pruned item's flags went with it.
+        let rows: i64 = db
+            .conn
+            .query_row("SELECT COUNT(*) FROM item_flags", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(rows, 2);
     }
 
     #[test]
-    fn get_items_orders_by_timestamp_desc() {
+    fn prune_dismissed_notifications_keeps_active() {
         let db = Database::new_in_memory().unwrap();
+        db.upsert_item(&make_item()).unwrap();
+        let mut dismissed = make_notification();
+        dismissed.is_dismissed = true;
+        db.insert_notification(&dismissed).unwrap();
+        let mut active = make_notification();
+        active.id = "notif-2".to_string();
+        db.insert_notification(&active).unwrap();
+
+        assert_eq!(db.prune_dismissed_notifications(2000).unwrap(), 1);
+        assert_eq!(db.get_active_notifications().unwrap().len(), 1);
+    }
 
-        for (i, ts) in [(0, 100i64), (1, 300), (2, 200)] {
-            let mut item = make_item();
-            item.id = format!("jira-TEST-{}", i);
-            item.source_id = format!("TEST-{}", i);
-            item.timestamp = ts;
-            db.upsert_item(&item).unwrap();
+    #[test]
+    fn maintenance_runs_keep_most_recent() {
+        let db = Database::new_in_memory().unwrap();
+        for i in 0..(MAINTENANCE_RUNS_KEPT + 5) {
+            db.record_maintenance_run(&MaintenanceRun {
+                ran_at: i,
+                items_pruned: i as usize,
+                notifications_pruned: 0,
+                vacuumed: i % 2 == 0,
+            })
+            .unwrap();
         }
+        db.optimize(true).unwrap();
 
-        let items = db.get_items(None, false, 100).unwrap();
-        assert_eq!(items.len(), 3);
-        assert_eq!(items[0].timestamp, 300);
-        assert_eq!(items[1].timestamp, 200);
-        assert_eq!(items[2].timestamp, 100);
+        let runs = db.get_maintenance_runs(100).unwrap();
+        assert_eq!(runs.len() as i64, MAINTENANCE_RUNS_KEPT);
+        assert_eq!(runs[0].ran_at, MAINTENANCE_RUNS_KEPT + 4);
+        assert_eq!(db.get_maintenance_runs(1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn snoozed_item_is_hidden_with_its_notifications() {
+        let db = Database::new_in_memory().unwrap();
+        let item = make_item();
+        db.upsert_item(&item).unwrap();
+        db.insert_notification(&make_notification()).unwrap();
+
+        db.snooze_item(&item.id, Some(5000)).unwrap();
+        assert!(db
+            .get_items(&ItemQuery::default())
+            .unwrap()
+            .items
+            .is_empty());
+        assert!(db.get_active_notifications().unwrap().is_empty());
+        // Still counts as active, so the next poll doesn't notify again.
+        assert!(db.has_active_notification(&item.id, "assigned").unwrap());
+
+        // A poll doesn't cancel the snooze.
+        db.upsert_item(&item).unwrap();
+        let query = ItemQuery {
+            include_snoozed: true,
+            ..Default::default()
+        };
+        let items = db.get_items(&query).unwrap().items;
+        assert_eq!(items[0].snoozed_until, Some(5000));
+
+        db.snooze_item(&item.id, None).unwrap();
+        assert_eq!(db.get_items(&ItemQuery::default()).unwrap().items.len(), 1);
+        assert_eq!(db.get_active_notifications().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn wake_snoozed_returns_due_rows_once() {
+        let db = Database::new_in_memory().unwrap();
+        let item = make_item();
+        let mut later = make_item();
+        later.id = "jira-TEST-2".to_string();
+        later.source_id = "TEST-2".to_string();
+        db.upsert_item(&item).unwrap();
+        db.upsert_item(&later).unwrap();
+        db.insert_notification(&make_notification()).unwrap();
+        db.snooze_notification("notif-1", Some(2000)).unwrap();
+        db.snooze_item(&later.id, Some(9000)).unwrap();
+        assert!(db.get_active_notifications().unwrap().is_empty());
+
+        let woken = db.wake_snoozed(1999).unwrap();
+        assert!(woken.items.is_empty() && woken.notifications.is_empty());
+
+        let woken = db.wake_snoozed(2000).unwrap();
+        assert!(woken.items.is_empty());
+        assert_eq!(woken.notifications.len(), 1);
+        assert_eq!(woken.notifications[0].0.id, "notif-1");
+        assert_eq!(woken.notifications[0].1, item.title);
+        assert_eq!(db.get_active_notifications().unwrap().len(), 1);
+
+        let woken = db.wake_snoozed(9000).unwrap();
+        assert_eq!(woken.items.len(), 1);
+        assert_eq!(woken.items[0].id, later.id);
+        assert_eq!(db.get_items(&ItemQuery::default()).unwrap().items.len(), 2);
+
+        let woken = db.wake_snoozed(10_000).unwrap();
+        assert!(woken.items.is_empty() && woken.notifications.is_empty());
+    }
+
+    #[test]
+    fn overdue_notifications_restart_after_escalation() {
+        let db = Database::new_in_memory().unwrap();
+        db.upsert_item(&make_item()).unwrap();
+        let mut critical = make_notification(); // created_at 1000
+        critical.urgency = "critical".to_string();
+        db.insert_notification(&critical).unwrap();
+        let mut high = make_notification();
+        high.id = "notif-2".to_string();
+        db.insert_notification(&high).unwrap();
+
+        assert!(db
+            .get_overdue_notifications("critical", 999)
+            .unwrap()
+            .is_empty());
+        let overdue = db.get_overdue_notifications("critical", 1000).unwrap();
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].0.id, "notif-1");
+        assert_eq!(overdue[0].1, make_item().title);
+
+        db.mark_notification_escalated("notif-1", 1900).unwrap();
+        assert!(db
+            .get_overdue_notifications("critical", 1899)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            db.get_overdue_notifications("critical", 1900)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        db.dismiss_notification("notif-1").unwrap();
+        assert!(db
+            .get_overdue_notifications("critical", 5000)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn undelivered_notifications_until_marked() {
+        let db = Database::new_in_memory().unwrap();
+        db.upsert_item(&make_item()).unwrap();
+        db.insert_notification(&make_notification()).unwrap();
+        let mut shown = make_notification();
+        shown.id = "notif-2".to_string();
+        shown.delivered_at = Some(1000);
+        db.insert_notification(&shown).unwrap();
+
+        let pending = db.get_undelivered_notifications().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].notification.id, "notif-1");
+        assert_eq!(pending[0].title, make_item().title);
+        assert_eq!(pending[0].source, "jira");
+
+        db.mark_notifications_delivered(&["notif-1"], 2000).unwrap();
+        assert!(db.get_undelivered_notifications().unwrap().is_empty());
+        // Still active: delivery only concerns the native notification.
+        assert_eq!(db.get_active_notifications().unwrap().len(), 2);
     }
 
     #[test]
@@ -601,16 +2441,16 @@ mod tests {
         let db = Database::new_in_memory().unwrap();
         let item = make_item();
         db.upsert_item(&item).unwrap();
-        assert!(!db.get_items(None, false, 1).unwrap()[0].is_read);
+        assert!(!db.get_items(&ItemQuery::default()).unwrap().items[0].is_read);
 
         db.mark_item_read(&item.id, true).unwrap();
-        assert!(db.get_items(None, false, 1).unwrap()[0].is_read);
+        assert!(db.get_items(&ItemQuery::default()).unwrap().items[0].is_read);
 
         db.mark_item_read(&item.id, false).unwrap();
-        assert!(!db.get_items(None, false, 1).unwrap()[0].is_read);
+        assert!(!db.get_items(&ItemQuery::default()).unwrap().items[0].is_read);
 
         db.mark_item_read(&item.id, true).unwrap();
-        assert!(db.get_items(None, false, 1).unwrap()[0].is_read);
+        assert!(db.get_items(&ItemQuery::default()).unwrap().items[0].is_read);
     }
 
     #[test]
@@ -682,6 +2522,30 @@ mod tests {
         assert!(!db.has_active_notification(&item.id, "assigned").unwrap());
     }
 
+    #[test]
+    fn notified_urgency_tracks_downgrades() {
+        let db = Database::new_in_memory().unwrap();
+        let item = make_item();
+        db.upsert_item(&item).unwrap();
+        assert_eq!(db.get_notified_urgency(&item.id).unwrap(), None);
+
+        db.insert_notification(&make_notification()).unwrap(); // medium
+        db.set_notified_urgency(&item.id, "high", 1000).unwrap();
+        db.set_notified_urgency(&item.id, "medium", 1100).unwrap();
+        assert_eq!(
+            db.get_notified_urgency(&item.id).unwrap().as_deref(),
+            Some("medium")
+        );
+
+        // Whatever the stored urgency, e.g. one a routing rule overrode.
+        assert_eq!(db.downgrade_notifications(&item.id, "low").unwrap(), 1);
+        assert_eq!(db.downgrade_notifications(&item.id, "low").unwrap(), 0);
+        assert_eq!(db.get_active_notifications().unwrap()[0].urgency, "low");
+
+        db.dismiss_item_notifications(&item.id).unwrap();
+        assert!(db.get_active_notifications().unwrap().is_empty());
+    }
+
     #[test]
     fn dismiss_all_notifications_clears_active() {
         let db = Database::new_in_memory().unwrap();
@@ -699,6 +2563,9 @@ mod tests {
             urgency: "high".to_string(),
             is_dismissed: false,
             created_at: 1000,
+            snoozed_until: None,
+            escalated_at: None,
+            delivered_at: None,
         };
         db.insert_notification(&n2).unwrap();
 
@@ -747,6 +2614,10 @@ mod tests {
             last_error: None,
             error_count: 0,
             settings: Some(r#"{"project":"PROJ"}"#.to_string()),
+            last_error_at: None,
+            auto_disabled_at: None,
+            last_error_kind: None,
+            retry_at: None,
         };
         db.upsert_plugin_config(&config).unwrap();
 
@@ -759,6 +2630,94 @@ mod tests {
         assert_eq!(got.last_error, config.last_error);
         assert_eq!(got.error_count, config.error_count);
         assert_eq!(got.settings, config.settings);
+        assert_eq!(got.last_error_at, config.last_error_at);
+    }
+
+    #[test]
+    fn record_poll_failure_increments_count() {
+        let db = Database::new_in_memory().unwrap();
+        db.upsert_plugin_config(&make_config("github", r#"{"token":"t"}"#))
+            .unwrap();
+
+        assert_eq!(db.record_poll_failure("github", "401 Unauthorized", "auth", 100).unwrap(), 1);
+        assert_eq!(
+            db.record_poll_failure("github", "timeout", "timeout", 200)
+                .unwrap(),
+            2
+        );
+
+        let got = db.get_plugin_config("github").unwrap().unwrap();
+        assert_eq!(got.error_count, 2);
+        assert_eq!(got.last_error.as_deref(), Some("timeout"));
+        assert_eq!(got.last_error_kind.as_deref(), Some("timeout"));
+        assert_eq!(got.last_error_at, Some(200));
+    }
+
+    #[test]
+    fn record_rate_limit_pauses_without_counting() {
+        let db = Database::new_in_memory().unwrap();
+        db.upsert_plugin_config(&make_config("github", r#"{"token":"t"}"#))
+            .unwrap();
+        db.record_poll_failure("github", "502", "network", 50)
+            .unwrap();
+
+        assert_eq!(db.record_rate_limit("github", "429", 400, 100).unwrap(), 1);
+        let got = db.get_plugin_config("github").unwrap().unwrap();
+        assert_eq!(got.error_count, 1);
+        assert_eq!(got.last_error_kind.as_deref(), Some("rate_limited"));
+        assert_eq!(got.retry_at, Some(400));
+
+        // A manual refresh ignores the pause.
+        db.reset_circuit_breaker("github").unwrap();
+        assert_eq!(
+            db.get_plugin_config("github").unwrap().unwrap().retry_at,
+            None
+        );
+    }
+
+    #[test]
+    fn reset_circuit_breaker_reenables_auto_disabled() {
+        let db = Database::new_in_memory().unwrap();
+        db.upsert_plugin_config(&make_config("jira", r#"{"apiToken":"a"}"#))
+            .unwrap();
+        db.record_poll_failure("jira", "401", "auth", 10).unwrap();
+        db.auto_disable_plugin("jira", "Disabled after 5 failures", 20)
+            .unwrap();
+
+        let tripped = db.get_plugin_config("jira").unwrap().unwrap();
+        assert!(!tripped.is_enabled);
+        assert_eq!(tripped.auto_disabled_at, Some(20));
+        assert!(db.get_enabled_plugin_configs().unwrap().is_empty());
+
+        db.reset_circuit_breaker("jira").unwrap();
+        let reset = db.get_plugin_config("jira").unwrap().unwrap();
+        assert!(reset.is_enabled);
+        assert_eq!(reset.error_count, 0);
+        assert_eq!(reset.auto_disabled_at, None);
+    }
+
+    #[test]
+    fn reset_circuit_breaker_keeps_user_disabled() {
+        let db = Database::new_in_memory().unwrap();
+        let mut config = make_config("jira", r#"{"apiToken":"a"}"#);
+        config.is_enabled = false;
+        config.error_count = 3;
+        db.upsert_plugin_config(&config).unwrap();
+
+        db.reset_circuit_breaker("jira").unwrap();
+        let got = db.get_plugin_config("jira").unwrap().unwrap();
+        assert!(!got.is_enabled);
+        assert_eq!(got.error_count, 0);
+    }
+
+    #[test]
+    fn record_poll_failure_unknown_plugin() {
+        let db = Database::new_in_memory().unwrap();
+        assert_eq!(
+            db.record_poll_failure("nope", "boom", "failed", 1).unwrap(),
+            0
+        );
+        assert!(db.get_plugin_config("nope").unwrap().is_none());
     }
 
     #[test]
@@ -773,6 +2732,10 @@ mod tests {
             last_error: None,
             error_count: 0,
             settings: None,
+            last_error_at: None,
+            auto_disabled_at: None,
+            last_error_kind: None,
+            retry_at: None,
         };
         db.upsert_plugin_config(&config).unwrap();
 
@@ -785,9 +2748,52 @@ mod tests {
     }
 
     #[test]
-    fn seed_default_weights() {
+    fn save_plugin_config_keeps_breaker_state() {
+        let db = Database::new_in_memory().unwrap();
+        let mut config = make_config("jira", r#"{"apiToken":"a"}"#);
+        config.settings = Some(r#"{"timeout_secs":15}"#.to_string());
+        db.save_plugin_config(&config).unwrap();
+        db.record_poll_failure("jira", "401", "auth", 10).unwrap();
+        db.auto_disable_plugin("jira", "Disabled after 5 failures", 20)
+            .unwrap();
+
+        // What the Settings form sends: zeroed counters, no settings.
+        let mut edited = make_config("jira", r#"{"apiToken":"b"}"#);
+        edited.poll_interval_secs = 120;
+        edited.settings = None;
+        db.save_plugin_config(&edited).unwrap();
+
+        let got = db.get_plugin_config("jira").unwrap().unwrap();
+        assert!(got.is_enabled);
+        assert_eq!(
+            db.reveal_credentials(got.credentials.as_deref().unwrap())
+                .unwrap(),
+            r#"{"apiToken":"b"}"#
+        );
+        assert_eq!(got.poll_interval_secs, 120);
+        assert_eq!(got.settings.as_deref(), Some(r#"{"timeout_secs":15}"#));
+        assert_eq!(got.error_count, 1);
+        assert_eq!(got.auto_disabled_at, Some(20));
+        assert_eq!(got.last_error_kind.as_deref(), Some("auth"));
+    }
+
+    /// The weights jira.manifest.json declares.
+    fn jira_weights() -> Vec<(String, i32)> {
+        [
+            ("assigned_to_me", 3),
+            ("priority_p1_blocker", 4),
+            ("mentioned_in_comment", 2),
+            ("deadline_24h", 3),
+        ]
+        .iter()
+        .map(|(signal, weight)| (signal.to_string(), *weight))
+        .collect()
+    }
+
+    #[test]
+    fn seed_weights_inserts_defaults() {
         let db = Database::new_in_memory().unwrap();
-        db.seed_default_weights().unwrap();
+        db.seed_weights("jira", &jira_weights()).unwrap();
 
         let weights = db.get_weights("jira").unwrap();
         assert_eq!(weights.len(), 4);
@@ -802,10 +2808,158 @@ mod tests {
     #[test]
     fn seed_weights_idempotent() {
         let db = Database::new_in_memory().unwrap();
-        db.seed_default_weights().unwrap();
-        db.seed_default_weights().unwrap();
+        db.seed_weights("jira", &jira_weights()).unwrap();
+        db.seed_weights("jira", &jira_weights()).unwrap();
 
         let weights = db.get_weights("jira").unwrap();
         assert_eq!(weights.len(), 4);
     }
+
+    #[test]
+    fn seed_weights_keeps_user_overrides() {
+        let db = Database::new_in_memory().unwrap();
+        db.seed_weights("jira", &jira_weights()).unwrap();
+        db.upsert_weight(&HeuristicWeight {
+            id: "jira-deadline_24h".to_string(),
+            source: "jira".to_string(),
+            signal: "deadline_24h".to_string(),
+            weight: 7,
+        })
+        .unwrap();
+
+        // Startup re-seeds every launch; it must not reset tuned weights.
+        db.seed_weights("jira", &jira_weights()).unwrap();
+
+        let weights = db.get_weights("jira").unwrap();
+        let deadline = weights.iter().find(|w| w.signal == "deadline_24h").unwrap();
+        assert_eq!(deadline.weight, 7);
+    }
+
+    #[test]
+    fn sync_cursor_round_trip() {
+        let db = Database::new_in_memory().unwrap();
+        assert_eq!(db.get_sync_cursor("github").unwrap(), None);
+
+        db.set_sync_cursor("github", Some(r#"{"since":100}"#), 100)
+            .unwrap();
+        db.set_sync_cursor("github", Some(r#"{"since":200}"#), 200)
+            .unwrap();
+        assert_eq!(
+            db.get_sync_cursor("github").unwrap().as_deref(),
+            Some(r#"{"since":200}"#)
+        );
+        assert_eq!(db.get_sync_cursor("jira").unwrap(), None);
+
+        db.set_sync_cursor("github", None, 300).unwrap();
+        assert_eq!(db.get_sync_cursor("github").unwrap(), None);
+    }
+
+    #[test]
+    fn migration_adds_signals_column_to_legacy_items() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE items (
+                id TEXT PRIMARY KEY,
+                source TEXT NOT NULL,
+                source_id TEXT NOT NULL,
+                item_type TEXT NOT NULL,
+                title TEXT NOT NULL,
+                summary TEXT,
+                url TEXT NOT NULL,
+                author TEXT,
+                timestamp INTEGER NOT NULL,
+                priority INTEGER DEFAULT 0,
+                metadata TEXT,
+                tags TEXT,
+                is_read INTEGER DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                UNIQUE(source, source_id)
+            );
+            INSERT INTO items (id, source, source_id, item_type, title, url, timestamp,
+                               created_at, updated_at)
+            VALUES ('jira-OLD-1', 'jira', 'OLD-1', 'ticket', 'Old', 'https://x', 1, 1, 1);",
+        )
+        .unwrap();
+        let db = Database { conn, cipher: None };
+        db.run_migrations().unwrap();
+
+        let items = db.get_items(&ItemQuery::default()).unwrap().items;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].signals, None);
+
+        db.upsert_item(&make_item()).unwrap();
+        assert_eq!(db.get_items(&ItemQuery::default()).unwrap().items.len(), 2);
+    }
+
+    fn test_cipher() -> CredentialCipher {
+        CredentialCipher::from_key(&[42u8; 32])
+    }
+
+    fn make_config(plugin_id: &str, credentials: &str) -> PluginConfig {
+        PluginConfig {
+            plugin_id: plugin_id.to_string(),
+            is_enabled: true,
+            credentials: Some(credentials.to_string()),
+            poll_interval_secs: 300,
+            last_poll_at: None,
+            last_error: None,
+            error_count: 0,
+            settings: None,
+            last_error_at: None,
+            auto_disabled_at: None,
+            last_error_kind: None,
+            retry_at: None,
+        }
+    }
+
+    #[test]
+    fn credentials_sealed_on_write() {
+        let mut db = Database::new_in_memory().unwrap();
+        db.enable_encryption(test_cipher()).unwrap();
+        db.upsert_plugin_config(&make_config("github", r#"{"token":"ghp_secret"}"#))
+            .unwrap();
+
+        let stored = db.get_plugin_config("github").unwrap().unwrap().credentials.unwrap();
+        assert!(crypto::is_sealed(&stored));
+        assert!(!stored.contains("ghp_secret"));
+        assert_eq!(
+            db.reveal_credentials(&stored).unwrap(),
+            r#"{"token":"ghp_secret"}"#
+        );
+
+        // Re-saving a config read back from the DB must not double-encrypt.
+        let config = db.get_plugin_config("github").unwrap().unwrap();
+        db.upsert_plugin_config(&config).unwrap();
+        let again = db.get_plugin_config("github").unwrap().unwrap().credentials.unwrap();
+        assert_eq!(db.reveal_credentials(&again).unwrap(), r#"{"token":"ghp_secret"}"#);
+    }
+
+    #[test]
+    fn enable_encryption_migrates_plaintext_rows() {
+        let mut db = Database::new_in_memory().unwrap();
+        db.upsert_plugin_config(&make_config("jira", r#"{"apiToken":"a"}"#))
+            .unwrap();
+        db.upsert_plugin_config(&make_config("gmail", r#"{"refreshToken":"b"}"#))
+            .unwrap();
+
+        assert_eq!(db.enable_encryption(test_cipher()).unwrap(), 2);
+
+        for (id, plain) in [("jira", r#"{"apiToken":"a"}"#), ("gmail", r#"{"refreshToken":"b"}"#)] {
+            let stored = db.get_plugin_config(id).unwrap().unwrap().credentials.unwrap();
+            assert!(crypto::is_sealed(&stored));
+            assert_eq!(db.reveal_credentials(&stored).unwrap(), plain);
+        }
+
+        // Second run finds nothing left to migrate.
+        assert_eq!(db.encrypt_plaintext_credentials().unwrap(), 0);
+    }
+
+    #[test]
+    fn reveal_sealed_without_key_fails() {
+        let sealed = test_cipher().seal("secret").unwrap();
+        let db = Database::new_in_memory().unwrap();
+        assert!(db.reveal_credentials(&sealed).is_err());
+        assert_eq!(db.reveal_credentials("plain").unwrap(), "plain");
+    }
 }
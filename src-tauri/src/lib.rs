@@ -1,15 +1,25 @@
 mod commands;
+mod crypto;
 mod db;
+mod migrations;
 mod models;
 mod notifications;
+mod plugin_host;
+mod plugin_registry;
 mod plugin_runtime;
+mod quiet_hours;
 mod scheduler;
+mod scoring;
 
 use std::sync::{Arc, Mutex};
 
 use commands::AppState;
+use crypto::CredentialCipher;
 use db::Database;
-use tauri::Manager;
+use plugin_host::PluginHosts;
+use plugin_registry::PluginRegistry;
+use scheduler::ActivePolls;
+use tauri::{Manager, RunEvent};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -26,42 +36,90 @@ pub fn run() {
                 .map_err(|e| format!("Failed to create app data dir: {}", e))?;
 
             let db_path = app_dir.join("nexus-hub.db");
-            let db = Arc::new(Mutex::new(
-                Database::new(db_path)
-                    .map_err(|e| format!("Failed to init database: {}", e))?,
-            ));
-            db.lock()
-                .map_err(|e| format!("DB lock poisoned during setup: {}", e))?
-                .seed_default_weights()
-                .map_err(|e| format!("Failed to seed default weights: {}", e))?;
-
+            let mut database =
+                Database::new(db_path).map_err(|e| format!("Failed to init database: {}", e))?;
+            let cipher = CredentialCipher::load(&app_dir)
+                .map_err(|e| format!("Failed to load credential key: {}", e))?;
+            let migrated = database
+                .enable_encryption(cipher)
+                .map_err(|e| format!("Failed to encrypt stored credentials: {}", e))?;
+            if migrated > 0 {
+                println!("[setup] Encrypted {} plaintext credential row(s)", migrated);
+            }
             let plugins_dir = app
                 .path()
                 .resource_dir()
                 .map_err(|e| format!("Failed to get resource dir: {}", e))?
                 .join("plugins");
+            let registry = PluginRegistry::scan(&plugins_dir);
+            registry
+                .seed_weights(&database)
+                .map_err(|e| format!("Failed to seed plugin weights: {}", e))?;
+
+            notifications::refresh_badge(app.handle(), &database);
+
+            let db = Arc::new(Mutex::new(database));
+            let registry = Arc::new(Mutex::new(registry));
+            let polls = Arc::new(ActivePolls::default());
+            let hosts = Arc::new(PluginHosts::default());
 
             app.manage(AppState {
                 db: Arc::clone(&db),
-                plugins_dir: plugins_dir.clone(),
+                registry: Arc::clone(&registry),
+                polls: Arc::clone(&polls),
+                hosts: Arc::clone(&hosts),
             });
 
-            scheduler::start_polling(app.handle().clone(), Arc::clone(&db), plugins_dir);
+            scheduler::start_polling(
+                app.handle().clone(),
+                Arc::clone(&db),
+                registry,
+                Arc::clone(&polls),
+                hosts,
+            );
+            scheduler::start_maintenance(Arc::clone(&db), polls);
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::get_items,
+            commands::search_items,
+            commands::get_views,
+            commands::save_view,
+            commands::delete_view,
+            commands::get_view_items,
+            commands::get_view_unread_counts,
             commands::mark_read,
+            commands::snooze_item,
+            commands::snooze_notification,
+            commands::set_item_flags,
             commands::get_notifications,
             commands::dismiss_notification,
             commands::dismiss_all_notifications,
+            commands::get_quiet_hours,
+            commands::set_quiet_hours,
+            commands::get_notification_rules,
+            commands::save_notification_rule,
+            commands::delete_notification_rule,
             commands::get_app_setting,
             commands::set_app_setting,
             commands::get_plugin_config,
             commands::save_plugin_config,
             commands::refresh_plugin,
+            commands::list_plugins,
+            commands::test_plugin_connection,
+            commands::get_heuristic_weights,
+            commands::set_heuristic_weight,
+            commands::get_maintenance_runs,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                // Kill in-flight polls and the Deno hosts instead of orphaning them.
+                let state = app.state::<AppState>();
+                state.polls.cancel_all();
+                state.hosts.shutdown();
+            }
+        });
 }
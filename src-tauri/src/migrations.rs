@@ -0,0 +1,675 @@
+//! Versioned schema migrations.
+//!
+//! The schema version is stored in `PRAGMA user_version`. Each entry of
+//! [`MIGRATIONS`] moves the database from version `n` to `n + 1` (its position
+//! in the list, counting from 1) inside its own transaction, so a failed step
+//! leaves the database at the last good version. Fresh databases run the same
+//! steps as upgraded ones, which keeps column order — and the index-based row
+//! mapping in `db.rs` — identical for both.
+//!
+//! Append new migrations to the end; never edit or reorder shipped ones.
+//!
+//! Databases created before versioning existed are at version 0 but may
+//! already contain any of the changes up to version 8. Those steps are
+//! therefore written to be idempotent; later ones can assume the exact schema
+//! of the previous version.
+
+use rusqlite::{params, Connection, Result};
+
+pub struct Migration {
+    pub description: &'static str,
+    pub apply: fn(&Connection) -> Result<()>,
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        description: "initial schema",
+        apply: initial_schema,
+    },
+    Migration {
+        description: "items.signals for the scoring engine",
+        apply: |conn| add_column_if_missing(conn, "items", "signals", "TEXT"),
+    },
+    Migration {
+        description: "plugin_config.last_error_at",
+        apply: |conn| add_column_if_missing(conn, "plugin_config", "last_error_at", "INTEGER"),
+    },
+    Migration {
+        description: "plugin_config.auto_disabled_at for the circuit breaker",
+        apply: |conn| add_column_if_missing(conn, "plugin_config", "auto_disabled_at", "INTEGER"),
+    },
+    Migration {
+        description: "typed plugin failures and rate-limit pauses",
+        apply: |conn| {
+            add_column_if_missing(conn, "plugin_config", "last_error_kind", "TEXT")?;
+            add_column_if_missing(conn, "plugin_config", "retry_at", "INTEGER")
+        },
+    },
+    Migration {
+        description: "plugin_sync_state for incremental sync cursors",
+        apply: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS plugin_sync_state (
+                    plugin_id TEXT PRIMARY KEY,
+                    cursor TEXT NOT NULL,
+                    updated_at INTEGER NOT NULL
+                );",
+            )
+        },
+    },
+    Migration {
+        description: "items.archived_at for items gone upstream",
+        apply: |conn| {
+            add_column_if_missing(conn, "items", "archived_at", "INTEGER")?;
+            conn.execute_batch(
+                "CREATE INDEX IF NOT EXISTS idx_items_archived_at ON items(archived_at);",
+            )
+        },
+    },
+    Migration {
+        description: "maintenance_runs log",
+        apply: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS maintenance_runs (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    ran_at INTEGER NOT NULL,
+                    items_pruned INTEGER NOT NULL,
+                    notifications_pruned INTEGER NOT NULL,
+                    vacuumed INTEGER NOT NULL
+                );",
+            )
+        },
+    },
+    Migration {
+        description: "items_fts full-text index",
+        apply: |conn| {
+            // `item_id` rather than the items rowid: VACUUM may renumber the
+            // rowids of a table without an INTEGER PRIMARY KEY.
+            conn.execute_batch(&format!(
+                "CREATE VIRTUAL TABLE items_fts USING fts5(
+                    item_id UNINDEXED, title, summary, author, tags, metadata,
+                    tokenize = 'porter unicode61 remove_diacritics 2'
+                );
+                INSERT INTO items_fts (item_id, title, summary, author, tags, metadata)
+                    {};
+                CREATE TRIGGER items_fts_delete AFTER DELETE ON items BEGIN
+                    DELETE FROM items_fts WHERE item_id = old.id;
+                END;",
+                ITEMS_FTS_ROW
+            ))
+        },
+    },
+    Migration {
+        description: "saved_views",
+        apply: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE saved_views (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    query TEXT NOT NULL,
+                    min_age_secs INTEGER,
+                    max_age_secs INTEGER,
+                    position INTEGER NOT NULL DEFAULT 0,
+                    created_at INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL
+                );",
+            )
+        },
+    },
+    Migration {
+        description: "snoozed_until on items and notifications",
+        apply: |conn| {
+            conn.execute_batch(
+                "ALTER TABLE items ADD COLUMN snoozed_until INTEGER;
+                 ALTER TABLE notifications ADD COLUMN snoozed_until INTEGER;
+                 CREATE INDEX idx_items_snoozed_until ON items(snoozed_until);
+                 CREATE INDEX idx_notifications_snoozed_until ON notifications(snoozed_until);",
+            )
+        },
+    },
+    Migration {
+        // Kept out of `items` so that `upsert_item` can never overwrite it.
+        description: "item_flags",
+        apply: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE item_flags (
+                    item_id TEXT PRIMARY KEY REFERENCES items(id) ON DELETE CASCADE,
+                    starred INTEGER NOT NULL DEFAULT 0,
+                    pinned INTEGER NOT NULL DEFAULT 0,
+                    note TEXT,
+                    labels TEXT
+                );",
+            )
+        },
+    },
+    Migration {
+        description: "escalated_at on notifications",
+        apply: |conn| {
+            conn.execute_batch("ALTER TABLE notifications ADD COLUMN escalated_at INTEGER;")
+        },
+    },
+    Migration {
+        // Existing notifications were shown when they were created.
+        description: "delivered_at on notifications",
+        apply: |conn| {
+            conn.execute_batch(
+                "ALTER TABLE notifications ADD COLUMN delivered_at INTEGER;
+                 UPDATE notifications SET delivered_at = created_at;",
+            )
+        },
+    },
+    Migration {
+        description: "notification_rules",
+        apply: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE notification_rules (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    is_enabled INTEGER NOT NULL DEFAULT 1,
+                    position INTEGER NOT NULL DEFAULT 0,
+                    conditions TEXT NOT NULL,
+                    action TEXT NOT NULL,
+                    created_at INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL
+                );",
+            )
+        },
+    },
+    Migration {
+        // Seeded with the most urgent active notification of each item, so
+        // the first poll after upgrading doesn't mistake it for an escalation.
+        description: "notified_urgency",
+        apply: |conn| {
+            conn.execute_batch(
+                "CREATE TABLE notified_urgency (
+                    item_id TEXT PRIMARY KEY REFERENCES items(id) ON DELETE CASCADE,
+                    urgency TEXT NOT NULL,
+                    updated_at INTEGER NOT NULL
+                );
+                INSERT INTO notified_urgency (item_id, urgency, updated_at)
+                SELECT item_id, urgency, created_at FROM (
+                    SELECT item_id, urgency, created_at,
+                           MAX(CASE urgency WHEN 'critical' THEN 4 WHEN 'high' THEN 3
+                                            WHEN 'medium' THEN 2 ELSE 1 END)
+                    FROM notifications WHERE is_dismissed = 0 GROUP BY item_id
+                );",
+            )
+        },
+    },
+    Migration {
+        // `item_id` is UNINDEXED in items_fts, so deleting by it scanned the
+        // whole index. Each item now gets a stable fts rowid to delete by;
+        // the index is rebuilt to use them.
+        description: "items_fts_keys",
+        apply: |conn| {
+            conn.execute_batch(&format!(
+                "CREATE TABLE items_fts_keys (
+                    fts_rowid INTEGER PRIMARY KEY,
+                    item_id TEXT NOT NULL UNIQUE
+                );
+                INSERT INTO items_fts_keys (item_id) SELECT id FROM items;
+                DELETE FROM items_fts;
+                INSERT INTO items_fts (rowid, item_id, title, summary, author, tags, metadata)
+                    SELECT k.fts_rowid, r.* FROM ({}) r
+                    JOIN items_fts_keys k ON k.item_id = r.id;
+                DROP TRIGGER items_fts_delete;
+                CREATE TRIGGER items_fts_delete AFTER DELETE ON items BEGIN
+                    DELETE FROM items_fts WHERE rowid =
+                        (SELECT fts_rowid FROM items_fts_keys WHERE item_id = old.id);
+                    DELETE FROM items_fts_keys WHERE item_id = old.id;
+                END;",
+                ITEMS_FTS_ROW
+            ))
+        },
+    },
+];
+
+/// The `items_fts` columns for the rows of `items i`, with the tags and
+/// metadata JSON flattened to their string values. Used by the backfill above
+/// and by `Database::upsert_item`, which keeps the index in sync.
+pub const ITEMS_FTS_ROW: &str = "
+    SELECT i.id, i.title, i.summary, i.author,
+        (SELECT group_concat(value, ' ') FROM json_tree(
+            CASE WHEN json_valid(i.tags) THEN i.tags ELSE 'null' END) WHERE type = 'text'),
+        (SELECT group_concat(value, ' ') FROM json_tree(
+            CASE WHEN json_valid(i.metadata) THEN i.metadata ELSE 'null' END) WHERE type = 'text')
+    FROM items i";
+
+/// Schema version of a fully migrated database.
+pub fn latest_version() -> usize {
+    MIGRATIONS.len()
+}
+
+pub fn schema_version(conn: &Connection) -> Result<usize> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))
+        .map(|v| v.max(0) as usize)
+}
+
+/// Bring the database up to the latest version.
+pub fn run(conn: &Connection) -> Result<()> {
+    apply(conn, MIGRATIONS, latest_version())
+}
+
+/// Apply `migrations` up to and including version `target`.
+fn apply(conn: &Connection, migrations: &[Migration], target: usize) -> Result<()> {
+    let current = schema_version(conn)?;
+    if current > migrations.len() {
+        eprintln!(
+            "[db] Schema version {} is newer than this build ({}); skipping migrations",
+            current,
+            migrations.len()
+        );
+        return Ok(());
+    }
+
+    for (index, migration) in migrations.iter().enumerate().take(target).skip(current) {
+        let version = index + 1;
+        let tx = conn.unchecked_transaction()?;
+        (migration.apply)(&tx).map_err(|e| {
+            eprintln!(
+                "[db] Migration {} ({}) failed: {}",
+                version, migration.description, e
+            );
+            e
+        })?;
+        tx.pragma_update(None, "user_version", version as i64)?;
+        tx.commit()?;
+        println!(
+            "[db] Applied migration {} ({})",
+            version, migration.description
+        );
+    }
+    Ok(())
+}
+
+/// Version 1: the schema as first shipped.
+fn initial_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        CREATE TABLE IF NOT EXISTS items (
+            id TEXT PRIMARY KEY,
+            source TEXT NOT NULL,
+            source_id TEXT NOT NULL,
+            item_type TEXT NOT NULL,
+            title TEXT NOT NULL,
+            summary TEXT,
+            url TEXT NOT NULL,
+            author TEXT,
+            timestamp INTEGER NOT NULL,
+            priority INTEGER DEFAULT 0,
+            metadata TEXT,
+            tags TEXT,
+            is_read INTEGER DEFAULT 0,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            UNIQUE(source, source_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS notifications (
+            id TEXT PRIMARY KEY,
+            item_id TEXT NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+            reason TEXT NOT NULL,
+            urgency TEXT NOT NULL,
+            is_dismissed INTEGER DEFAULT 0,
+            created_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS plugin_config (
+            plugin_id TEXT PRIMARY KEY,
+            is_enabled INTEGER DEFAULT 1,
+            credentials TEXT,
+            poll_interval_secs INTEGER DEFAULT 600,
+            last_poll_at INTEGER,
+            last_error TEXT,
+            error_count INTEGER DEFAULT 0,
+            settings TEXT
+        );
+
+        CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS heuristic_weights (
+            id TEXT PRIMARY KEY,
+            source TEXT NOT NULL,
+            signal TEXT NOT NULL,
+            weight INTEGER NOT NULL,
+            UNIQUE(source, signal)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_items_source ON items(source);
+        CREATE INDEX IF NOT EXISTS idx_items_timestamp ON items(timestamp DESC);
+        CREATE INDEX IF NOT EXISTS idx_items_priority ON items(priority DESC);
+        CREATE INDEX IF NOT EXISTS idx_items_is_read ON items(is_read);
+        CREATE INDEX IF NOT EXISTS idx_notifications_urgency ON notifications(urgency);
+        CREATE INDEX IF NOT EXISTS idx_notifications_dismissed ON notifications(is_dismissed);
+    ",
+    )?;
+
+    // The earliest dev databases created `notifications` without ON DELETE
+    // CASCADE; rebuild it (rename → create → copy → drop) if so.
+    let cascades: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_foreign_key_list('notifications')
+         WHERE \"table\" = 'items' AND on_delete = 'CASCADE'",
+        [],
+        |row| row.get::<_, i32>(0).map(|n| n > 0),
+    )?;
+    if !cascades {
+        conn.execute_batch(
+            "
+            ALTER TABLE notifications RENAME TO _notifications_old;
+
+            CREATE TABLE notifications (
+                id TEXT PRIMARY KEY,
+                item_id TEXT NOT NULL REFERENCES items(id) ON DELETE CASCADE,
+                reason TEXT NOT NULL,
+                urgency TEXT NOT NULL,
+                is_dismissed INTEGER DEFAULT 0,
+                created_at INTEGER NOT NULL
+            );
+
+            INSERT INTO notifications SELECT * FROM _notifications_old;
+            DROP TABLE _notifications_old;
+
+            CREATE INDEX IF NOT EXISTS idx_notifications_urgency ON notifications(urgency);
+            CREATE INDEX IF NOT EXISTS idx_notifications_dismissed ON notifications(is_dismissed);
+        ",
+        )?;
+    }
+    Ok(())
+}
+
+/// `ALTER TABLE ... ADD COLUMN` unless the column already exists.
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+        params![table, column],
+        |row| row.get::<_, i32>(0).map(|n| n > 0),
+    )?;
+
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {};",
+            table, column, decl
+        ))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Last migration whose change could already exist in an unversioned database.
+    const LEGACY_VERSION: usize = 8;
+
+    fn open() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        conn
+    }
+
+    /// Tables, indexes and every column (in order) of the database.
+    fn schema(conn: &Connection) -> Vec<String> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT type, name FROM sqlite_master
+                 WHERE name NOT LIKE 'sqlite_%' ORDER BY type, name",
+            )
+            .unwrap();
+        let objects: Vec<(String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        let mut schema = Vec::new();
+        for (kind, name) in objects {
+            schema.push(format!("{} {}", kind, name));
+            if kind != "table" {
+                continue;
+            }
+            let mut cols = conn
+                .prepare(
+                    "SELECT cid, name, type, \"notnull\", dflt_value, pk
+                     FROM pragma_table_info(?1) ORDER BY cid",
+                )
+                .unwrap();
+            let columns = cols
+                .query_map(params![name], |row| {
+                    Ok(format!(
+                        "  {}.{} {} notnull={} default={:?} pk={}",
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, i64>(3)?,
+                        row.get::<_, Option<String>>(4)?,
+                        row.get::<_, i64>(5)?,
+                    ))
+                })
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            schema.extend(columns);
+        }
+        schema
+    }
+
+    fn latest_schema() -> Vec<String> {
+        let conn = open();
+        run(&conn).unwrap();
+        schema(&conn)
+    }
+
+    /// Rows that every version since 1 can hold.
+    fn insert_fixture_rows(conn: &Connection) {
+        conn.execute_batch(
+            "INSERT INTO items (id, source, source_id, item_type, title, url, timestamp,
+                                created_at, updated_at)
+             VALUES ('jira-OLD-1', 'jira', 'OLD-1', 'ticket', 'Old', 'https://x', 1, 1, 1);
+             INSERT INTO notifications (id, item_id, reason, urgency, created_at)
+             VALUES ('n1', 'jira-OLD-1', 'assigned', 'medium', 1);
+             INSERT INTO plugin_config (plugin_id, credentials) VALUES ('jira', '{}');
+             INSERT INTO app_settings (key, value) VALUES ('quiet_hours_start', '22');",
+        )
+        .unwrap();
+    }
+
+    fn assert_fixture_rows_survived(conn: &Connection) {
+        let count = |sql: &str| conn.query_row(sql, [], |r| r.get::<_, i64>(0)).unwrap();
+        assert_eq!(count("SELECT COUNT(*) FROM items"), 1);
+        assert_eq!(count("SELECT COUNT(*) FROM notifications"), 1);
+        assert_eq!(count("SELECT COUNT(*) FROM plugin_config"), 1);
+        assert_eq!(count("SELECT COUNT(*) FROM app_settings"), 1);
+
+        // Notifications still cascade with their item.
+        conn.execute("DELETE FROM items WHERE id = 'jira-OLD-1'", [])
+            .unwrap();
+        assert_eq!(count("SELECT COUNT(*) FROM notifications"), 0);
+        assert_eq!(count("SELECT COUNT(*) FROM items_fts"), 0);
+        assert_eq!(count("SELECT COUNT(*) FROM items_fts_keys"), 0);
+    }
+
+    #[test]
+    fn fresh_database_reaches_latest_version() {
+        let conn = open();
+        run(&conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), latest_version());
+
+        // Running again is a no-op.
+        run(&conn).unwrap();
+        assert_eq!(schema(&conn), latest_schema());
+    }
+
+    #[test]
+    fn upgrades_from_every_version() {
+        let latest = latest_schema();
+        for version in 1..=latest_version() {
+            let conn = open();
+            apply(&conn, MIGRATIONS, version).unwrap();
+            assert_eq!(schema_version(&conn).unwrap(), version);
+            insert_fixture_rows(&conn);
+
+            run(&conn).unwrap();
+            assert_eq!(schema_version(&conn).unwrap(), latest_version());
+            assert_eq!(schema(&conn), latest, "upgrade from version {}", version);
+            assert_fixture_rows_survived(&conn);
+        }
+    }
+
+    #[test]
+    fn notified_urgency_is_seeded_from_active_notifications() {
+        let conn = open();
+        apply(&conn, MIGRATIONS, 15).unwrap();
+        insert_fixture_rows(&conn);
+        run(&conn).unwrap();
+        let urgency: String = conn
+            .query_row(
+                "SELECT urgency FROM notified_urgency WHERE item_id = 'jira-OLD-1'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(urgency, "medium");
+    }
+
+    #[test]
+    fn adopts_unversioned_database_without_cascade() {
+        // The earliest dev schema: version 0, notifications FK without CASCADE.
+        let conn = open();
+        conn.execute_batch(
+            "CREATE TABLE items (
+                id TEXT PRIMARY KEY,
+                source TEXT NOT NULL,
+                source_id TEXT NOT NULL,
+                item_type TEXT NOT NULL,
+                title TEXT NOT NULL,
+                summary TEXT,
+                url TEXT NOT NULL,
+                author TEXT,
+                timestamp INTEGER NOT NULL,
+                priority INTEGER DEFAULT 0,
+                metadata TEXT,
+                tags TEXT,
+                is_read INTEGER DEFAULT 0,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                UNIQUE(source, source_id)
+            );
+            CREATE TABLE notifications (
+                id TEXT PRIMARY KEY,
+                item_id TEXT NOT NULL REFERENCES items(id),
+                reason TEXT NOT NULL,
+                urgency TEXT NOT NULL,
+                is_dismissed INTEGER DEFAULT 0,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE plugin_config (
+                plugin_id TEXT PRIMARY KEY,
+                is_enabled INTEGER DEFAULT 1,
+                credentials TEXT,
+                poll_interval_secs INTEGER DEFAULT 600,
+                last_poll_at INTEGER,
+                last_error TEXT,
+                error_count INTEGER DEFAULT 0,
+                settings TEXT
+            );
+            CREATE TABLE app_settings (key TEXT PRIMARY KEY, value TEXT NOT NULL);",
+        )
+        .unwrap();
+        insert_fixture_rows(&conn);
+
+        run(&conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), latest_version());
+        assert_eq!(schema(&conn), latest_schema());
+        assert_fixture_rows_survived(&conn);
+    }
+
+    #[test]
+    fn adopts_unversioned_database_with_later_changes() {
+        // A database from the last build before versioning: every change up to
+        // LEGACY_VERSION is present, but user_version was never set.
+        let conn = open();
+        apply(&conn, MIGRATIONS, LEGACY_VERSION).unwrap();
+        insert_fixture_rows(&conn);
+        conn.pragma_update(None, "user_version", 0).unwrap();
+
+        run(&conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), latest_version());
+        assert_eq!(schema(&conn), latest_schema());
+        assert_fixture_rows_survived(&conn);
+    }
+
+    #[test]
+    fn fts_index_is_backfilled() {
+        let conn = open();
+        apply(&conn, MIGRATIONS, 8).unwrap();
+        insert_fixture_rows(&conn);
+
+        run(&conn).unwrap();
+        let hits: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM items_fts WHERE items_fts MATCH 'old' AND item_id = 'jira-OLD-1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(hits, 1);
+    }
+
+    #[test]
+    fn fts_rows_are_keyed_by_rowid() {
+        let conn = open();
+        apply(&conn, MIGRATIONS, 16).unwrap();
+        insert_fixture_rows(&conn);
+        conn.execute_batch(&format!(
+            "INSERT INTO items_fts (item_id, title, summary, author, tags, metadata) {}",
+            ITEMS_FTS_ROW
+        ))
+        .unwrap();
+
+        run(&conn).unwrap();
+        let keyed: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM items_fts f JOIN items_fts_keys k
+                 ON k.fts_rowid = f.rowid AND k.item_id = f.item_id",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(keyed, 1);
+    }
+
+    #[test]
+    fn failed_migration_rolls_back() {
+        let migrations = [
+            Migration {
+                description: "ok",
+                apply: |conn| conn.execute_batch("CREATE TABLE a (x INTEGER);"),
+            },
+            Migration {
+                description: "fails halfway",
+                apply: |conn| conn.execute_batch("CREATE TABLE b (x INTEGER); NOT SQL;"),
+            },
+        ];
+        let conn = open();
+        assert!(apply(&conn, &migrations, migrations.len()).is_err());
+
+        assert_eq!(schema_version(&conn).unwrap(), 1);
+        assert_eq!(
+            schema(&conn),
+            vec!["table a", "  0.x INTEGER notnull=0 default=None pk=0"]
+        );
+    }
+
+    #[test]
+    fn newer_database_is_left_alone() {
+        let conn = open();
+        conn.pragma_update(None, "user_version", latest_version() as i64 + 1)
+            .unwrap();
+        run(&conn).unwrap();
+        assert!(schema(&conn).is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NexusItem {
@@ -17,6 +18,28 @@ pub struct NexusItem {
     pub is_read: bool,
     pub created_at: i64,
     pub updated_at: i64,
+    pub signals: Option<String>,    // JSON array of signal names
+    pub archived_at: Option<i64>,   // set when the item disappeared upstream
+    pub snoozed_until: Option<i64>, // hidden from the feed until then
+    #[serde(default)]
+    pub flags: ItemFlags, // user-owned; never written by `upsert_item`
+}
+
+/// What the user has set on an item, kept in `item_flags` apart from the
+/// plugin-owned fields so that re-polls and retention leave it alone.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ItemFlags {
+    pub starred: bool,
+    pub pinned: bool, // listed before everything else in `get_items`
+    pub note: Option<String>,
+    pub labels: Vec<String>,
+}
+
+impl ItemFlags {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,18 +50,25 @@ pub struct Notification {
     pub urgency: String,
     pub is_dismissed: bool,
     pub created_at: i64,
+    pub snoozed_until: Option<i64>, // hidden until then, then shown again
+    pub escalated_at: Option<i64>,  // last time the escalation ladder re-fired it
+    pub delivered_at: Option<i64>,  // None while waiting for a batch or digest
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginConfig {
     pub plugin_id: String,
     pub is_enabled: bool,
-    pub credentials: Option<String>, // Encrypted JSON
+    pub credentials: Option<String>, // JSON; sealed at rest by `crypto`
     pub poll_interval_secs: i64,
     pub last_poll_at: Option<i64>,
     pub last_error: Option<String>,
     pub error_count: i32,
     pub settings: Option<String>, // JSON
+    pub last_error_at: Option<i64>,
+    pub auto_disabled_at: Option<i64>, // set when the circuit breaker trips
+    pub last_error_kind: Option<String>, // PluginError kind of the last failure
+    pub retry_at: Option<i64>,         // rate-limited: don't poll before this
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,3 +78,178 @@ pub struct HeuristicWeight {
     pub signal: String,
     pub weight: i32,
 }
+
+/// Outcome of one database maintenance pass (see `scheduler::run_maintenance`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MaintenanceRun {
+    pub ran_at: i64,
+    pub items_pruned: usize,
+    pub notifications_pruned: usize,
+    pub vacuumed: bool,
+}
+
+/// Sort order of `get_items` after pinned items; always descending, ties
+/// broken by item id.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemSort {
+    #[default]
+    Timestamp,
+    Priority,
+    UpdatedAt,
+}
+
+impl ItemSort {
+    pub fn column(self) -> &'static str {
+        match self {
+            ItemSort::Timestamp => "timestamp",
+            ItemSort::Priority => "priority",
+            ItemSort::UpdatedAt => "updated_at",
+        }
+    }
+}
+
+/// Filters, sort and page position for `get_items`. Every field is optional
+/// in the command payload; list filters match any of their values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ItemQuery {
+    pub sources: Vec<String>,
+    pub item_types: Vec<String>,
+    pub tags: Vec<String>,
+    pub signals: Vec<String>,
+    pub author: Option<String>, // case-insensitive
+    pub since: Option<i64>,     // timestamp >= since
+    pub until: Option<i64>,     // timestamp < until
+    pub min_priority: Option<i32>,
+    /// Metadata values by key (`a.b` for nested keys), all of which must match.
+    pub metadata: BTreeMap<String, serde_json::Value>,
+    pub unread_only: bool,
+    pub include_archived: bool,
+    pub include_snoozed: bool,
+    pub starred_only: bool,
+    pub pinned_only: bool,
+    pub labels: Vec<String>,
+    pub sort: ItemSort,
+    pub limit: Option<i64>,
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+}
+
+/// One page of `get_items`; `next_cursor` is `None` on the last page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemPage {
+    pub items: Vec<NexusItem>,
+    pub next_cursor: Option<String>,
+}
+
+/// A named `ItemQuery` kept in `saved_views` and shown in the sidebar.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedView {
+    pub id: String, // empty when creating a view
+    pub name: String,
+    pub query: ItemQuery, // cursor and limit are not stored
+    /// Relative bounds, resolved at evaluation time: only items at least /
+    /// at most this many seconds old.
+    pub min_age_secs: Option<i64>,
+    pub max_age_secs: Option<i64>,
+    pub position: i64, // sidebar order
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl SavedView {
+    /// The view's query with its age bounds turned into a time range.
+    pub fn resolve(&self, now: i64) -> ItemQuery {
+        let mut query = self.query.clone();
+        if let Some(max_age) = self.max_age_secs {
+            let since = now - max_age;
+            query.since = Some(query.since.map_or(since, |s| s.max(since)));
+        }
+        if let Some(min_age) = self.min_age_secs {
+            let until = now - min_age;
+            query.until = Some(query.until.map_or(until, |u| u.min(until)));
+        }
+        query
+    }
+}
+
+/// What a notification routing rule matches. Every given condition must
+/// hold; list conditions match any of their values, empty ones anything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuleConditions {
+    pub sources: Vec<String>,
+    pub item_types: Vec<String>,
+    pub signals: Vec<String>, // the item's signals or the notification's reason
+    pub authors: Vec<String>, // case-insensitive
+    pub tags: Vec<String>,
+    /// Item metadata values by key (`a.b` for nested keys).
+    pub metadata: BTreeMap<String, serde_json::Value>,
+}
+
+/// What a matching routing rule does to a notification.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleAction {
+    /// Notify even if focus mode or the escalation ladder would stay silent.
+    #[default]
+    AlwaysNotify,
+    /// Keep it in the app, but never as a native notification.
+    NeverNotify,
+    OverrideUrgency {
+        urgency: String,
+    },
+    BypassQuietHours,
+}
+
+/// A routing rule from `notification_rules`, applied in `position` order by
+/// `notifications::route`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationRule {
+    pub id: String, // empty when creating a rule
+    pub name: String,
+    pub is_enabled: bool,
+    pub position: i64,
+    pub conditions: RuleConditions,
+    pub action: RuleAction,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl Default for NotificationRule {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            is_enabled: true,
+            position: 0,
+            conditions: RuleConditions::default(),
+            action: RuleAction::default(),
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+}
+
+/// Filters for `search_items`; every field is optional in the command payload.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchFilters {
+    pub sources: Vec<String>, // empty = all sources
+    pub unread_only: bool,
+    pub include_archived: bool,
+    pub limit: Option<i64>,
+}
+
+/// One full-text search result. Matched terms in `title_highlight` and
+/// `snippet` are wrapped in `\u{2}` … `\u{3}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub item: NexusItem,
+    pub title_highlight: String,
+    pub snippet: String,
+    pub score: f64, // higher is a better match
+}
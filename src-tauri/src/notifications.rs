@@ -1,8 +1,287 @@
-use tauri::AppHandle;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, UserAttentionType};
 use tauri_plugin_notification::NotificationExt;
+use uuid::Uuid;
 
-use crate::db::Database;
-use crate::models::Notification;
+use crate::db::{Database, PendingDelivery};
+use crate::models::{NexusItem, Notification, NotificationRule, RuleAction, RuleConditions};
+use crate::quiet_hours::QuietSchedule;
+use crate::scoring;
+
+/// Titles listed in the body of a summary notification.
+const SUMMARY_TITLES: usize = 3;
+
+/// What the escalation ladder does for one urgency tier.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EscalationStep {
+    pub badge: bool,           // counted in the window / tray badge
+    pub toast: bool,           // native OS notification
+    pub sound: Option<String>, // played with the toast: "default" or a sound file
+    pub attention: bool,       // flash the taskbar entry / bounce the dock icon
+}
+
+/// The urgency tier → actions mapping, read from the `escalation_ladder` app
+/// setting (JSON in this shape; tiers missing from it keep their defaults).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EscalationLadder {
+    pub low: EscalationStep,
+    pub medium: EscalationStep,
+    pub high: EscalationStep,
+    pub critical: EscalationStep,
+    /// Fire a critical notification's step again once it has stayed
+    /// undismissed this long; 0 never repeats it.
+    pub repeat_critical_after_secs: i64,
+}
+
+impl Default for EscalationLadder {
+    /// Silent badge → notification → sound → attention blink.
+    fn default() -> Self {
+        let badge = EscalationStep {
+            badge: true,
+            ..Default::default()
+        };
+        let toast = EscalationStep {
+            toast: true,
+            ..badge.clone()
+        };
+        let sound = EscalationStep {
+            sound: Some("default".to_string()),
+            ..toast.clone()
+        };
+        let attention = EscalationStep {
+            attention: true,
+            ..sound.clone()
+        };
+        Self {
+            low: badge,
+            medium: toast,
+            high: sound,
+            critical: attention,
+            repeat_critical_after_secs: 15 * 60,
+        }
+    }
+}
+
+impl EscalationLadder {
+    pub fn load(db: &Database) -> Self {
+        match db.get_app_setting("escalation_ladder").ok().flatten() {
+            Some(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                eprintln!("[notifications] Invalid escalation_ladder setting: {}", e);
+                Self::default()
+            }),
+            None => Self::default(),
+        }
+    }
+
+    pub fn step(&self, urgency: &str) -> &EscalationStep {
+        match urgency {
+            "critical" => &self.critical,
+            "high" => &self.high,
+            "medium" => &self.medium,
+            _ => &self.low,
+        }
+    }
+}
+
+/// When low / medium notifications are collected into a digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestMode {
+    Off,
+    Hourly,
+    Daily,
+}
+
+impl DigestMode {
+    fn interval_secs(self) -> Option<i64> {
+        match self {
+            DigestMode::Off => None,
+            DigestMode::Hourly => Some(3600),
+            DigestMode::Daily => Some(86_400),
+        }
+    }
+}
+
+/// How new notifications reach the OS, read from app settings:
+/// `notification_batch_window_secs`, `notification_burst_threshold` and
+/// `notification_digest` (`off`, `hourly` or `daily`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeliveryPolicy {
+    /// Hold non-critical notifications until the oldest is this old, so those
+    /// arriving close together go out together. 0 delivers each poll's
+    /// notifications as soon as it finishes.
+    pub batch_window_secs: i64,
+    /// A source with at least this many notifications in one delivery gets a
+    /// single summary instead of one toast each.
+    pub burst_threshold: usize,
+    pub digest: DigestMode,
+}
+
+impl Default for DeliveryPolicy {
+    fn default() -> Self {
+        Self {
+            batch_window_secs: 0,
+            burst_threshold: 3,
+            digest: DigestMode::Off,
+        }
+    }
+}
+
+impl DeliveryPolicy {
+    pub fn load(db: &Database) -> Self {
+        let defaults = Self::default();
+        let read = |key: &str| {
+            db.get_app_setting(key)
+                .ok()
+                .flatten()
+                .and_then(|v| v.trim().parse::<i64>().ok())
+        };
+        let digest = match db.get_app_setting("notification_digest").ok().flatten() {
+            Some(mode) if mode == "hourly" => DigestMode::Hourly,
+            Some(mode) if mode == "daily" => DigestMode::Daily,
+            _ => defaults.digest,
+        };
+        Self {
+            batch_window_secs: read("notification_batch_window_secs")
+                .filter(|v| *v >= 0)
+                .unwrap_or(defaults.batch_window_secs),
+            burst_threshold: read("notification_burst_threshold")
+                .filter(|v| *v > 0)
+                .map(|v| v as usize)
+                .unwrap_or(defaults.burst_threshold),
+            digest,
+        }
+    }
+}
+
+/// One native notification of a flush; the indices point into the pending list.
+#[derive(Debug, PartialEq, Eq)]
+enum Delivery {
+    /// Kept from the OS by a `NeverNotify` rule.
+    Silent(usize),
+    Single(usize),
+    /// A burst from one source.
+    Summary(Vec<usize>),
+    Digest(Vec<usize>),
+}
+
+/// What the routing rules decided for one notification.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Route {
+    /// Set by the first matching `OverrideUrgency` rule.
+    pub urgency: Option<String>,
+    /// `Some(true)` always notify, `Some(false)` never; the first matching
+    /// rule of either kind wins.
+    pub notify: Option<bool>,
+    pub bypass_quiet_hours: bool,
+}
+
+impl Route {
+    /// Whether a rule asked for this notification to break through.
+    fn is_forced(&self) -> bool {
+        self.notify == Some(true) || self.bypass_quiet_hours
+    }
+}
+
+/// Apply the enabled `rules` (in order) to a notification about `item`.
+pub fn route(rules: &[NotificationRule], notif: &Notification, item: &NexusItem) -> Route {
+    let mut route = Route::default();
+    for rule in rules
+        .iter()
+        .filter(|r| r.is_enabled && rule_matches(&r.conditions, notif, item))
+    {
+        match &rule.action {
+            RuleAction::AlwaysNotify => {
+                route.notify.get_or_insert(true);
+            }
+            RuleAction::NeverNotify => {
+                route.notify.get_or_insert(false);
+            }
+            RuleAction::OverrideUrgency { urgency } => {
+                route.urgency.get_or_insert_with(|| urgency.clone());
+            }
+            RuleAction::BypassQuietHours => route.bypass_quiet_hours = true,
+        }
+    }
+    route
+}
+
+/// The route of a stored notification, looking up its item and the rules.
+fn route_stored(db: &Database, notif: &Notification) -> Route {
+    let rules = db.get_rules().unwrap_or_else(|e| {
+        eprintln!("[notifications] Failed to load notification rules: {}", e);
+        Vec::new()
+    });
+    if rules.is_empty() {
+        return Route::default();
+    }
+    match db.get_item(&notif.item_id) {
+        Ok(Some(item)) => route(&rules, notif, &item),
+        _ => Route::default(),
+    }
+}
+
+fn rule_matches(conditions: &RuleConditions, notif: &Notification, item: &NexusItem) -> bool {
+    let any_of = |values: &[String], candidate: &str| {
+        values.is_empty() || values.iter().any(|v| v == candidate)
+    };
+    if !any_of(&conditions.sources, &item.source)
+        || !any_of(&conditions.item_types, &item.item_type)
+    {
+        return false;
+    }
+    if !conditions.signals.is_empty() {
+        // The reason is only the deciding signal, or whatever a plugin
+        // without signals reported.
+        let signals = scoring::parse_signals(item.signals.as_deref());
+        if !signals
+            .iter()
+            .map(String::as_str)
+            .chain(notif.reason.split(',').map(str::trim))
+            .any(|signal| conditions.signals.iter().any(|s| s == signal))
+        {
+            return false;
+        }
+    }
+    if !conditions.authors.is_empty() {
+        let author = item.author.as_deref().unwrap_or_default();
+        if !conditions
+            .authors
+            .iter()
+            .any(|a| a.eq_ignore_ascii_case(author))
+        {
+            return false;
+        }
+    }
+    if !conditions.tags.is_empty() {
+        let tags: Vec<String> = item
+            .tags
+            .as_deref()
+            .and_then(|t| serde_json::from_str(t).ok())
+            .unwrap_or_default();
+        if !tags.iter().any(|t| conditions.tags.contains(t)) {
+            return false;
+        }
+    }
+    if !conditions.metadata.is_empty() {
+        let metadata: serde_json::Value = item
+            .metadata
+            .as_deref()
+            .and_then(|m| serde_json::from_str(m).ok())
+            .unwrap_or_default();
+        for (key, expected) in &conditions.metadata {
+            let value = key
+                .split('.')
+                .try_fold(&metadata, |value, segment| value.get(segment));
+            if value != Some(expected) {
+                return false;
+            }
+        }
+    }
+    true
+}
 
 /// Map machine-readable signal names to human-readable labels.
 /// Comma-separated reasons are each mapped independently.
@@ -26,17 +305,25 @@ pub fn humanize_reason(reason: &str) -> String {
         .join(", ")
 }
 
-/// Determine whether a native notification should be sent, considering
-/// focus mode and quiet hours settings.
-pub fn should_send_notification(db: &Database, urgency: &str) -> bool {
+/// Determine whether a native notification should be sent at `now`,
+/// considering focus mode and quiet hours settings.
+pub fn should_send_notification(db: &Database, urgency: &str, now: i64) -> bool {
+    should_send_routed(db, urgency, &Route::default(), now)
+}
+
+/// `should_send_notification`, with the routing rules' say first.
+fn should_send_routed(db: &Database, urgency: &str, route: &Route, now: i64) -> bool {
+    if route.notify == Some(false) {
+        return false;
+    }
+
     // Check quiet hours first
-    if let (Ok(Some(start)), Ok(Some(end))) = (
-        db.get_app_setting("quiet_hours_start"),
-        db.get_app_setting("quiet_hours_end"),
-    ) {
-        if is_in_quiet_hours(&start, &end) {
-            return false;
-        }
+    let now = DateTime::from_timestamp(now, 0).unwrap_or_else(Utc::now);
+    if !route.bypass_quiet_hours && QuietSchedule::load(db).is_quiet_at(now) {
+        return false;
+    }
+    if route.notify == Some(true) {
+        return true;
     }
 
     // Check focus mode
@@ -59,6 +346,81 @@ pub fn should_send_notification(db: &Database, urgency: &str) -> bool {
     true
 }
 
+/// How an item's urgency now compares with the one it was last notified at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrgencyChange {
+    /// Never notified before.
+    New,
+    Unchanged,
+    /// Worth a fresh notification even when one for the same reason is active.
+    Escalated,
+    /// Downgrade the active notifications quietly.
+    Deescalated,
+}
+
+pub fn urgency_change(last_notified: Option<&str>, urgency: &str) -> UrgencyChange {
+    let Some(last) = last_notified else {
+        return UrgencyChange::New;
+    };
+    match urgency_level(urgency).cmp(&urgency_level(last)) {
+        std::cmp::Ordering::Greater => UrgencyChange::Escalated,
+        std::cmp::Ordering::Less => UrgencyChange::Deescalated,
+        std::cmp::Ordering::Equal => UrgencyChange::Unchanged,
+    }
+}
+
+/// Act on `item_id` now scoring `urgency`, compared with the tier it was
+/// last notified at: notify when it is new or more urgent, quietly downgrade
+/// its active notifications when it dropped, and otherwise leave it alone,
+/// even if the reason differs or the user dismissed the notification.
+/// An `OverrideUrgency` rule applies before the comparison, so the tracked
+/// tier is always the one delivered. Returns the change when anything was
+/// written.
+pub fn track_urgency(
+    db: &Database,
+    item_id: &str,
+    reason: &str,
+    urgency: &str,
+    now: i64,
+) -> rusqlite::Result<Option<UrgencyChange>> {
+    let mut notif = Notification {
+        id: Uuid::new_v4().to_string(),
+        item_id: item_id.to_string(),
+        reason: reason.to_string(),
+        urgency: urgency.to_string(),
+        is_dismissed: false,
+        created_at: now,
+        snoozed_until: None,
+        escalated_at: None,
+        delivered_at: None,
+    };
+    if let Some(routed) = route_stored(db, &notif).urgency {
+        notif.urgency = routed;
+    }
+    let last = db.get_notified_urgency(item_id)?;
+    let change = urgency_change(last.as_deref(), &notif.urgency);
+    match change {
+        UrgencyChange::Escalated => {
+            // The new notification supersedes the item's older ones.
+            db.dismiss_item_notifications(item_id)?;
+        }
+        UrgencyChange::Deescalated => {
+            db.downgrade_notifications(item_id, &notif.urgency)?;
+            db.set_notified_urgency(item_id, &notif.urgency, now)?;
+            return Ok(Some(change));
+        }
+        UrgencyChange::New => {
+            if notif.urgency == "low" || db.has_active_notification(item_id, reason)? {
+                return Ok(None);
+            }
+        }
+        UrgencyChange::Unchanged => return Ok(None),
+    }
+    db.set_notified_urgency(item_id, &notif.urgency, now)?;
+    db.insert_notification(&notif)?;
+    Ok(Some(change))
+}
+
 fn urgency_level(urgency: &str) -> u8 {
     match urgency {
         "critical" => 4,
@@ -73,41 +435,836 @@ fn urgency_meets_threshold(urgency: &str, threshold: &str) -> bool {
     urgency_level(urgency) >= urgency_level(threshold)
 }
 
-fn is_in_quiet_hours(start: &str, end: &str) -> bool {
-    let now = chrono::Local::now().format("%H:%M").to_string();
-    let now = now.as_str();
-    if start <= end {
-        // Same-day range: e.g. 09:00 - 17:00
-        now >= start && now < end
+/// Run the escalation ladder step of the notification's urgency: toast,
+/// sound and attention request unless quiet hours or focus mode hold them
+/// back, then update the badge, which is never held back.
+pub fn dispatch(app: &AppHandle, db: &Database, notif: &Notification, title: &str, now: i64) {
+    let ladder = EscalationLadder::load(db);
+    announce(
+        app,
+        db,
+        &ladder,
+        &route_stored(db, notif),
+        &notif.urgency,
+        &notification_title(notif, title),
+        &humanize_reason(&notif.reason),
+        now,
+    );
+    update_badge(app, db, &ladder);
+}
+
+/// Deliver the notifications that are still waiting, following the
+/// `DeliveryPolicy`: bursts from one source become a summary, low / medium
+/// ones wait for the digest if it is on, and nothing but critical goes out
+/// before the batch window has passed.
+pub fn flush_pending(app: &AppHandle, db: &Database, now: i64) {
+    let mut pending = match db.get_undelivered_notifications() {
+        Ok(pending) => pending,
+        Err(e) => {
+            eprintln!(
+                "[notifications] Failed to load pending notifications: {}",
+                e
+            );
+            return;
+        }
+    };
+    if pending.is_empty() {
+        return;
+    }
+    let routes = route_pending(db, &mut pending, now);
+    let policy = DeliveryPolicy::load(db);
+    let deliveries = plan_delivery(
+        &pending,
+        &routes,
+        &policy,
+        now,
+        digest_due(db, &policy, now),
+    );
+    let ladder = EscalationLadder::load(db);
+
+    let mut delivered: Vec<&str> = Vec::new();
+    for delivery in &deliveries {
+        let indices = match delivery {
+            Delivery::Silent(i) => std::slice::from_ref(i),
+            Delivery::Single(i) => {
+                let p = &pending[*i];
+                announce(
+                    app,
+                    db,
+                    &ladder,
+                    &routes[*i],
+                    &p.notification.urgency,
+                    &notification_title(&p.notification, &p.title),
+                    &humanize_reason(&p.notification.reason),
+                    now,
+                );
+                std::slice::from_ref(i)
+            }
+            Delivery::Summary(indices) => {
+                let group: Vec<&PendingDelivery> = indices.iter().map(|i| &pending[*i]).collect();
+                announce(
+                    app,
+                    db,
+                    &ladder,
+                    &Route::default(),
+                    most_urgent(&group),
+                    &summary_title(&group),
+                    &summary_body(&group),
+                    now,
+                );
+                indices.as_slice()
+            }
+            Delivery::Digest(indices) => {
+                let group: Vec<&PendingDelivery> = indices.iter().map(|i| &pending[*i]).collect();
+                // The digest is the one notification these get, so it always
+                // shows (quietly) unless quiet hours or focus mode say otherwise.
+                if should_send_notification(db, most_urgent(&group), now) {
+                    show_toast(app, &digest_title(&group), &digest_body(&group), None);
+                }
+                if let Err(e) = db.set_app_setting("notification_digest_sent_at", &now.to_string())
+                {
+                    eprintln!("[notifications] Failed to record digest: {}", e);
+                }
+                indices.as_slice()
+            }
+        };
+        delivered.extend(indices.iter().map(|i| pending[*i].notification.id.as_str()));
+    }
+    if let Err(e) = db.mark_notifications_delivered(&delivered, now) {
+        eprintln!("[notifications] Failed to record delivery: {}", e);
+    }
+}
+
+/// Run the routing rules over `pending`, storing any urgency they override.
+fn route_pending(db: &Database, pending: &mut [PendingDelivery], now: i64) -> Vec<Route> {
+    let rules = db.get_rules().unwrap_or_else(|e| {
+        eprintln!("[notifications] Failed to load notification rules: {}", e);
+        Vec::new()
+    });
+    pending
+        .iter_mut()
+        .map(|p| {
+            let item = match rules.is_empty() {
+                true => None,
+                false => db.get_item(&p.notification.item_id).ok().flatten(),
+            };
+            let Some(item) = item else {
+                return Route::default();
+            };
+            let route = route(&rules, &p.notification, &item);
+            if let Some(urgency) = route
+                .urgency
+                .as_ref()
+                .filter(|u| **u != p.notification.urgency)
+            {
+                // Tracked too, so the next poll compares against what was
+                // actually delivered.
+                let item_id = &p.notification.item_id;
+                if let Err(e) = db
+                    .set_notification_urgency(&p.notification.id, urgency)
+                    .and_then(|_| db.set_notified_urgency(item_id, urgency, now))
+                {
+                    eprintln!("[notifications] Failed to override urgency: {}", e);
+                }
+                p.notification.urgency = urgency.clone();
+            }
+            route
+        })
+        .collect()
+}
+
+/// Whether a digest is due. The first call only starts the clock.
+fn digest_due(db: &Database, policy: &DeliveryPolicy, now: i64) -> bool {
+    let Some(interval) = policy.digest.interval_secs() else {
+        return false;
+    };
+    let last = db
+        .get_app_setting("notification_digest_sent_at")
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse::<i64>().ok());
+    match last {
+        Some(last) => now - last >= interval,
+        None => {
+            let _ = db.set_app_setting("notification_digest_sent_at", &now.to_string());
+            false
+        }
+    }
+}
+
+/// Decide how to deliver `pending` (oldest first) at `now`. Notifications a
+/// rule forces through go out on their own straight away.
+fn plan_delivery(
+    pending: &[PendingDelivery],
+    routes: &[Route],
+    policy: &DeliveryPolicy,
+    now: i64,
+    digest_due: bool,
+) -> Vec<Delivery> {
+    let mut deliveries = Vec::new();
+    let mut rest = Vec::new();
+    for (i, route) in routes.iter().enumerate() {
+        if route.notify == Some(false) {
+            deliveries.push(Delivery::Silent(i));
+        } else if route.is_forced() {
+            deliveries.push(Delivery::Single(i));
+        } else {
+            rest.push(i);
+        }
+    }
+    let (digest, immediate): (Vec<usize>, Vec<usize>) = rest.into_iter().partition(|i| {
+        policy.digest != DigestMode::Off
+            && urgency_level(&pending[*i].notification.urgency) <= urgency_level("medium")
+    });
+
+    let window_passed = immediate
+        .first()
+        .is_some_and(|i| now - pending[*i].notification.created_at >= policy.batch_window_secs);
+    let has_critical = immediate
+        .iter()
+        .any(|i| pending[*i].notification.urgency == "critical");
+    if window_passed || has_critical {
+        // Group by source, in order of each source's oldest notification.
+        let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+        for i in immediate {
+            let source = pending[i].source.as_str();
+            match groups.iter_mut().find(|(s, _)| *s == source) {
+                Some((_, group)) => group.push(i),
+                None => groups.push((source, vec![i])),
+            }
+        }
+        for (_, group) in groups {
+            if group.len() >= policy.burst_threshold {
+                deliveries.push(Delivery::Summary(group));
+            } else {
+                deliveries.extend(group.into_iter().map(Delivery::Single));
+            }
+        }
+    }
+    if digest_due && !digest.is_empty() {
+        deliveries.push(Delivery::Digest(digest));
+    }
+    deliveries
+}
+
+fn most_urgent<'a>(group: &[&'a PendingDelivery]) -> &'a str {
+    group
+        .iter()
+        .map(|p| p.notification.urgency.as_str())
+        .max_by_key(|u| urgency_level(u))
+        .unwrap_or("low")
+}
+
+/// e.g. "5 new items (1 critical, 2 high) from GitHub".
+fn summary_title(group: &[&PendingDelivery]) -> String {
+    let count = |urgency: &str| {
+        group
+            .iter()
+            .filter(|p| p.notification.urgency == urgency)
+            .count()
+    };
+    let severe: Vec<String> = ["critical", "high"]
+        .iter()
+        .map(|u| (count(u), u))
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, u)| format!("{} {}", n, u))
+        .collect();
+    let mut title = items_label(group.len());
+    if !severe.is_empty() {
+        title.push_str(&format!(" ({})", severe.join(", ")));
+    }
+    let source = group.first().map_or("", |p| p.source.as_str());
+    format!("{} from {}", title, source_label(source))
+}
+
+fn summary_body(group: &[&PendingDelivery]) -> String {
+    let mut lines: Vec<String> = group
+        .iter()
+        .take(SUMMARY_TITLES)
+        .map(|p| p.title.clone())
+        .collect();
+    if group.len() > SUMMARY_TITLES {
+        lines.push(format!("…and {} more", group.len() - SUMMARY_TITLES));
+    }
+    lines.join("\n")
+}
+
+fn digest_title(group: &[&PendingDelivery]) -> String {
+    format!("Digest: {}", items_label(group.len()))
+}
+
+/// e.g. "3 from GitHub, 2 from Jira".
+fn digest_body(group: &[&PendingDelivery]) -> String {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for p in group {
+        match counts.iter_mut().find(|(s, _)| *s == p.source) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((&p.source, 1)),
+        }
+    }
+    counts
+        .iter()
+        .map(|(source, n)| format!("{} from {}", n, source_label(source)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn items_label(count: usize) -> String {
+    if count == 1 {
+        "1 new item".to_string()
     } else {
-        // Overnight range: e.g. 22:00 - 08:00
-        now >= start || now < end
+        format!("{} new items", count)
+    }
+}
+
+fn source_label(source: &str) -> &str {
+    match source {
+        "github" => "GitHub",
+        "gmail" => "Gmail",
+        "jira" => "Jira",
+        other => other,
+    }
+}
+
+/// Fire the ladder again for critical notifications left undismissed for
+/// `repeat_critical_after_secs` since they were last announced.
+pub fn reescalate_overdue(app: &AppHandle, db: &Database, now: i64) {
+    let ladder = EscalationLadder::load(db);
+    if ladder.repeat_critical_after_secs <= 0 {
+        return;
+    }
+    let overdue =
+        match db.get_overdue_notifications("critical", now - ladder.repeat_critical_after_secs) {
+            Ok(overdue) => overdue,
+            Err(e) => {
+                eprintln!(
+                    "[notifications] Failed to load overdue notifications: {}",
+                    e
+                );
+                return;
+            }
+        };
+    for (notif, title) in &overdue {
+        dispatch(app, db, notif, title, now);
+        if let Err(e) = db.mark_notification_escalated(&notif.id, now) {
+            eprintln!("[notifications] Failed to record escalation: {}", e);
+        }
     }
 }
 
-/// Send a native OS notification for medium+ urgency items.
-/// Low-urgency notifications are silently skipped (badge only in the UI).
-pub fn send_native_notification(app: &AppHandle, notif: &Notification, title: &str) {
+/// Set the window / tray badge to the number of active notifications whose
+/// tier counts towards it. Call after anything that changes them.
+pub fn refresh_badge(app: &AppHandle, db: &Database) {
+    update_badge(app, db, &EscalationLadder::load(db));
+}
+
+fn update_badge(app: &AppHandle, db: &Database, ladder: &EscalationLadder) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let count = match db.get_active_notifications() {
+        Ok(active) => active
+            .iter()
+            .filter(|n| ladder.step(&n.urgency).badge)
+            .count() as i64,
+        Err(e) => {
+            eprintln!("[notifications] Failed to count notifications: {}", e);
+            return;
+        }
+    };
+    // Unsupported on Windows; nothing useful to report there.
+    let _ = window.set_badge_count((count > 0).then_some(count));
+}
+
+fn request_attention(app: &AppHandle, urgency: &str) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let kind = if urgency == "critical" {
+        UserAttentionType::Critical
+    } else {
+        UserAttentionType::Informational
+    };
+    if let Err(e) = window.request_user_attention(Some(kind)) {
+        eprintln!("[notifications] Failed to request attention: {}", e);
+    }
+}
+
+/// Run the ladder step of `urgency` for one native notification.
+#[allow(clippy::too_many_arguments)]
+fn announce(
+    app: &AppHandle,
+    db: &Database,
+    ladder: &EscalationLadder,
+    route: &Route,
+    urgency: &str,
+    title: &str,
+    body: &str,
+    now: i64,
+) {
+    if !should_send_routed(db, urgency, route, now) {
+        return;
+    }
+    let step = ladder.step(urgency);
+    if step.toast || route.notify == Some(true) {
+        show_toast(app, title, body, step.sound.as_deref());
+    }
+    if step.attention {
+        request_attention(app, urgency);
+    }
+}
+
+/// The item title, prefixed with the urgency for high and critical.
+fn notification_title(notif: &Notification, title: &str) -> String {
     let urgency_label = match notif.urgency.as_str() {
         "critical" => "[CRITICAL]",
         "high"     => "[HIGH]",
-        "medium"   => "",
-        _          => return, // low — no native notification
+        _          => "",
     };
 
-    let notif_title = if urgency_label.is_empty() {
+    if urgency_label.is_empty() {
         title.to_string()
     } else {
         format!("{} {}", urgency_label, title)
-    };
+    }
+}
+
+/// Show a native OS notification, with `sound` if given.
+fn show_toast(app: &AppHandle, title: &str, body: &str, sound: Option<&str>) {
+    let mut builder = app.notification().builder().title(title).body(body);
+    if let Some(sound) = sound {
+        builder = builder.sound(sound);
+    }
+    if let Err(e) = builder.show() {
+        eprintln!("[notifications] Failed to show notification: {}", e);
+    }
+}
+
+/// Remind the user of an item they snoozed, whatever its urgency.
+pub fn send_reminder_notification(app: &AppHandle, title: &str) {
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title("Snoozed item")
+        .body(title)
+        .show()
+    {
+        eprintln!("[notifications] Failed to show notification: {}", e);
+    }
+}
 
+/// Tell the user a plugin was switched off by the scheduler's circuit breaker.
+/// Always shown — quiet hours and focus mode don't hide a broken integration.
+pub fn send_plugin_disabled_notification(app: &AppHandle, plugin_id: &str, reason: &str) {
     if let Err(e) = app
         .notification()
         .builder()
-        .title(&notif_title)
-        .body(&humanize_reason(&notif.reason))
+        .title(format!("{} sync paused", plugin_id))
+        .body(reason)
         .show()
     {
         eprintln!("[notifications] Failed to show notification: {}", e);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(list: &[PendingDelivery]) -> Vec<Route> {
+        vec![Route::default(); list.len()]
+    }
+
+    fn pending(id: &str, source: &str, urgency: &str, created_at: i64) -> PendingDelivery {
+        PendingDelivery {
+            notification: Notification {
+                id: id.to_string(),
+                item_id: format!("{}-item", id),
+                reason: "assigned".to_string(),
+                urgency: urgency.to_string(),
+                is_dismissed: false,
+                created_at,
+                snoozed_until: None,
+                escalated_at: None,
+                delivered_at: None,
+            },
+            title: format!("Item {}", id),
+            source: source.to_string(),
+        }
+    }
+
+    fn ci_item(branch: &str) -> NexusItem {
+        NexusItem {
+            id: "github-ci-1".to_string(),
+            source: "github".to_string(),
+            source_id: "ci-1".to_string(),
+            item_type: "ci_run".to_string(),
+            title: "CI failed".to_string(),
+            summary: None,
+            url: "https://github.com/acme/app/actions/runs/1".to_string(),
+            author: Some("Dependabot".to_string()),
+            timestamp: 100,
+            priority: 2,
+            metadata: Some(format!(r#"{{"head": {{"branch": "{}"}}}}"#, branch)),
+            tags: Some(r#"["ci"]"#.to_string()),
+            is_read: false,
+            created_at: 100,
+            updated_at: 100,
+            signals: None,
+            archived_at: None,
+            snoozed_until: None,
+            flags: Default::default(),
+        }
+    }
+
+    fn rule(name: &str, conditions: RuleConditions, action: RuleAction) -> NotificationRule {
+        NotificationRule {
+            id: name.to_string(),
+            name: name.to_string(),
+            conditions,
+            action,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rules_route_by_item_and_reason() {
+        let main_ci = rule(
+            "CI failed on main",
+            RuleConditions {
+                item_types: vec!["ci_run".to_string()],
+                signals: vec!["ci_failed".to_string()],
+                metadata: [("head.branch".to_string(), serde_json::json!("main"))].into(),
+                ..Default::default()
+            },
+            RuleAction::BypassQuietHours,
+        );
+        let bots = rule(
+            "Mute bots",
+            RuleConditions {
+                authors: vec!["dependabot".to_string()],
+                ..Default::default()
+            },
+            RuleAction::NeverNotify,
+        );
+        let escalate = rule(
+            "CI is urgent",
+            RuleConditions {
+                tags: vec!["ci".to_string(), "deploy".to_string()],
+                ..Default::default()
+            },
+            RuleAction::OverrideUrgency {
+                urgency: "critical".to_string(),
+            },
+        );
+        let mut notif = pending("n", "github", "medium", 100).notification;
+        notif.reason = "ci_failed, mentioned".to_string();
+
+        let rules = [main_ci.clone(), escalate.clone()];
+        let on_main = route(&rules, &notif, &ci_item("main"));
+        assert!(on_main.bypass_quiet_hours && on_main.is_forced());
+        assert_eq!(on_main.urgency.as_deref(), Some("critical"));
+        assert!(!route(&rules, &notif, &ci_item("feature")).bypass_quiet_hours);
+
+        // A scored item's reason names only the deciding signal.
+        let mut scored = ci_item("main");
+        scored.signals = Some(r#"["ci_failed", "mentioned"]"#.to_string());
+        notif.reason = "mentioned".to_string();
+        assert!(route(&rules, &notif, &scored).bypass_quiet_hours);
+        notif.reason = "ci_failed, mentioned".to_string();
+
+        // The first matching notify rule wins; disabled rules are skipped.
+        let always = rule(
+            "Always",
+            RuleConditions::default(),
+            RuleAction::AlwaysNotify,
+        );
+        let rules = [bots.clone(), always.clone()];
+        assert_eq!(route(&rules, &notif, &ci_item("main")).notify, Some(false));
+        let rules = [
+            NotificationRule {
+                is_enabled: false,
+                ..bots
+            },
+            always,
+        ];
+        assert_eq!(route(&rules, &notif, &ci_item("main")).notify, Some(true));
+
+        notif.reason = "mentioned".to_string();
+        assert_eq!(
+            route(&[main_ci], &notif, &ci_item("main")),
+            Route::default()
+        );
+    }
+
+    #[test]
+    fn quiet_hours_hold_back_all_but_bypassing_routes() {
+        let db = Database::new_in_memory().unwrap();
+        db.set_app_setting(
+            "quiet_hours_schedule",
+            r#"{"timezone": "UTC", "windows": [{"start": "22:00", "end": "07:00"}]}"#,
+        )
+        .unwrap();
+        let bypass = Route {
+            bypass_quiet_hours: true,
+            ..Default::default()
+        };
+        let always = Route {
+            notify: Some(true),
+            ..Default::default()
+        };
+        let night = 1_772_319_600; // 2026-02-28 23:00 UTC
+        let noon = night + 13 * 3600;
+
+        assert!(!should_send_routed(
+            &db,
+            "critical",
+            &Route::default(),
+            night
+        ));
+        assert!(!should_send_routed(&db, "critical", &always, night));
+        assert!(should_send_routed(&db, "low", &bypass, night));
+        assert!(should_send_routed(&db, "low", &Route::default(), noon));
+    }
+
+    #[test]
+    fn routes_override_focus_mode_and_skip_batching() {
+        let db = Database::new_in_memory().unwrap();
+        db.set_app_setting("focus_mode_enabled", "1").unwrap();
+        let always = Route {
+            notify: Some(true),
+            ..Default::default()
+        };
+        let never = Route {
+            notify: Some(false),
+            ..Default::default()
+        };
+        assert!(!should_send_routed(&db, "low", &Route::default(), 100));
+        assert!(should_send_routed(&db, "low", &always, 100));
+        assert!(!should_send_routed(&db, "critical", &never, 100));
+
+        let policy = DeliveryPolicy {
+            batch_window_secs: 60,
+            digest: DigestMode::Hourly,
+            ..Default::default()
+        };
+        let list = vec![
+            pending("a", "jira", "low", 100),
+            pending("b", "jira", "low", 100),
+            pending("c", "jira", "high", 100),
+        ];
+        assert_eq!(
+            plan_delivery(
+                &list,
+                &[always, never, Route::default()],
+                &policy,
+                100,
+                false
+            ),
+            vec![Delivery::Single(0), Delivery::Silent(1)]
+        );
+    }
+
+    #[test]
+    fn urgency_change_compares_tiers() {
+        assert_eq!(urgency_change(None, "medium"), UrgencyChange::New);
+        assert_eq!(
+            urgency_change(Some("medium"), "critical"),
+            UrgencyChange::Escalated
+        );
+        assert_eq!(
+            urgency_change(Some("high"), "high"),
+            UrgencyChange::Unchanged
+        );
+        assert_eq!(
+            urgency_change(Some("high"), "low"),
+            UrgencyChange::Deescalated
+        );
+    }
+
+    #[test]
+    fn same_tier_is_not_notified_again() {
+        let db = Database::new_in_memory().unwrap();
+        let item = ci_item("main");
+        db.upsert_item(&item).unwrap();
+        let track = |reason: &str, urgency: &str| {
+            track_urgency(&db, &item.id, reason, urgency, 100).unwrap()
+        };
+
+        assert_eq!(track("ci_failed", "low"), None);
+        assert_eq!(track("ci_failed", "medium"), Some(UrgencyChange::New));
+        // A different set of signals at the same tier is no news...
+        assert_eq!(track("mentioned", "medium"), None);
+        // ...and neither is the same tier once the user dismissed it.
+        db.dismiss_all_notifications().unwrap();
+        assert_eq!(track("ci_failed", "medium"), None);
+        assert!(db.get_active_notifications().unwrap().is_empty());
+
+        assert_eq!(track("ci_failed", "low"), Some(UrgencyChange::Deescalated));
+        assert_eq!(track("mentioned", "high"), Some(UrgencyChange::Escalated));
+        let active = db.get_active_notifications().unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(
+            (active[0].reason.as_str(), active[0].urgency.as_str()),
+            ("mentioned", "high")
+        );
+    }
+
+    #[test]
+    fn overridden_urgency_is_the_tracked_tier() {
+        let db = Database::new_in_memory().unwrap();
+        let item = ci_item("main");
+        db.upsert_item(&item).unwrap();
+        db.upsert_rule(&rule(
+            "CI is urgent",
+            RuleConditions {
+                item_types: vec!["ci_run".to_string()],
+                ..Default::default()
+            },
+            RuleAction::OverrideUrgency {
+                urgency: "critical".to_string(),
+            },
+        ))
+        .unwrap();
+        let track =
+            |urgency: &str| track_urgency(&db, &item.id, "ci_failed", urgency, 100).unwrap();
+        let active_urgency = || db.get_active_notifications().unwrap()[0].urgency.clone();
+
+        assert_eq!(track("medium"), Some(UrgencyChange::New));
+        assert_eq!(active_urgency(), "critical");
+        // Still critical once routed, so rising to high is no news.
+        assert_eq!(track("high"), None);
+
+        // Without the rule, a drop moves the overridden notification down.
+        db.delete_rule("CI is urgent").unwrap();
+        assert_eq!(track("medium"), Some(UrgencyChange::Deescalated));
+        assert_eq!(active_urgency(), "medium");
+        assert_eq!(
+            db.get_notified_urgency(&item.id).unwrap().as_deref(),
+            Some("medium")
+        );
+    }
+
+    #[test]
+    fn default_ladder_escalates_by_tier() {
+        let ladder = EscalationLadder::default();
+        assert!(ladder.step("low").badge && !ladder.step("low").toast);
+        assert!(ladder.step("medium").toast && ladder.step("medium").sound.is_none());
+        assert_eq!(ladder.step("high").sound.as_deref(), Some("default"));
+        assert!(!ladder.step("high").attention);
+        assert!(ladder.step("critical").attention);
+        assert_eq!(ladder.step("unknown"), ladder.step("low"));
+    }
+
+    #[test]
+    fn ladder_loads_from_settings() {
+        let db = Database::new_in_memory().unwrap();
+        assert_eq!(EscalationLadder::load(&db), EscalationLadder::default());
+
+        db.set_app_setting(
+            "escalation_ladder",
+            r#"{"high": {"toast": true, "sound": "/sounds/ping.wav"}, "repeat_critical_after_secs": 0}"#,
+        )
+        .unwrap();
+        let ladder = EscalationLadder::load(&db);
+        assert_eq!(ladder.high.sound.as_deref(), Some("/sounds/ping.wav"));
+        assert!(!ladder.high.badge);
+        assert_eq!(ladder.repeat_critical_after_secs, 0);
+        // Tiers not mentioned keep their defaults.
+        assert_eq!(ladder.critical, EscalationLadder::default().critical);
+
+        db.set_app_setting("escalation_ladder", "not json").unwrap();
+        assert_eq!(EscalationLadder::load(&db), EscalationLadder::default());
+    }
+
+    #[test]
+    fn bursts_from_one_source_become_a_summary() {
+        let policy = DeliveryPolicy::default(); // threshold 3, no window
+        let list = vec![
+            pending("g1", "github", "medium", 100),
+            pending("j1", "jira", "high", 100),
+            pending("g2", "github", "high", 100),
+            pending("g3", "github", "critical", 100),
+        ];
+        assert_eq!(
+            plan_delivery(&list, &plain(&list), &policy, 100, false),
+            vec![Delivery::Summary(vec![0, 2, 3]), Delivery::Single(1)]
+        );
+
+        let group: Vec<&PendingDelivery> = [0, 2, 3].iter().map(|i| &list[*i]).collect();
+        assert_eq!(
+            summary_title(&group),
+            "3 new items (1 critical, 1 high) from GitHub"
+        );
+        assert_eq!(most_urgent(&group), "critical");
+    }
+
+    #[test]
+    fn batch_window_holds_all_but_critical() {
+        let policy = DeliveryPolicy {
+            batch_window_secs: 60,
+            ..Default::default()
+        };
+        let list = vec![pending("a", "jira", "high", 100)];
+        assert!(plan_delivery(&list, &plain(&list), &policy, 159, false).is_empty());
+        assert_eq!(
+            plan_delivery(&list, &plain(&list), &policy, 160, false),
+            vec![Delivery::Single(0)]
+        );
+
+        let list = vec![
+            pending("a", "jira", "high", 100),
+            pending("b", "gmail", "critical", 150),
+        ];
+        assert_eq!(
+            plan_delivery(&list, &plain(&list), &policy, 150, false),
+            vec![Delivery::Single(0), Delivery::Single(1)]
+        );
+    }
+
+    #[test]
+    fn digest_collects_low_and_medium_until_due() {
+        let policy = DeliveryPolicy {
+            digest: DigestMode::Hourly,
+            ..Default::default()
+        };
+        let list = vec![
+            pending("a", "jira", "low", 100),
+            pending("b", "jira", "high", 100),
+            pending("c", "gmail", "medium", 100),
+        ];
+        assert_eq!(
+            plan_delivery(&list, &plain(&list), &policy, 100, false),
+            vec![Delivery::Single(1)]
+        );
+        assert_eq!(
+            plan_delivery(&list, &plain(&list), &policy, 100, true),
+            vec![Delivery::Single(1), Delivery::Digest(vec![0, 2])]
+        );
+
+        let group: Vec<&PendingDelivery> = [0, 2].iter().map(|i| &list[*i]).collect();
+        assert_eq!(digest_title(&group), "Digest: 2 new items");
+        assert_eq!(digest_body(&group), "1 from Jira, 1 from Gmail");
+    }
+
+    #[test]
+    fn delivery_policy_loads_from_settings() {
+        let db = Database::new_in_memory().unwrap();
+        assert_eq!(DeliveryPolicy::load(&db), DeliveryPolicy::default());
+
+        db.set_app_setting("notification_batch_window_secs", "45")
+            .unwrap();
+        db.set_app_setting("notification_burst_threshold", "0")
+            .unwrap();
+        db.set_app_setting("notification_digest", "daily").unwrap();
+        let policy = DeliveryPolicy::load(&db);
+        assert_eq!(policy.batch_window_secs, 45);
+        assert_eq!(policy.burst_threshold, 3); // 0 is ignored
+        assert_eq!(policy.digest, DigestMode::Daily);
+
+        // The first check only starts the digest clock.
+        assert!(!digest_due(&db, &policy, 1000));
+        assert!(!digest_due(&db, &policy, 1000 + 86_399));
+        assert!(digest_due(&db, &policy, 1000 + 86_400));
+    }
+}
@@ -0,0 +1,649 @@
+//! Persistent Deno plugin hosts.
+//!
+//! Instead of cold-starting Deno for every call, each plugin gets one
+//! long-lived `deno run` process that imports the plugin module once and
+//! serves requests as line-delimited JSON-RPC on stdin/stdout:
+//!
+//! ```text
+//! → {"jsonrpc":"2.0","id":1,"method":"fetch","params":{"config":"{…}","cursor":null}}
+//! ← {"jsonrpc":"2.0","id":1,"result":"{…}"}
+//! ← {"jsonrpc":"2.0","id":2,"error":{"code":-32000,"message":"…",
+//!                                    "data":{"kind":"rate_limited","retryAfter":60}}}
+//! ```
+//!
+//! `data` is present when the plugin threw a `PluginFailure`; its `kind`
+//! becomes the matching [`PluginError`] variant.
+//!
+//! Hosts are per plugin rather than shared so each keeps exactly the Deno
+//! permissions from its own manifest. A host that crashes is replaced on the
+//! next call. A call that times out or is cancelled is abandoned on its own:
+//! its host takes no new calls and is killed once the other calls in flight
+//! on it have finished, so a slow `validateConnection` can't fail a `fetch`.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use crate::plugin_registry::RegisteredPlugin;
+use crate::plugin_runtime::{CancelToken, PluginError};
+
+/// How often a pending call is checked for timeout or cancellation.
+const WAIT_TICK: Duration = Duration::from_millis(50);
+
+/// Lines of host stderr kept for error messages.
+const STDERR_TAIL_LINES: usize = 20;
+
+/// Entry script for a plugin host; `__PLUGIN_URL__` is replaced with the
+/// plugin module's file URL. The import is static so Deno needs no read
+/// permission to load it.
+const HOST_SCRIPT: &str = r#"import * as plugin from "__PLUGIN_URL__";
+
+// Plugin logging on stdout would corrupt the protocol; send it to stderr.
+console.log = console.error;
+console.info = console.error;
+
+const encoder = new TextEncoder();
+let writes = Promise.resolve();
+
+function reply(message) {
+  const bytes = encoder.encode(JSON.stringify(message) + "\n");
+  writes = writes.then(async () => {
+    let written = 0;
+    while (written < bytes.length) {
+      written += await Deno.stdout.write(bytes.subarray(written));
+    }
+  });
+  return writes;
+}
+
+async function handle(line) {
+  let id = null;
+  try {
+    const request = JSON.parse(line);
+    id = request.id;
+    const fn = plugin[request.method];
+    if (typeof fn !== "function") {
+      throw new Error(`Plugin does not export '${request.method}'`);
+    }
+    const result = await fn(request.params?.config ?? "{}", request.params?.cursor ?? null);
+    await reply({ jsonrpc: "2.0", id, result });
+  } catch (e) {
+    const message = e instanceof Error ? e.message : String(e);
+    const error = { code: -32000, message };
+    if (typeof e?.kind === "string") {
+      error.data = { kind: e.kind, retryAfter: e.retryAfter ?? null };
+    }
+    await reply({ jsonrpc: "2.0", id, error });
+  }
+}
+
+const decoder = new TextDecoder();
+let buffered = "";
+for await (const chunk of Deno.stdin.readable) {
+  buffered += decoder.decode(chunk, { stream: true });
+  let newline;
+  while ((newline = buffered.indexOf("\n")) >= 0) {
+    const line = buffered.slice(0, newline).trim();
+    buffered = buffered.slice(newline + 1);
+    if (line) handle(line);
+  }
+}
+"#;
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    id: u64,
+    #[serde(default)]
+    result: Option<serde_json::Value>,
+    #[serde(default)]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    message: String,
+    #[serde(default)]
+    data: Option<RpcErrorData>,
+}
+
+/// Failure classification sent by plugins that throw a `PluginFailure`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RpcErrorData {
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    retry_after: Option<u64>,
+}
+
+impl RpcError {
+    fn into_plugin_error(self, method: &str) -> PluginError {
+        let (kind, retry_after) = match self.data {
+            Some(data) => (data.kind, data.retry_after),
+            None => (None, None),
+        };
+        PluginError::from_plugin(
+            kind.as_deref(),
+            format!("Plugin '{}' failed: {}", method, self.message),
+            retry_after,
+        )
+    }
+}
+
+type Reply = Result<String, RpcError>;
+type Pending = Arc<Mutex<HashMap<u64, mpsc::Sender<Reply>>>>;
+
+/// Temporary host entry script, removed when dropped.
+struct HostScript(PathBuf);
+
+impl HostScript {
+    fn write(plugin_url: &str) -> Result<Self, PluginError> {
+        let path =
+            std::env::temp_dir().join(format!("nexus-plugin-host-{}.ts", uuid::Uuid::new_v4()));
+        fs::write(&path, HOST_SCRIPT.replace("__PLUGIN_URL__", plugin_url))
+            .map_err(|e| PluginError::Failed(format!("Failed to write plugin host: {}", e)))?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for HostScript {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// One running host process.
+struct Host {
+    /// Id of the plugin the host runs, for errors and log lines.
+    plugin_id: String,
+    child: Mutex<Child>,
+    stdin: Mutex<ChildStdin>,
+    pending: Pending,
+    next_id: AtomicU64,
+    alive: Arc<AtomicBool>,
+    /// Set when a call was abandoned; the host may still be running it.
+    retiring: AtomicBool,
+    stderr_tail: Arc<Mutex<VecDeque<String>>>,
+    stderr_reader: Mutex<Option<thread::JoinHandle<()>>>,
+    /// Plugin file and permission flags the host was launched with; it is
+    /// reused only while both still match.
+    launch_key: (PathBuf, Vec<String>),
+    _script: Option<HostScript>,
+}
+
+impl Host {
+    /// Launch a Deno host for `plugin` with the given permission flags.
+    fn launch(
+        plugin: &RegisteredPlugin,
+        permission_args: Vec<String>,
+    ) -> Result<Self, PluginError> {
+        let canonical = plugin
+            .path
+            .canonicalize()
+            .map_err(|e| PluginError::Failed(format!("Cannot resolve plugin path: {}", e)))?;
+
+        #[cfg(windows)]
+        let plugin_url = {
+            let path_str = canonical.to_string_lossy().replace('\\', "/");
+            // Strip UNC prefix \\?\ that canonicalize() adds on Windows
+            let path_str = path_str.trim_start_matches("//?/");
+            format!("file:///{}", path_str)
+        };
+        #[cfg(not(windows))]
+        let plugin_url = format!("file://{}", canonical.display());
+
+        // `deno eval` always has every permission, so the host goes through
+        // `deno run` from a temp file instead.
+        let script = HostScript::write(&plugin_url)?;
+
+        let mut cmd = Command::new("deno");
+        cmd.arg("run").args(&permission_args).arg(&script.0);
+
+        // On Windows, prevent a visible CMD window from flashing when a host starts.
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        Self::spawn(
+            cmd,
+            &plugin.manifest.id,
+            (plugin.path.clone(), permission_args),
+            Some(script),
+        )
+    }
+
+    fn spawn(
+        mut cmd: Command,
+        label: &str,
+        launch_key: (PathBuf, Vec<String>),
+        script: Option<HostScript>,
+    ) -> Result<Self, PluginError> {
+        let mut child = cmd
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                PluginError::Failed(format!(
+                    "Failed to launch deno: {}. Install Deno: https://deno.com \
+                     (Windows: winget install Deno.Deno)",
+                    e
+                ))
+            })?;
+
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let stderr = child.stderr.take().expect("stderr is piped");
+
+        let pending: Pending = Arc::default();
+        let alive = Arc::new(AtomicBool::new(true));
+        let stderr_tail: Arc<Mutex<VecDeque<String>>> = Arc::default();
+
+        // Route each response line to the caller waiting on its id. When the
+        // host exits, dropping the senders wakes every waiting caller.
+        {
+            let pending = Arc::clone(&pending);
+            let alive = Arc::clone(&alive);
+            let label = label.to_string();
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines() {
+                    let Ok(line) = line else { break };
+                    let Ok(response) = serde_json::from_str::<RpcResponse>(&line) else {
+                        eprintln!("[plugin:{}] ignoring non-protocol output: {}", label, line);
+                        continue;
+                    };
+                    let reply = match (response.result, response.error) {
+                        (_, Some(error)) => Err(error),
+                        (Some(serde_json::Value::String(result)), None) => Ok(result),
+                        (Some(result), None) => Ok(result.to_string()),
+                        (None, None) => Err(RpcError {
+                            message: "empty response".to_string(),
+                            data: None,
+                        }),
+                    };
+                    if let Some(tx) = pending.lock().ok().and_then(|mut p| p.remove(&response.id)) {
+                        let _ = tx.send(reply);
+                    }
+                }
+                alive.store(false, Ordering::SeqCst);
+                if let Ok(mut pending) = pending.lock() {
+                    pending.clear();
+                }
+            });
+        }
+
+        let stderr_reader = {
+            let tail = Arc::clone(&stderr_tail);
+            let label = label.to_string();
+            thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    eprintln!("[plugin:{}] {}", label, line);
+                    if let Ok(mut tail) = tail.lock() {
+                        if tail.len() == STDERR_TAIL_LINES {
+                            tail.pop_front();
+                        }
+                        tail.push_back(line);
+                    }
+                }
+            })
+        };
+
+        Ok(Self {
+            plugin_id: label.to_string(),
+            child: Mutex::new(child),
+            stdin: Mutex::new(stdin),
+            pending,
+            next_id: AtomicU64::new(1),
+            alive,
+            retiring: AtomicBool::new(false),
+            stderr_tail,
+            stderr_reader: Mutex::new(Some(stderr_reader)),
+            launch_key,
+            _script: script,
+        })
+    }
+
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::SeqCst)
+    }
+
+    /// Whether new calls may go to this host.
+    fn is_usable(&self) -> bool {
+        self.is_alive() && !self.retiring.load(Ordering::SeqCst)
+    }
+
+    /// Stop taking calls, and kill the host once none are left in flight.
+    fn retire(&self) {
+        self.retiring.store(true, Ordering::SeqCst);
+        self.kill_if_idle();
+    }
+
+    fn kill_if_idle(&self) {
+        let idle = self.pending.lock().map(|p| p.is_empty()).unwrap_or(true);
+        if idle && self.retiring.load(Ordering::SeqCst) {
+            self.kill();
+        }
+    }
+
+    fn kill(&self) {
+        self.alive.store(false, Ordering::SeqCst);
+        if let Ok(mut child) = self.child.lock() {
+            // The process may already have exited on its own.
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    /// Last lines the host wrote to stderr, once it has exited.
+    fn exit_output(&self) -> String {
+        if let Some(reader) = self.stderr_reader.lock().ok().and_then(|mut r| r.take()) {
+            let _ = reader.join();
+        }
+        self.stderr_tail
+            .lock()
+            .map(|tail| tail.iter().cloned().collect::<Vec<_>>().join("\n"))
+            .unwrap_or_default()
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        config_json: &str,
+        cursor: Option<&str>,
+        timeout: Duration,
+        cancel: &CancelToken,
+    ) -> Result<String, PluginError> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = mpsc::channel();
+        self.pending
+            .lock()
+            .map_err(|e| PluginError::Failed(format!("Plugin host lock error: {}", e)))?
+            .insert(id, tx);
+
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": { "config": config_json, "cursor": cursor },
+        });
+        let written = self
+            .stdin
+            .lock()
+            .map_err(|e| std::io::Error::other(e.to_string()))
+            .and_then(|mut stdin| {
+                stdin.write_all(format!("{}\n", request).as_bytes())?;
+                stdin.flush()
+            });
+        // Checked after registering so a host that died in between can't leave
+        // us waiting on a reply that will never come.
+        if written.is_err() || !self.is_alive() {
+            if let Ok(mut pending) = self.pending.lock() {
+                pending.remove(&id);
+            }
+            self.kill();
+            return Err(PluginError::Failed(format!(
+                "Plugin host exited before '{}': {}",
+                method,
+                self.exit_output().trim()
+            )));
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let reply = match rx.recv_timeout(WAIT_TICK) {
+                Ok(Ok(result)) => Ok(result.trim().to_string()),
+                Ok(Err(error)) => Err(error.into_plugin_error(method)),
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(PluginError::Failed(format!(
+                        "Plugin host exited during '{}': {}",
+                        method,
+                        self.exit_output().trim()
+                    )))
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let abandoned = if cancel.is_cancelled() {
+                        PluginError::Cancelled {
+                            plugin_id: self.plugin_id.clone(),
+                            function: method.to_string(),
+                        }
+                    } else if Instant::now() >= deadline {
+                        PluginError::Timeout {
+                            plugin_id: self.plugin_id.clone(),
+                            function: method.to_string(),
+                            secs: timeout.as_secs(),
+                        }
+                    } else {
+                        continue;
+                    };
+                    // A late reply is dropped; other calls keep their host
+                    // until they finish.
+                    if let Ok(mut pending) = self.pending.lock() {
+                        pending.remove(&id);
+                    }
+                    self.retire();
+                    return Err(abandoned);
+                }
+            };
+            // The last call on a retiring host takes it down.
+            self.kill_if_idle();
+            return reply;
+        }
+    }
+}
+
+impl Drop for Host {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// Running plugin hosts, keyed by plugin ID.
+#[derive(Default)]
+pub struct PluginHosts {
+    hosts: Mutex<HashMap<String, Arc<Host>>>,
+}
+
+impl PluginHosts {
+    /// Call a plugin export (`fetch`, `validateConnection`) with `config_json`
+    /// and the sync `cursor`, and return the JSON string it produced. Starts or
+    /// restarts the plugin's host as needed. A call that outlives `timeout`
+    /// or is cancelled retires its host, which later calls replace.
+    pub fn call(
+        &self,
+        plugin: &RegisteredPlugin,
+        method: &str,
+        config_json: &str,
+        cursor: Option<&str>,
+        timeout: Duration,
+        cancel: &CancelToken,
+    ) -> Result<String, PluginError> {
+        let host = self.host_for(plugin, config_json)?;
+        host.request(method, config_json, cursor, timeout, cancel)
+    }
+
+    fn host_for(
+        &self,
+        plugin: &RegisteredPlugin,
+        config_json: &str,
+    ) -> Result<Arc<Host>, PluginError> {
+        let permission_args = plugin.manifest.deno_permission_args(config_json);
+        let launch_key = (plugin.path.clone(), permission_args);
+
+        let mut hosts = self
+            .hosts
+            .lock()
+            .map_err(|e| PluginError::Failed(format!("Plugin host lock error: {}", e)))?;
+        if let Some(host) = hosts.get(&plugin.manifest.id) {
+            if host.is_usable() && host.launch_key == launch_key {
+                return Ok(Arc::clone(host));
+            }
+        }
+
+        // Crashed, retired, or launched with other permissions (e.g. a changed
+        // Jira base URL). The old host exits once its in-flight calls finish.
+        let host = Arc::new(Host::launch(plugin, launch_key.1)?);
+        hosts.insert(plugin.manifest.id.clone(), Arc::clone(&host));
+        Ok(host)
+    }
+
+    /// Stop every host (app shutdown).
+    pub fn shutdown(&self) {
+        if let Ok(mut hosts) = self.hosts.lock() {
+            for (_, host) in hosts.drain() {
+                host.kill();
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// A shell script standing in for the Deno host.
+    fn shell_host(script: &str) -> Host {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", script]);
+        Host::spawn(cmd, "test", (PathBuf::new(), Vec::new()), None).unwrap()
+    }
+
+    const ECHO_HOST: &str = r#"while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed 's/.*"id":\([0-9]*\).*/\1/')
+  method=$(printf '%s' "$line" | sed 's/.*"method":"\([a-zA-Z]*\)".*/\1/')
+  if [ "$method" = "fail" ]; then
+    printf '{"jsonrpc":"2.0","id":%s,"error":{"code":-32000,"message":"bad token"}}\n' "$id"
+  elif [ "$method" = "throttled" ]; then
+    printf '{"jsonrpc":"2.0","id":%s,"error":{"code":-32000,"message":"429","data":{"kind":"rate_limited","retryAfter":90}}}\n' "$id"
+  else
+    printf '{"jsonrpc":"2.0","id":%s,"result":"pong-%s"}\n' "$id" "$id"
+  fi
+done"#;
+
+    fn call(host: &Host, method: &str, timeout: Duration) -> Result<String, PluginError> {
+        host.request(method, "{}", None, timeout, &CancelToken::new())
+    }
+
+    #[test]
+    fn host_serves_multiple_requests() {
+        let host = shell_host(ECHO_HOST);
+        assert_eq!(
+            call(&host, "fetch", Duration::from_secs(5)).unwrap(),
+            "pong-1"
+        );
+        assert_eq!(
+            call(&host, "fetch", Duration::from_secs(5)).unwrap(),
+            "pong-2"
+        );
+        assert!(host.is_alive());
+    }
+
+    #[test]
+    fn host_reports_plugin_errors() {
+        let host = shell_host(ECHO_HOST);
+        let err = call(&host, "fail", Duration::from_secs(5)).unwrap_err();
+        assert_eq!(
+            err,
+            PluginError::Failed("Plugin 'fail' failed: bad token".to_string())
+        );
+        // A plugin error doesn't take the host down.
+        assert!(call(&host, "fetch", Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn host_forwards_failure_kind() {
+        let host = shell_host(ECHO_HOST);
+        let err = call(&host, "throttled", Duration::from_secs(5)).unwrap_err();
+        assert_eq!(
+            err,
+            PluginError::RateLimited {
+                message: "Plugin 'throttled' failed: 429".to_string(),
+                retry_after: Some(90),
+            }
+        );
+    }
+
+    #[test]
+    fn host_crash_fails_call_with_stderr() {
+        let host = shell_host("read line; echo 'module not found' >&2; exit 1");
+        let err = call(&host, "fetch", Duration::from_secs(5)).unwrap_err();
+        assert!(err.to_string().contains("module not found"), "{}", err);
+        assert!(!host.is_alive());
+    }
+
+    #[test]
+    fn host_killed_on_timeout() {
+        let host = shell_host("cat > /dev/null");
+        let started = Instant::now();
+        let err = call(&host, "fetch", Duration::from_millis(100)).unwrap_err();
+        assert!(matches!(err, PluginError::Timeout { .. }));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!host.is_alive());
+    }
+
+    #[test]
+    fn timeout_leaves_other_calls_running() {
+        // Answers `fetch` after a moment and never answers `hang`.
+        let host = Arc::new(shell_host(
+            r#"while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed 's/.*"id":\([0-9]*\).*/\1/')
+  case "$line" in
+    *'"method":"fetch"'*)
+      (sleep 0.5; printf '{"jsonrpc":"2.0","id":%s,"result":"done"}\n' "$id") & ;;
+  esac
+done"#,
+        ));
+        let fetching = {
+            let host = Arc::clone(&host);
+            thread::spawn(move || call(&host, "fetch", Duration::from_secs(5)))
+        };
+        thread::sleep(Duration::from_millis(50));
+
+        let err = call(&host, "hang", Duration::from_millis(100)).unwrap_err();
+        assert!(matches!(err, PluginError::Timeout { .. }));
+        assert!(host.is_alive() && !host.is_usable());
+
+        assert_eq!(fetching.join().unwrap().unwrap(), "done");
+        assert!(!host.is_alive());
+    }
+
+    #[test]
+    fn host_killed_on_cancel() {
+        let host = shell_host("cat > /dev/null");
+        let cancel = CancelToken::new();
+        let remote = cancel.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            remote.cancel();
+        });
+
+        let err = host
+            .request("fetch", "{}", None, Duration::from_secs(30), &cancel)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            PluginError::Cancelled {
+                plugin_id: "test".to_string(),
+                function: "fetch".to_string()
+            }
+        );
+        assert!(!host.is_alive());
+    }
+
+    #[test]
+    fn host_script_imports_plugin_statically() {
+        let script = HOST_SCRIPT.replace("__PLUGIN_URL__", "file:///plugins/jira.ts");
+        assert!(script.starts_with(r#"import * as plugin from "file:///plugins/jira.ts";"#));
+    }
+}
@@ -0,0 +1,475 @@
+//! Plugin registry — discovers plugins in the plugins directory.
+//!
+//! A plugin is a `{id}.ts` file with an `{id}.manifest.json` next to it. The
+//! manifest describes the plugin (name, version, credential form, default poll
+//! interval, the signals it emits with their default weights) and the Deno
+//! permissions it runs with. `.ts` files without a manifest are shared modules
+//! such as `plugin_interface.ts` and are never executed directly.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+
+const DEFAULT_POLL_INTERVAL_SECS: i64 = 600;
+const MIN_POLL_INTERVAL_SECS: i64 = 60;
+const FIELD_TYPES: [&str; 5] = ["text", "password", "email", "url", "list"];
+
+/// Permissions a plugin asks for in its manifest.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPermissions {
+    /// Hosts (optionally `host:port`) the plugin may connect to.
+    #[serde(default)]
+    pub net: Vec<String>,
+    /// Credential fields holding a URL whose host is also allowed — for
+    /// self-hosted services such as Jira's `baseUrl`.
+    #[serde(default)]
+    pub net_from_credentials: Vec<String>,
+    /// Environment variables the plugin may read.
+    #[serde(default)]
+    pub env: Vec<String>,
+}
+
+/// One input of the plugin's credentials form in Settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialField {
+    /// Key in the credentials JSON passed to the plugin.
+    pub key: String,
+    pub label: String,
+    /// `text`, `password`, `email`, `url`, or `list` (comma-separated input
+    /// stored as a JSON array).
+    #[serde(rename = "type", default = "default_field_type")]
+    pub field_type: String,
+    #[serde(default)]
+    pub placeholder: Option<String>,
+    #[serde(default)]
+    pub hint: Option<String>,
+    #[serde(default)]
+    pub optional: bool,
+}
+
+fn default_field_type() -> String {
+    "text".to_string()
+}
+
+/// A heuristic signal the plugin emits, with its default weight.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalSpec {
+    pub name: String,
+    pub weight: i32,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Contents of `{id}.manifest.json`, next to the plugin's `.ts` file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub credentials: Vec<CredentialField>,
+    #[serde(default = "default_poll_interval")]
+    pub default_poll_interval_secs: i64,
+    #[serde(default)]
+    pub signals: Vec<SignalSpec>,
+    pub permissions: PluginPermissions,
+}
+
+fn default_poll_interval() -> i64 {
+    DEFAULT_POLL_INTERVAL_SECS
+}
+
+impl PluginManifest {
+    /// Manifest path for a plugin file: `github.ts` → `github.manifest.json`.
+    pub fn path_for(plugin_path: &Path) -> PathBuf {
+        plugin_path.with_extension("manifest.json")
+    }
+
+    /// Load the manifest for a plugin. A plugin without one is refused.
+    pub fn load(plugin_path: &Path) -> Result<Self, String> {
+        let path = Self::path_for(plugin_path);
+        let json = fs::read_to_string(&path).map_err(|e| {
+            format!(
+                "Plugin has no readable manifest at {:?} ({}); refusing to run it unsandboxed",
+                path, e
+            )
+        })?;
+        Self::parse(&json).map_err(|e| format!("Invalid manifest {:?}: {}", path, e))
+    }
+
+    pub fn parse(json: &str) -> Result<Self, String> {
+        let manifest: Self = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        if !is_valid_plugin_id(&manifest.id) {
+            return Err(format!("invalid plugin id '{}'", manifest.id));
+        }
+        if manifest.default_poll_interval_secs < MIN_POLL_INTERVAL_SECS {
+            return Err(format!(
+                "defaultPollIntervalSecs must be at least {}",
+                MIN_POLL_INTERVAL_SECS
+            ));
+        }
+        if let Some(field) = manifest
+            .credentials
+            .iter()
+            .find(|f| !FIELD_TYPES.contains(&f.field_type.as_str()))
+        {
+            return Err(format!(
+                "credential field '{}' has unknown type '{}'",
+                field.key, field.field_type
+            ));
+        }
+
+        let perms = &manifest.permissions;
+        if let Some(host) = perms.net.iter().find(|h| !is_valid_host(h)) {
+            return Err(format!("invalid net host '{}'", host));
+        }
+        if let Some(var) = perms.env.iter().find(|v| !is_valid_env_name(v)) {
+            return Err(format!("invalid env var name '{}'", var));
+        }
+        Ok(manifest)
+    }
+
+    /// Deno permission flags for one run. `config_json` supplies the hosts
+    /// named by `netFromCredentials`.
+    pub fn deno_permission_args(&self, config_json: &str) -> Vec<String> {
+        let perms = &self.permissions;
+        let mut hosts = perms.net.clone();
+        if !perms.net_from_credentials.is_empty() {
+            let config: serde_json::Value = serde_json::from_str(config_json).unwrap_or_default();
+            for field in &perms.net_from_credentials {
+                if let Some(host) = config
+                    .get(field)
+                    .and_then(|v| v.as_str())
+                    .and_then(host_from_url)
+                {
+                    if !hosts.contains(&host) {
+                        hosts.push(host);
+                    }
+                }
+            }
+        }
+
+        let mut args = vec!["--no-prompt".to_string()];
+        if !hosts.is_empty() {
+            args.push(format!("--allow-net={}", hosts.join(",")));
+        }
+        if !perms.env.is_empty() {
+            args.push(format!("--allow-env={}", perms.env.join(",")));
+        }
+        args
+    }
+}
+
+fn is_valid_plugin_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn is_valid_host(host: &str) -> bool {
+    !host.is_empty()
+        && !host.starts_with('-')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | ':' | '[' | ']'))
+}
+
+fn is_valid_env_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Extract `host[:port]` from an http(s) URL, or `None` if it isn't one.
+fn host_from_url(url: &str) -> Option<String> {
+    let rest = url
+        .trim()
+        .strip_prefix("https://")
+        .or_else(|| url.trim().strip_prefix("http://"))?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    // Drop any userinfo ("user:pass@host").
+    let host = authority.rsplit('@').next()?;
+    is_valid_host(host).then(|| host.to_ascii_lowercase())
+}
+
+/// A plugin file together with its validated manifest.
+#[derive(Debug, Clone)]
+pub struct RegisteredPlugin {
+    pub manifest: PluginManifest,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Default)]
+pub struct PluginRegistry {
+    dir: PathBuf,
+    plugins: BTreeMap<String, RegisteredPlugin>,
+}
+
+impl PluginRegistry {
+    /// Scan `dir` for `{id}.ts` + `{id}.manifest.json` pairs. Plugins with an
+    /// invalid manifest are logged and skipped.
+    pub fn scan(dir: &Path) -> Self {
+        let mut registry = Self {
+            dir: dir.to_path_buf(),
+            plugins: BTreeMap::new(),
+        };
+        registry.rescan();
+        registry
+    }
+
+    /// Re-read the plugins directory, picking up added or removed plugins.
+    pub fn rescan(&mut self) {
+        self.plugins.clear();
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("[registry] Cannot read {:?}: {}", self.dir, e);
+                return;
+            }
+        };
+
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            let Some(stem) = plugin_stem(&path) else {
+                continue;
+            };
+            // No manifest: a shared module, not a plugin.
+            if !PluginManifest::path_for(&path).exists() {
+                continue;
+            }
+
+            match PluginManifest::load(&path) {
+                Ok(manifest) if manifest.id == stem => {
+                    self.plugins
+                        .insert(manifest.id.clone(), RegisteredPlugin { manifest, path });
+                }
+                Ok(manifest) => eprintln!(
+                    "[registry] Skipping {:?}: manifest id '{}' does not match file name",
+                    path, manifest.id
+                ),
+                Err(e) => eprintln!("[registry] Skipping {:?}: {}", path, e),
+            }
+        }
+    }
+
+    pub fn get(&self, plugin_id: &str) -> Option<&RegisteredPlugin> {
+        self.plugins.get(plugin_id)
+    }
+
+    /// Manifests of every registered plugin, sorted by ID.
+    pub fn manifests(&self) -> Vec<PluginManifest> {
+        self.plugins.values().map(|p| p.manifest.clone()).collect()
+    }
+
+    /// Seed `heuristic_weights` with each plugin's declared signal defaults,
+    /// keeping weights the user has already tuned.
+    pub fn seed_weights(&self, db: &Database) -> rusqlite::Result<()> {
+        for plugin in self.plugins.values() {
+            let defaults: Vec<(String, i32)> = plugin
+                .manifest
+                .signals
+                .iter()
+                .map(|s| (s.name.clone(), s.weight))
+                .collect();
+            db.seed_weights(&plugin.manifest.id, &defaults)?;
+        }
+        Ok(())
+    }
+}
+
+/// Plugin ID for a candidate plugin file, or `None` for non-plugin files
+/// (`.d.ts`, `.test.ts`, directories, other extensions).
+fn plugin_stem(path: &Path) -> Option<&str> {
+    if !path.is_file() {
+        return None;
+    }
+    let name = path.file_name()?.to_str()?;
+    let stem = name.strip_suffix(".ts")?;
+    (!stem.contains('.')).then_some(stem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_json(id: &str, permissions: &str) -> String {
+        format!(
+            r#"{{"id":"{}","name":"Demo","version":"1.0.0","permissions":{}}}"#,
+            id, permissions
+        )
+    }
+
+    fn temp_plugins_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nexus-plugins-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn manifest_defaults() {
+        let manifest = PluginManifest::parse(&manifest_json("demo", "{}")).unwrap();
+        assert_eq!(manifest.default_poll_interval_secs, 600);
+        assert!(manifest.credentials.is_empty());
+        assert!(manifest.signals.is_empty());
+    }
+
+    #[test]
+    fn manifest_validation() {
+        assert!(PluginManifest::parse(&manifest_json("../x", "{}")).is_err());
+        assert!(PluginManifest::parse(r#"{"id":"demo","permissions":{}}"#).is_err());
+        assert!(PluginManifest::parse(
+            r#"{"id":"demo","name":"Demo","version":"1","defaultPollIntervalSecs":5,"permissions":{}}"#
+        )
+        .is_err());
+        assert!(PluginManifest::parse(
+            r#"{"id":"demo","name":"Demo","version":"1","credentials":[{"key":"k","label":"K","type":"file"}],"permissions":{}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn manifest_builds_permission_flags() {
+        let manifest = PluginManifest::parse(&manifest_json(
+            "demo",
+            r#"{"net":["api.github.com"],"env":["HTTPS_PROXY"]}"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            manifest.deno_permission_args("{}"),
+            vec![
+                "--no-prompt",
+                "--allow-net=api.github.com",
+                "--allow-env=HTTPS_PROXY"
+            ]
+        );
+    }
+
+    #[test]
+    fn manifest_without_permissions_grants_nothing() {
+        let manifest = PluginManifest::parse(&manifest_json("demo", "{}")).unwrap();
+        assert_eq!(manifest.deno_permission_args("{}"), vec!["--no-prompt"]);
+        assert!(PluginManifest::parse(r#"{"id":"demo","name":"Demo","version":"1"}"#).is_err());
+    }
+
+    #[test]
+    fn manifest_allows_host_from_credentials() {
+        let manifest = PluginManifest::parse(&manifest_json(
+            "demo",
+            r#"{"netFromCredentials":["baseUrl"]}"#,
+        ))
+        .unwrap();
+        let args = manifest
+            .deno_permission_args(r#"{"baseUrl":"https://Acme.atlassian.net/","email":"a@b.c"}"#);
+        assert_eq!(args, vec!["--no-prompt", "--allow-net=acme.atlassian.net"]);
+
+        // A missing or non-URL field grants nothing.
+        let args = manifest.deno_permission_args(r#"{"baseUrl":"file:///etc/passwd"}"#);
+        assert_eq!(args, vec!["--no-prompt"]);
+    }
+
+    #[test]
+    fn manifest_rejects_flag_injection() {
+        let parse = |perms: &str| PluginManifest::parse(&manifest_json("demo", perms));
+        assert!(parse(r#"{"net":["a.com,evil.com"]}"#).is_err());
+        assert!(parse(r#"{"net":["--allow-all"]}"#).is_err());
+        assert!(parse(r#"{"env":["HOME PATH"]}"#).is_err());
+    }
+
+    #[test]
+    fn host_from_url_strips_path_and_userinfo() {
+        assert_eq!(
+            host_from_url("https://jira.local:8443/x"),
+            Some("jira.local:8443".into())
+        );
+        assert_eq!(
+            host_from_url("http://user:pw@example.com"),
+            Some("example.com".into())
+        );
+        assert_eq!(host_from_url("example.com"), None);
+    }
+
+    #[test]
+    fn missing_manifest_is_refused() {
+        let path = std::env::temp_dir().join(format!("nexus-{}.ts", uuid::Uuid::new_v4()));
+        let err = PluginManifest::load(&path).unwrap_err();
+        assert!(err.contains("manifest"));
+    }
+
+    #[test]
+    fn scan_registers_only_plugins_with_matching_manifests() {
+        let dir = temp_plugins_dir();
+        fs::write(dir.join("plugin_interface.ts"), "").unwrap();
+        fs::write(dir.join("demo.ts"), "").unwrap();
+        fs::write(dir.join("demo.manifest.json"), manifest_json("demo", "{}")).unwrap();
+        fs::write(dir.join("wrong.ts"), "").unwrap();
+        fs::write(
+            dir.join("wrong.manifest.json"),
+            manifest_json("other", "{}"),
+        )
+        .unwrap();
+        fs::write(dir.join("demo.test.ts"), "").unwrap();
+
+        let mut registry = PluginRegistry::scan(&dir);
+        assert_eq!(registry.manifests().len(), 1);
+        assert!(registry.get("demo").is_some());
+        assert!(registry.get("plugin_interface").is_none());
+        assert!(registry.get("wrong").is_none());
+
+        // Dropping in a new plugin shows up on rescan.
+        fs::write(dir.join("extra.ts"), "").unwrap();
+        fs::write(
+            dir.join("extra.manifest.json"),
+            manifest_json("extra", "{}"),
+        )
+        .unwrap();
+        registry.rescan();
+        assert!(registry.get("extra").is_some());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn bundled_plugins_register() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("plugins");
+        let ids: Vec<String> = PluginRegistry::scan(&dir)
+            .manifests()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(ids, ["github", "gmail", "jira"]);
+    }
+
+    #[test]
+    fn seed_weights_keeps_tuned_values() {
+        let dir = temp_plugins_dir();
+        fs::write(dir.join("demo.ts"), "").unwrap();
+        fs::write(
+            dir.join("demo.manifest.json"),
+            r#"{"id":"demo","name":"Demo","version":"1","permissions":{},
+                "signals":[{"name":"ping","weight":2},{"name":"pong","weight":5}]}"#,
+        )
+        .unwrap();
+        let registry = PluginRegistry::scan(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let db = Database::new_in_memory().unwrap();
+        db.seed_weights("demo", &[("ping".to_string(), 9)]).unwrap();
+        registry.seed_weights(&db).unwrap();
+
+        let mut weights: Vec<_> = db
+            .get_weights("demo")
+            .unwrap()
+            .into_iter()
+            .map(|w| (w.signal, w.weight))
+            .collect();
+        weights.sort();
+        assert_eq!(
+            weights,
+            vec![("ping".to_string(), 9), ("pong".to_string(), 5)]
+        );
+    }
+}
@@ -1,14 +1,162 @@
 //! Plugin runtime — executes TypeScript plugins via Deno subprocess.
 //!
 //! Each plugin is a TypeScript module that exports two async functions:
-//!   - `fetch(configJson: string): Promise<string>` — returns PluginResult JSON
+//!   - `fetch(configJson: string, cursor: string | null): Promise<string>` —
+//!     returns PluginResult JSON; `cursor` is what the previous poll returned
 //!   - `validateConnection(configJson: string): Promise<string>` — returns `{ok, status}` JSON
 //!
-//! Requires `deno` to be installed and available in PATH.
+//! Requires `deno` to be installed and available in PATH. Plugins run inside
+//! persistent per-plugin Deno hosts (see [`crate::plugin_host`]); this module
+//! holds the types shared with them and the parsers for what plugins return.
+//!
+//! Every call runs under a deadline and a [`CancelToken`]; a plugin that hangs
+//! or is cancelled mid-run has its Deno process killed.
+//!
+//! Failures are typed ([`PluginError`]). Plugins classify their own failures by
+//! throwing a `PluginFailure` (auth, rate limit, network, …), which the host
+//! forwards in the JSON-RPC error envelope; the scheduler reacts to the kind.
+//!
+//! Plugins are sandboxed: only plugins registered with a manifest (see
+//! [`crate::plugin_registry`]) can run, and Deno is launched with exactly the
+//! permissions the manifest declares (`--allow-net=…`, `--allow-env=…`).
+//! Credentials travel over the host's stdin, so no env access is needed to
+//! read them.
 
 use serde::{Deserialize, Serialize};
-use std::path::Path;
-use std::process::Command;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PluginError {
+    /// The plugin has no config row or no credentials yet.
+    NotConfigured(String),
+    /// The plugin is turned off.
+    Disabled(String),
+    /// The service rejected the credentials; polling again won't help until
+    /// the user re-enters them.
+    Auth(String),
+    /// The service asked us to slow down, optionally saying for how long.
+    RateLimited {
+        message: String,
+        retry_after: Option<u64>,
+    },
+    /// The service could not be reached or failed on its side.
+    Network(String),
+    /// The plugin returned something that isn't a valid result.
+    BadOutput(String),
+    /// The plugin did not finish within its deadline and was killed.
+    Timeout {
+        plugin_id: String,
+        function: String,
+        secs: u64,
+    },
+    /// The run was cancelled (app shutdown or plugin disabled) and was killed.
+    Cancelled { plugin_id: String, function: String },
+    /// Launch failure, host crash or an error the plugin didn't classify.
+    Failed(String),
+}
+
+impl PluginError {
+    /// Build the error for a failure a plugin reported. `kind` and
+    /// `retry_after` come from the `data` of the JSON-RPC error (see
+    /// `PluginFailure` in `plugins/plugin_interface.ts`); unknown or missing
+    /// kinds are plain failures.
+    pub fn from_plugin(kind: Option<&str>, message: String, retry_after: Option<u64>) -> Self {
+        match kind {
+            Some("auth") => Self::Auth(message),
+            Some("rate_limited") => Self::RateLimited {
+                message,
+                retry_after,
+            },
+            Some("network") => Self::Network(message),
+            Some("bad_output") => Self::BadOutput(message),
+            _ => Self::Failed(message),
+        }
+    }
+
+    /// Stable name of the failure kind, stored in `plugin_config.last_error_kind`
+    /// and sent to the frontend.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::NotConfigured(_) => "not_configured",
+            Self::Disabled(_) => "disabled",
+            Self::Auth(_) => "auth",
+            Self::RateLimited { .. } => "rate_limited",
+            Self::Network(_) => "network",
+            Self::BadOutput(_) => "bad_output",
+            Self::Timeout { .. } => "timeout",
+            Self::Cancelled { .. } => "cancelled",
+            Self::Failed(_) => "failed",
+        }
+    }
+
+    /// Seconds the service asked us to wait, for rate limits that said so.
+    pub fn retry_after(&self) -> Option<u64> {
+        match self {
+            Self::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout {
+                plugin_id,
+                function,
+                secs,
+            } => write!(
+                f,
+                "Plugin '{}' {} timed out after {}s",
+                plugin_id, function, secs
+            ),
+            Self::Cancelled {
+                plugin_id,
+                function,
+            } => write!(f, "Plugin '{}' {} was cancelled", plugin_id, function),
+            Self::NotConfigured(msg)
+            | Self::Disabled(msg)
+            | Self::Auth(msg)
+            | Self::RateLimited { message: msg, .. }
+            | Self::Network(msg)
+            | Self::BadOutput(msg)
+            | Self::Failed(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl From<PluginError> for String {
+    fn from(e: PluginError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Internal errors (DB, locks, crypto) that happen around a plugin call.
+impl From<String> for PluginError {
+    fn from(msg: String) -> Self {
+        Self::Failed(msg)
+    }
+}
+
+/// Shared flag used to abort a running plugin from another thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PluginItem {
@@ -25,6 +173,10 @@ pub struct PluginItem {
     pub timestamp: i64,
     pub metadata: serde_json::Value,
     pub tags: Vec<String>,
+    /// Raw heuristic signals (e.g. `review_requested`). Scored in Rust against
+    /// the stored weights; plugins that omit them keep their own notifications.
+    #[serde(default)]
+    pub signals: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,71 +191,48 @@ pub struct PluginNotification {
 pub struct PluginResult {
     pub items: Vec<PluginItem>,
     pub notifications: Vec<PluginNotification>,
+    /// Opaque sync state handed back to the plugin's next `fetch`, so it only
+    /// asks its API for changes. Plugins that omit it get a full fetch every time.
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// True when `items` only holds what changed since `cursor`, or was cut
+    /// short by an API page limit. Only complete results are used to archive
+    /// items that disappeared upstream.
+    #[serde(default)]
+    pub partial: bool,
 }
 
-/// Execute a named export from a TypeScript plugin file via `deno eval`.
-/// The function receives `config_json` as its argument and must return a JSON string.
-pub fn execute_plugin(
-    plugin_path: &Path,
-    function: &str,
-    config_json: &str,
-) -> Result<String, String> {
-    let canonical = plugin_path
-        .canonicalize()
-        .map_err(|e| format!("Cannot resolve plugin path: {}", e))?;
-
-    #[cfg(windows)]
-    let plugin_url = {
-        let path_str = canonical.to_string_lossy().replace('\\', "/");
-        // Strip UNC prefix \\?\ that canonicalize() adds on Windows
-        let path_str = path_str.trim_start_matches("//?/");
-        format!("file:///{}", path_str)
-    };
-    #[cfg(not(windows))]
-    let plugin_url = format!("file://{}", canonical.display());
-
-    // Deno eval script: import the function, call it with the config JSON, print the result.
-    let script = format!(
-        r#"import {{ {function} }} from "{plugin_url}";
-const result = await {function}(Deno.env.get("NEXUS_CONFIG") ?? "{{}}");
-console.log(result);"#
-    );
-
-    let mut cmd = Command::new("deno");
-    cmd.args(["eval", &script])
-        .env("NEXUS_CONFIG", config_json);
-
-    // On Windows, prevent a visible CMD window from flashing on each plugin execution.
-    #[cfg(windows)]
-    {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        cmd.creation_flags(CREATE_NO_WINDOW);
-    }
-
-    let output = cmd.output().map_err(|e| {
-            format!(
-                "Failed to launch deno: {}. Install Deno: https://deno.com \
-                 (Windows: winget install Deno.Deno)",
-                e
-            )
-        })?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Plugin '{}' failed: {}", function, stderr.trim()));
-    }
-
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+/// Result of a plugin's `validateConnection` export.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ConnectionStatus {
+    pub ok: bool,
+    pub status: i64,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// First 200 characters of a plugin's output, for parse error messages.
+fn preview(json: &str) -> &str {
+    match json.char_indices().nth(200) {
+        Some((end, _)) => &json[..end],
+        None => json,
+    }
 }
 
 pub fn parse_plugin_result(json: &str) -> Result<PluginResult, String> {
     serde_json::from_str(json).map_err(|e| {
-        let preview = if json.len() > 200 { &json[..200] } else { json };
+        let preview = preview(json);
         format!("Failed to parse plugin result: {} (received: {}...)", e, preview)
     })
 }
 
+pub fn parse_connection_status(json: &str) -> Result<ConnectionStatus, String> {
+    serde_json::from_str(json).map_err(|e| {
+        let preview = preview(json);
+        format!("Failed to parse connection status: {} (received: {}...)", e, preview)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,6 +267,7 @@ mod tests {
         assert_eq!(result.items[0].item_type, "ticket");
         assert_eq!(result.items[0].title, "Fix login bug");
         assert_eq!(result.items[0].summary, Some("Users cannot log in".to_string()));
+        assert!(result.items[0].signals.is_empty());
     }
 
     #[test]
@@ -146,6 +276,14 @@ mod tests {
         let result = parse_plugin_result(json).unwrap();
         assert!(result.items.is_empty());
         assert!(result.notifications.is_empty());
+        assert_eq!(result.cursor, None);
+    }
+
+    #[test]
+    fn parse_with_cursor() {
+        let json = r#"{"items":[],"notifications":[],"cursor":"{\"since\":1700000000}"}"#;
+        let result = parse_plugin_result(json).unwrap();
+        assert_eq!(result.cursor.as_deref(), Some(r#"{"since":1700000000}"#));
     }
 
     #[test]
@@ -187,6 +325,29 @@ mod tests {
         assert_eq!(result.notifications[0].urgency, "high");
     }
 
+    #[test]
+    fn parse_with_signals() {
+        let json = r#"{
+            "items": [{
+                "id": "github-1",
+                "source": "github",
+                "sourceId": "1",
+                "type": "pr",
+                "title": "Add feature",
+                "summary": null,
+                "url": "https://github.com/o/r/pull/1",
+                "author": "bob",
+                "timestamp": 1000,
+                "metadata": {},
+                "tags": [],
+                "signals": ["review_requested"]
+            }],
+            "notifications": []
+        }"#;
+        let result = parse_plugin_result(json).unwrap();
+        assert_eq!(result.items[0].signals, vec!["review_requested".to_string()]);
+    }
+
     #[test]
     fn parse_with_metadata() {
         let json = r#"{
@@ -214,4 +375,78 @@ mod tests {
         assert_eq!(meta["labels"][0], "backend");
         assert_eq!(meta["labels"][1], "api");
     }
+
+    #[test]
+    fn parse_connection_ok() {
+        let status = parse_connection_status(r#"{"ok":true,"status":200}"#).unwrap();
+        assert_eq!(
+            status,
+            ConnectionStatus {
+                ok: true,
+                status: 200,
+                error: None
+            }
+        );
+    }
+
+    #[test]
+    fn parse_connection_failure_with_error() {
+        let status =
+            parse_connection_status(r#"{"ok":false,"status":0,"error":"GitHub API error: 401"}"#)
+                .unwrap();
+        assert!(!status.ok);
+        assert_eq!(status.error.as_deref(), Some("GitHub API error: 401"));
+    }
+
+    #[test]
+    fn parse_connection_invalid() {
+        let err = parse_connection_status("oops").unwrap_err();
+        assert!(err.contains("Failed to parse connection status"));
+    }
+
+    #[test]
+    fn parse_error_preview_cuts_on_char_boundary() {
+        // 'é' is two bytes, so byte 200 falls in the middle of one.
+        let json = format!("x{}", "é".repeat(300));
+        let err = parse_connection_status(&json).unwrap_err();
+        assert!(err.contains(&format!("x{}...", "é".repeat(199))));
+
+        let err = parse_plugin_result(&json).unwrap_err();
+        assert!(err.contains("Failed to parse plugin result"));
+    }
+
+    #[test]
+    fn timeout_error_message() {
+        let err = PluginError::Timeout {
+            plugin_id: "github".to_string(),
+            function: "fetch".to_string(),
+            secs: 60,
+        };
+        assert_eq!(
+            String::from(err),
+            "Plugin 'github' fetch timed out after 60s"
+        );
+    }
+
+    #[test]
+    fn plugin_reported_kinds() {
+        let err = PluginError::from_plugin(Some("rate_limited"), "slow down".into(), Some(30));
+        assert_eq!(err.kind(), "rate_limited");
+        assert_eq!(err.retry_after(), Some(30));
+        assert_eq!(err.to_string(), "slow down");
+
+        let auth = PluginError::from_plugin(Some("auth"), "401".into(), None);
+        assert_eq!(auth, PluginError::Auth("401".to_string()));
+        assert_eq!(auth.retry_after(), None);
+
+        // Plugins that throw plain errors, or send a kind we don't know.
+        assert_eq!(
+            PluginError::from_plugin(None, "x".into(), None).kind(),
+            "failed"
+        );
+        assert_eq!(
+            PluginError::from_plugin(Some("weird"), "x".into(), None).kind(),
+            "failed"
+        );
+    }
 }
@@ -0,0 +1,263 @@
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+
+/// When native notifications stay silent, stored as JSON in the
+/// `quiet_hours_schedule` app setting. Without it, the older
+/// `quiet_hours_start` / `quiet_hours_end` pair is one daily window.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuietSchedule {
+    /// IANA name such as `Europe/Berlin`; `None` is the system time zone.
+    pub timezone: Option<String>,
+    pub windows: Vec<QuietWindow>,
+    /// Holidays and time off: quiet all day, both ends included.
+    pub days_off: Vec<DateRange>,
+}
+
+/// A daily quiet window, from `start` up to but not including `end`. One
+/// that doesn't end after it starts runs past midnight and belongs to the
+/// day it starts on, so equal times cover 24 hours. `"24:00"` ends a window
+/// at midnight: `"00:00"`–`"24:00"` (or `"00:00"`–`"00:00"`) is all day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuietWindow {
+    /// Empty = every day.
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+    pub start: String, // "HH:MM"
+    pub end: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl QuietSchedule {
+    pub fn load(db: &Database) -> Self {
+        let setting = |key: &str| db.get_app_setting(key).ok().flatten();
+        if let Some(json) = setting("quiet_hours_schedule") {
+            match serde_json::from_str::<Self>(&json) {
+                Ok(schedule) => return schedule,
+                Err(e) => eprintln!("[quiet_hours] Ignoring invalid schedule: {}", e),
+            }
+        }
+        match (setting("quiet_hours_start"), setting("quiet_hours_end")) {
+            (Some(start), Some(end)) => Self {
+                windows: vec![QuietWindow {
+                    days: Vec::new(),
+                    start,
+                    end,
+                }],
+                ..Default::default()
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// An error message for the first thing that can't be applied.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(tz) = &self.timezone {
+            tz.parse::<Tz>()
+                .map_err(|_| format!("Unknown time zone '{}'", tz))?;
+        }
+        for window in &self.windows {
+            parse_minute(&window.start, false)
+                .ok_or_else(|| format!("'{}' is not an HH:MM start time", window.start))?;
+            parse_minute(&window.end, true)
+                .ok_or_else(|| format!("'{}' is not an HH:MM time", window.end))?;
+        }
+        if let Some(range) = self.days_off.iter().find(|r| r.end < r.start) {
+            return Err(format!("{} ends before it starts", range.start));
+        }
+        Ok(())
+    }
+
+    /// Whether `now` falls on a day off or in a window, in the schedule's
+    /// time zone.
+    pub fn is_quiet_at(&self, now: DateTime<Utc>) -> bool {
+        let local = self.local_time(now);
+        let today = local.date();
+        if self
+            .days_off
+            .iter()
+            .any(|r| r.start <= today && today <= r.end)
+        {
+            return true;
+        }
+        let yesterday = (local - Duration::days(1)).weekday();
+        let time = local.hour() * 60 + local.minute();
+        self.windows.iter().any(|window| {
+            let (Some(start), Some(end)) = (
+                parse_minute(&window.start, false),
+                parse_minute(&window.end, true),
+            ) else {
+                return false;
+            };
+            let on = |day: Weekday| window.days.is_empty() || window.days.contains(&day);
+            if start < end {
+                on(today.weekday()) && start <= time && time < end
+            } else {
+                (on(today.weekday()) && time >= start) || (on(yesterday) && time < end)
+            }
+        })
+    }
+
+    fn local_time(&self, now: DateTime<Utc>) -> NaiveDateTime {
+        match self.timezone.as_deref().map(str::parse::<Tz>) {
+            Some(Ok(tz)) => now.with_timezone(&tz).naive_local(),
+            Some(Err(_)) | None => now.with_timezone(&chrono::Local).naive_local(),
+        }
+    }
+}
+
+/// Minutes since midnight of an `"HH:MM"` time; `"24:00"` (1440) only if
+/// it is the end of a window.
+fn parse_minute(time: &str, is_end: bool) -> Option<u32> {
+    if is_end && time == "24:00" {
+        return Some(24 * 60);
+    }
+    let time = NaiveTime::parse_from_str(time, "%H:%M").ok()?;
+    Some(time.hour() * 60 + time.minute())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn window(days: &[Weekday], start: &str, end: &str) -> QuietWindow {
+        QuietWindow {
+            days: days.to_vec(),
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    #[test]
+    fn windows_follow_weekdays_and_timezone() {
+        let schedule = QuietSchedule {
+            timezone: Some("America/New_York".to_string()),
+            windows: vec![
+                // Weeknights, running into the next morning.
+                window(
+                    &[
+                        Weekday::Mon,
+                        Weekday::Tue,
+                        Weekday::Wed,
+                        Weekday::Thu,
+                        Weekday::Fri,
+                    ],
+                    "22:00",
+                    "07:00",
+                ),
+                window(&[Weekday::Sat, Weekday::Sun], "00:00", "24:00"),
+                window(&[Weekday::Wed], "12:00", "13:00"),
+            ],
+            ..Default::default()
+        };
+        // 2026-03-04 is a Wednesday; New York is UTC-5 then.
+        assert!(schedule.is_quiet_at(at(2026, 3, 4, 17, 30))); // 12:30 lunch
+        assert!(!schedule.is_quiet_at(at(2026, 3, 4, 18, 0))); // 13:00
+        assert!(schedule.is_quiet_at(at(2026, 3, 5, 3, 0))); // Wed 22:00
+        assert!(schedule.is_quiet_at(at(2026, 3, 5, 11, 59))); // Thu 06:59
+        assert!(!schedule.is_quiet_at(at(2026, 3, 5, 12, 0))); // Thu 07:00
+        assert!(!schedule.is_quiet_at(at(2026, 3, 5, 17, 30))); // no lunch window on Thursday
+
+        // The weekend window runs through 23:59 and ends at midnight;
+        // daylight saving time started on March 8th.
+        assert!(schedule.is_quiet_at(at(2026, 3, 9, 3, 59))); // Sun 23:59
+        assert!(!schedule.is_quiet_at(at(2026, 3, 9, 4, 0))); // Mon 00:00
+    }
+
+    #[test]
+    fn equal_start_and_end_cover_the_day() {
+        let schedule = QuietSchedule {
+            timezone: Some("UTC".to_string()),
+            windows: vec![window(&[Weekday::Sun], "00:00", "00:00")],
+            ..Default::default()
+        };
+        // 2026-03-08 is a Sunday.
+        assert!(!schedule.is_quiet_at(at(2026, 3, 7, 23, 59)));
+        assert!(schedule.is_quiet_at(at(2026, 3, 8, 0, 0)));
+        assert!(schedule.is_quiet_at(at(2026, 3, 8, 23, 59)));
+        assert!(!schedule.is_quiet_at(at(2026, 3, 9, 0, 0)));
+    }
+
+    #[test]
+    fn days_off_are_quiet_all_day() {
+        let schedule = QuietSchedule {
+            timezone: Some("Europe/Berlin".to_string()),
+            days_off: vec![DateRange {
+                start: NaiveDate::from_ymd_opt(2026, 12, 24).unwrap(),
+                end: NaiveDate::from_ymd_opt(2026, 12, 26).unwrap(),
+            }],
+            ..Default::default()
+        };
+        assert!(!schedule.is_quiet_at(at(2026, 12, 23, 22, 59)));
+        assert!(schedule.is_quiet_at(at(2026, 12, 23, 23, 0))); // midnight in Berlin
+        assert!(schedule.is_quiet_at(at(2026, 12, 26, 22, 59)));
+        assert!(!schedule.is_quiet_at(at(2026, 12, 26, 23, 0)));
+    }
+
+    #[test]
+    fn loads_schedule_or_legacy_window() {
+        let db = Database::new_in_memory().unwrap();
+        assert_eq!(QuietSchedule::load(&db), QuietSchedule::default());
+
+        db.set_app_setting("quiet_hours_start", "22:00").unwrap();
+        db.set_app_setting("quiet_hours_end", "08:00").unwrap();
+        let legacy = QuietSchedule::load(&db);
+        assert_eq!(legacy.windows, vec![window(&[], "22:00", "08:00")]);
+        assert_eq!(legacy.timezone, None);
+
+        db.set_app_setting(
+            "quiet_hours_schedule",
+            r#"{"timezone": "Asia/Tokyo", "windows": [{"days": ["Sat", "sun"], "start": "00:00", "end": "24:00"}],
+                "days_off": [{"start": "2026-08-10", "end": "2026-08-14"}]}"#,
+        )
+        .unwrap();
+        let schedule = QuietSchedule::load(&db);
+        assert_eq!(schedule.windows[0].days, vec![Weekday::Sat, Weekday::Sun]);
+        assert_eq!(schedule.days_off.len(), 1);
+        assert!(schedule.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_zones_times_and_ranges() {
+        let bad_zone = QuietSchedule {
+            timezone: Some("Mars/Olympus".to_string()),
+            ..Default::default()
+        };
+        assert!(bad_zone.validate().unwrap_err().contains("Mars/Olympus"));
+
+        let bad_time = QuietSchedule {
+            windows: vec![window(&[], "22", "07:00")],
+            ..Default::default()
+        };
+        assert!(bad_time.validate().is_err());
+        let late_start = QuietSchedule {
+            windows: vec![window(&[], "24:00", "07:00")],
+            ..Default::default()
+        };
+        assert!(late_start.validate().is_err());
+
+        let backwards = QuietSchedule {
+            days_off: vec![DateRange {
+                start: NaiveDate::from_ymd_opt(2026, 5, 2).unwrap(),
+                end: NaiveDate::from_ymd_opt(2026, 5, 1).unwrap(),
+            }],
+            ..Default::default()
+        };
+        assert!(backwards.validate().is_err());
+    }
+}
@@ -1,15 +1,20 @@
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use chrono::Utc;
+use serde::Serialize;
 use tauri::{AppHandle, Emitter};
+use tokio::sync::Semaphore;
 use tokio::time;
-use uuid::Uuid;
 
 use crate::db::Database;
-use crate::models::{NexusItem, Notification};
-use crate::plugin_runtime;
+use crate::models::{ItemFlags, MaintenanceRun, NexusItem, PluginConfig};
+use crate::notifications::{track_urgency, UrgencyChange};
+use crate::plugin_host::PluginHosts;
+use crate::plugin_registry::{PluginRegistry, RegisteredPlugin};
+use crate::plugin_runtime::{self, CancelToken, PluginError};
+use crate::scoring::{self, TierThresholds};
 
 impl From<(&plugin_runtime::PluginItem, i64)> for NexusItem {
     fn from((pi, now): (&plugin_runtime::PluginItem, i64)) -> Self {
@@ -39,62 +44,285 @@ impl From<(&plugin_runtime::PluginItem, i64)> for NexusItem {
             is_read: false,
             created_at: now,
             updated_at: now,
+            signals: Some(
+                serde_json::to_string(&pi.signals).unwrap_or_else(|e| {
+                    eprintln!("[scheduler] signals serialization failed: {}", e);
+                    "[]".to_string()
+                }),
+            ),
+            archived_at: None,
+            snoozed_until: None,
+            flags: ItemFlags::default(),
         }
     }
 }
 
+/// Default deadline for a single plugin call, overridable per plugin via
+/// `{"timeout_secs": N}` in `plugin_config.settings` or globally via the
+/// `plugin_timeout_secs` app setting.
+const DEFAULT_PLUGIN_TIMEOUT_SECS: u64 = 60;
+
+/// Resolve the timeout for a plugin call.
+fn plugin_timeout(db: &Database, config: Option<&PluginConfig>) -> Duration {
+    let per_plugin = config
+        .and_then(|c| c.settings.as_deref())
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+        .and_then(|v| v.get("timeout_secs").and_then(|t| t.as_u64()));
+    let global = db
+        .get_app_setting("plugin_timeout_secs")
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<u64>().ok());
+
+    let secs = per_plugin
+        .or(global)
+        .filter(|s| *s > 0)
+        .unwrap_or(DEFAULT_PLUGIN_TIMEOUT_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Default number of plugins polled in parallel; override with the
+/// `poll_concurrency` app setting (read when polling starts).
+const DEFAULT_POLL_CONCURRENCY: usize = 3;
+
+fn poll_concurrency(db: &Database) -> usize {
+    db.get_app_setting("poll_concurrency")
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_POLL_CONCURRENCY)
+}
+
+/// In-flight polls, keyed by plugin ID. Shared by the polling loop and
+/// commands so that a plugin is never polled twice at once and a running poll
+/// can be aborted when its plugin is disabled or the app exits.
+#[derive(Default)]
+pub struct ActivePolls {
+    tokens: Mutex<HashMap<String, CancelToken>>,
+    shutdown: CancelToken,
+}
+
+/// Exclusive claim on a plugin's poll; released when dropped.
+pub struct PollSlot {
+    polls: Arc<ActivePolls>,
+    plugin_id: String,
+    cancel: CancelToken,
+}
+
+impl Drop for PollSlot {
+    fn drop(&mut self) {
+        if let Ok(mut tokens) = self.polls.tokens.lock() {
+            tokens.remove(&self.plugin_id);
+        }
+    }
+}
+
+impl ActivePolls {
+    /// Claim the poll slot for a plugin, or `None` if it is already being polled.
+    pub fn try_begin(self: &Arc<Self>, plugin_id: &str) -> Option<PollSlot> {
+        let mut tokens = self.tokens.lock().ok()?;
+        if tokens.contains_key(plugin_id) {
+            return None;
+        }
+
+        let cancel = CancelToken::new();
+        if self.shutdown.is_cancelled() {
+            cancel.cancel();
+        }
+        tokens.insert(plugin_id.to_string(), cancel.clone());
+        Some(PollSlot {
+            polls: Arc::clone(self),
+            plugin_id: plugin_id.to_string(),
+            cancel,
+        })
+    }
+
+    /// Abort the in-flight poll of one plugin, if any.
+    pub fn cancel(&self, plugin_id: &str) {
+        if let Ok(tokens) = self.tokens.lock() {
+            if let Some(token) = tokens.get(plugin_id) {
+                token.cancel();
+            }
+        }
+    }
+
+    /// Abort every in-flight poll and refuse new ones (app shutdown).
+    pub fn cancel_all(&self) {
+        self.shutdown.cancel();
+        if let Ok(tokens) = self.tokens.lock() {
+            tokens.values().for_each(CancelToken::cancel);
+        }
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.is_cancelled()
+    }
+}
+
 pub struct Scheduler {
-    plugins_dir: PathBuf,
+    registry: Arc<Mutex<PluginRegistry>>,
+    polls: Arc<ActivePolls>,
+    hosts: Arc<PluginHosts>,
 }
 
 impl Scheduler {
-    pub fn new(plugins_dir: PathBuf) -> Self {
-        Self { plugins_dir }
+    pub fn new(
+        registry: Arc<Mutex<PluginRegistry>>,
+        polls: Arc<ActivePolls>,
+        hosts: Arc<PluginHosts>,
+    ) -> Self {
+        Self {
+            registry,
+            polls,
+            hosts,
+        }
+    }
+
+    /// Look up a registered plugin. Only plugins with a valid manifest run.
+    pub fn plugin(&self, plugin_id: &str) -> Result<RegisteredPlugin, String> {
+        self.registry
+            .lock()
+            .map_err(|e| format!("Registry lock error: {}", e))?
+            .get(plugin_id)
+            .cloned()
+            .ok_or_else(|| {
+                format!(
+                    "Plugin '{}' is not installed (no valid manifest in the plugins directory)",
+                    plugin_id
+                )
+            })
+    }
+
+    /// Run the plugin's `validateConnection` export against the given (possibly
+    /// unsaved) credentials JSON. Nothing is read from or written to the DB.
+    pub fn test_connection(
+        &self,
+        plugin_id: &str,
+        credentials: &str,
+        db: &Arc<Mutex<Database>>,
+    ) -> Result<plugin_runtime::ConnectionStatus, String> {
+        let timeout = {
+            let db_ref = db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+            let config = db_ref
+                .get_plugin_config(plugin_id)
+                .map_err(|e| e.to_string())?;
+            plugin_timeout(&db_ref, config.as_ref())
+        };
+
+        let plugin = self.plugin(plugin_id)?;
+        let json = self.hosts.call(
+            &plugin,
+            "validateConnection",
+            credentials,
+            None,
+            timeout,
+            &CancelToken::new(),
+        )?;
+        plugin_runtime::parse_connection_status(&json)
     }
 
     /// Poll a single plugin by ID, persist results to the database, and return the item count.
     /// Acquires and releases the DB lock internally so callers don't need to hold it.
     /// `app` is used to fire native OS notifications for medium+ urgency items.
+    /// Blocks on the plugin subprocess — call from a blocking thread, not the async runtime.
     pub fn poll_plugin(
         &self,
         plugin_id: &str,
         db: &Arc<Mutex<Database>>,
         app: &AppHandle,
-    ) -> Result<usize, String> {
+    ) -> Result<usize, PluginError> {
+        let slot = self.polls.try_begin(plugin_id).ok_or_else(|| {
+            PluginError::Failed(format!("Plugin '{}' poll already in progress", plugin_id))
+        })?;
+        self.poll_in_slot(&slot, db, app)
+    }
+
+    fn poll_in_slot(
+        &self,
+        slot: &PollSlot,
+        db: &Arc<Mutex<Database>>,
+        app: &AppHandle,
+    ) -> Result<usize, PluginError> {
+        let plugin_id = slot.plugin_id.as_str();
+        let cancel = &slot.cancel;
+
         // -- Phase 1: read config (short lock) --
-        let (credentials, config) = {
+        let (sealed, config, cursor, timeout) = {
             let db_ref = db.lock().map_err(|e| format!("DB lock error: {}", e))?;
             let config = db_ref
                 .get_plugin_config(plugin_id)
                 .map_err(|e| e.to_string())?
-                .ok_or_else(|| format!("Plugin '{}' not configured", plugin_id))?;
+                .ok_or_else(|| {
+                    PluginError::NotConfigured(format!("Plugin '{}' not configured", plugin_id))
+                })?;
 
             if !config.is_enabled {
-                return Err(format!("Plugin '{}' is disabled", plugin_id));
+                return Err(PluginError::Disabled(format!(
+                    "Plugin '{}' is disabled",
+                    plugin_id
+                )));
             }
 
-            let credentials = config
-                .credentials
-                .clone()
-                .ok_or_else(|| format!("Plugin '{}' has no credentials", plugin_id))?;
+            let sealed = config.credentials.clone().ok_or_else(|| {
+                PluginError::NotConfigured(format!("Plugin '{}' has no credentials", plugin_id))
+            })?;
 
-            (credentials, config)
+            let timeout = plugin_timeout(&db_ref, Some(&config));
+            let cursor = db_ref
+                .get_sync_cursor(plugin_id)
+                .map_err(|e| e.to_string())?;
+
+            (sealed, config, cursor, timeout)
             // db_ref dropped here — lock released before subprocess call
         };
 
-        // Validate plugin_id to prevent path traversal
-        if plugin_id.contains("..") || plugin_id.contains('/') || plugin_id.contains('\\') {
-            return Err(format!("Invalid plugin ID: '{}'", plugin_id));
-        }
+        let request = FetchRequest {
+            sealed: &sealed,
+            cursor: cursor.as_deref(),
+            timeout,
+            cancel,
+        };
+        let outcome = self.fetch_and_persist(plugin_id, request, config, db, app);
 
-        let plugin_path = self.plugins_dir.join(format!("{}.ts", plugin_id));
-        if !plugin_path.exists() {
-            return Err(format!("Plugin file not found: {:?}", plugin_path));
+        // Everything past configuration is a real poll failure: persist it and
+        // tell the frontend so a broken plugin doesn't fail silently. A
+        // cancelled poll (plugin disabled, app exiting) is not the plugin's fault.
+        if let Err(e) = &outcome {
+            if !cancel.is_cancelled() {
+                record_failure(plugin_id, e, db, app);
+            }
         }
+        outcome
+    }
+
+    fn fetch_and_persist(
+        &self,
+        plugin_id: &str,
+        request: FetchRequest<'_>,
+        config: PluginConfig,
+        db: &Arc<Mutex<Database>>,
+        app: &AppHandle,
+    ) -> Result<usize, PluginError> {
+        // Decrypted only for the duration of this poll; never written back.
+        let credentials = db
+            .lock()
+            .map_err(|e| format!("DB lock error: {}", e))?
+            .reveal_credentials(request.sealed)?;
+
+        let plugin = self.plugin(plugin_id)?;
 
         // -- Phase 2: execute plugin (NO lock held — subprocess may take seconds) --
-        let result_json = plugin_runtime::execute_plugin(&plugin_path, "fetch", &credentials)?;
-        let result = plugin_runtime::parse_plugin_result(&result_json)?;
+        let result_json = self.hosts.call(
+            &plugin,
+            "fetch",
+            &credentials,
+            request.cursor,
+            request.timeout,
+            request.cancel,
+        )?;
+        let result =
+            plugin_runtime::parse_plugin_result(&result_json).map_err(PluginError::BadOutput)?;
 
         let now = Utc::now().timestamp();
 
@@ -102,41 +330,94 @@ impl Scheduler {
         {
             let db_ref = db.lock().map_err(|e| format!("DB lock error: {}", e))?;
 
+            let thresholds = TierThresholds::load(&db_ref);
+            let mut weights: HashMap<String, scoring::Weights> = HashMap::new();
+            // (item_id, reason, urgency) for every scored item and plugin
+            // notification of this poll; low ones can only downgrade.
+            let mut pending: Vec<(String, String, String)> = Vec::new();
+
             for pi in &result.items {
-                let item = NexusItem::from((pi, now));
+                let mut item = NexusItem::from((pi, now));
+                if !pi.signals.is_empty() {
+                    if !weights.contains_key(&pi.source) {
+                        let w = scoring::load_weights(&db_ref, &pi.source)
+                            .map_err(|e| e.to_string())?;
+                        weights.insert(pi.source.clone(), w);
+                    }
+                    let source_weights = &weights[&pi.source];
+                    item.priority = scoring::score(&pi.signals, source_weights);
+
+                    // The full list stays in `items.signals`.
+                    let urgency = thresholds.urgency(item.priority);
+                    let reason = scoring::deciding_signal(&pi.signals, source_weights);
+                    pending.push((pi.id.clone(), reason.to_string(), urgency.to_string()));
+                }
                 db_ref.upsert_item(&item).map_err(|e| e.to_string())?;
             }
 
+            // Plugins that don't report signals still decide urgency themselves.
             for pn in &result.notifications {
-                // Skip if an active notification already exists for this item+reason
-                if db_ref
-                    .has_active_notification(&pn.item_id, &pn.reason)
-                    .unwrap_or(false)
-                {
-                    continue;
+                let scored = result
+                    .items
+                    .iter()
+                    .any(|i| i.id == pn.item_id && !i.signals.is_empty());
+                if !scored {
+                    pending.push((pn.item_id.clone(), pn.reason.clone(), pn.urgency.clone()));
                 }
+            }
 
-                let notif = Notification {
-                    id: Uuid::new_v4().to_string(),
-                    item_id: pn.item_id.clone(),
-                    reason: pn.reason.clone(),
-                    urgency: pn.urgency.clone(),
-                    is_dismissed: false,
-                    created_at: now,
-                };
-                db_ref.insert_notification(&notif).map_err(|e| e.to_string())?;
+            let mut notified = false;
+            let mut downgraded = false;
+            for (item_id, reason, urgency) in pending {
+                match track_urgency(&db_ref, &item_id, &reason, &urgency, now)
+                    .map_err(|e| e.to_string())?
+                {
+                    Some(UrgencyChange::Deescalated) => downgraded = true,
+                    Some(_) => notified = true,
+                    None => {}
+                }
+            }
+            // Delivered together, so a large sync doesn't fire a toast per item.
+            if notified {
+                crate::notifications::flush_pending(app, &db_ref, now);
+            }
+            if notified || downgraded {
+                crate::notifications::refresh_badge(app, &db_ref);
+            }
 
-                if let Some(item) = result.items.iter().find(|i| i.id == pn.item_id) {
-                    if crate::notifications::should_send_notification(&db_ref, &pn.urgency) {
-                        crate::notifications::send_native_notification(app, &notif, &item.title);
-                    }
+            // A full result is the plugin's whole view: anything it no longer
+            // returns was merged, closed or reassigned upstream. Partial
+            // (incremental) results say nothing about unchanged items.
+            if !result.partial {
+                let seen: HashSet<&str> = result
+                    .items
+                    .iter()
+                    .filter(|i| i.source == plugin_id)
+                    .map(|i| i.source_id.as_str())
+                    .collect();
+                let archived = db_ref
+                    .archive_missing_items(plugin_id, &seen, now)
+                    .map_err(|e| e.to_string())?;
+                if !archived.is_empty() {
+                    println!(
+                        "[scheduler] {}: archived {} items no longer upstream",
+                        plugin_id,
+                        archived.len()
+                    );
                 }
             }
 
-            let mut updated_config = config.clone();
+            db_ref
+                .set_sync_cursor(plugin_id, result.cursor.as_deref(), now)
+                .map_err(|e| e.to_string())?;
+
+            let mut updated_config = config;
             updated_config.last_poll_at = Some(now);
             updated_config.last_error = None;
+            updated_config.last_error_at = None;
             updated_config.error_count = 0;
+            updated_config.last_error_kind = None;
+            updated_config.retry_at = None;
             db_ref
                 .upsert_plugin_config(&updated_config)
                 .map_err(|e| e.to_string())?;
@@ -147,10 +428,286 @@ impl Scheduler {
     }
 }
 
+/// Inputs of one plugin `fetch` call.
+struct FetchRequest<'a> {
+    /// Credentials as stored (sealed when encryption is on).
+    sealed: &'a str,
+    /// Sync state from the previous poll; `None` means a full fetch.
+    cursor: Option<&'a str>,
+    timeout: Duration,
+    cancel: &'a CancelToken,
+}
+
+/// Payload of the `items-poll-failed` event.
+#[derive(Debug, Clone, Serialize)]
+pub struct PollFailure {
+    pub plugin_id: String,
+    pub error: String,
+    /// [`PluginError::kind`], so the UI can e.g. ask for new credentials.
+    pub kind: String,
+    pub error_count: i32,
+    pub failed_at: i64,
+    /// When a rate-limited plugin will be polled again.
+    pub retry_at: Option<i64>,
+}
+
+/// Retry pacing for failing plugins, read from app settings:
+/// `poll_backoff_base_secs`, `poll_backoff_max_secs`, `poll_max_failures`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackoffPolicy {
+    /// Delay after the first failure; doubles with every further failure.
+    pub base_secs: i64,
+    /// Upper bound on the retry delay.
+    pub max_secs: i64,
+    /// Consecutive failures after which the plugin is auto-disabled.
+    pub max_failures: i32,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            base_secs: 60,
+            max_secs: 3600,
+            max_failures: 5,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    pub fn load(db: &Database) -> Self {
+        let defaults = Self::default();
+        let read = |key: &str| {
+            db.get_app_setting(key)
+                .ok()
+                .flatten()
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .filter(|v| *v > 0)
+        };
+        Self {
+            base_secs: read("poll_backoff_base_secs").unwrap_or(defaults.base_secs),
+            max_secs: read("poll_backoff_max_secs").unwrap_or(defaults.max_secs),
+            max_failures: read("poll_max_failures")
+                .map(|v| v.min(i32::MAX as i64) as i32)
+                .unwrap_or(defaults.max_failures),
+        }
+    }
+
+    /// Delay before retrying a plugin that has failed `error_count` times in a row.
+    pub fn retry_delay(&self, error_count: i32) -> i64 {
+        let exponent = (error_count.max(1) - 1).min(30) as u32;
+        self.base_secs
+            .saturating_mul(1i64 << exponent)
+            .min(self.max_secs)
+    }
+
+    /// Whether a plugin should be polled at `now`: on its normal interval when
+    /// healthy, or once the backoff delay has passed since its last failure.
+    /// A rate-limited plugin also waits until its `retry_at`.
+    pub fn is_due(&self, config: &PluginConfig, now: i64) -> bool {
+        if config.retry_at.is_some_and(|at| now < at) {
+            return false;
+        }
+        if config.error_count > 0 {
+            if let Some(failed_at) = config.last_error_at {
+                return now - failed_at >= self.retry_delay(config.error_count);
+            }
+        }
+        match config.last_poll_at {
+            Some(last) => now - last >= config.poll_interval_secs,
+            None => true, // never polled → poll immediately
+        }
+    }
+}
+
+/// Persist a poll failure to `plugin_config` and emit `items-poll-failed`.
+///
+/// How the failure counts depends on its kind: a rate limit pauses the plugin
+/// until the service's `Retry-After` (or the backoff delay) without counting
+/// towards the circuit breaker; rejected credentials disable the plugin right
+/// away, since retrying can't fix them; anything else trips the breaker once
+/// the plugin reaches `max_failures` in a row.
+fn record_failure(
+    plugin_id: &str,
+    error: &PluginError,
+    db: &Arc<Mutex<Database>>,
+    app: &AppHandle,
+) {
+    let now = Utc::now().timestamp();
+    let message = error.to_string();
+    let (error_count, retry_at, disabled_reason) = match db.lock() {
+        Ok(db_ref) => {
+            let policy = BackoffPolicy::load(&db_ref);
+            let (recorded, retry_at) = match error {
+                PluginError::RateLimited { .. } => {
+                    let count = db_ref
+                        .get_plugin_config(plugin_id)
+                        .ok()
+                        .flatten()
+                        .map_or(0, |c| c.error_count);
+                    let delay = error
+                        .retry_after()
+                        .map(|secs| secs.min(i64::MAX as u64) as i64)
+                        .unwrap_or_else(|| policy.retry_delay(count + 1));
+                    let retry_at = now.saturating_add(delay);
+                    (
+                        db_ref.record_rate_limit(plugin_id, &message, retry_at, now),
+                        Some(retry_at),
+                    )
+                }
+                _ => (
+                    db_ref.record_poll_failure(plugin_id, &message, error.kind(), now),
+                    None,
+                ),
+            };
+            let count = recorded.unwrap_or_else(|e| {
+                eprintln!(
+                    "[scheduler] Failed to record {} poll error: {}",
+                    plugin_id, e
+                );
+                0
+            });
+
+            let reason = match error {
+                PluginError::Auth(_) => Some(format!(
+                    "Disabled because the credentials were rejected — re-enter them in Settings. \
+                     Last error: {}",
+                    message
+                )),
+                PluginError::RateLimited { .. } => None,
+                _ if count >= policy.max_failures => Some(format!(
+                    "Disabled after {} consecutive failures. Last error: {}",
+                    count, message
+                )),
+                _ => None,
+            };
+            let mut disabled_reason = None;
+            if let Some(reason) = reason {
+                match db_ref.auto_disable_plugin(plugin_id, &reason, now) {
+                    Ok(()) => disabled_reason = Some(reason),
+                    Err(e) => eprintln!("[scheduler] Failed to disable {}: {}", plugin_id, e),
+                }
+            }
+            (count, retry_at, disabled_reason)
+        }
+        Err(e) => {
+            eprintln!("[scheduler] DB lock error: {}", e);
+            (0, None, None)
+        }
+    };
+
+    let payload = PollFailure {
+        plugin_id: plugin_id.to_string(),
+        error: message,
+        kind: error.kind().to_string(),
+        error_count,
+        failed_at: now,
+        retry_at,
+    };
+    if let Err(e) = app.emit("items-poll-failed", payload.clone()) {
+        eprintln!("[scheduler] Failed to emit items-poll-failed: {}", e);
+    }
+
+    if let Some(reason) = disabled_reason {
+        crate::notifications::send_plugin_disabled_notification(app, plugin_id, &reason);
+        if let Err(e) = app.emit(
+            "plugin-disabled",
+            PollFailure {
+                error: reason,
+                ..payload
+            },
+        ) {
+            eprintln!("[scheduler] Failed to emit plugin-disabled: {}", e);
+        }
+    }
+}
+
+/// Run one scheduled poll on a blocking thread and report the outcome.
+fn run_scheduled_poll(
+    scheduler: &Scheduler,
+    slot: &PollSlot,
+    db: &Arc<Mutex<Database>>,
+    app: &AppHandle,
+) {
+    match scheduler.poll_in_slot(slot, db, app) {
+        Ok(count) => {
+            println!("[scheduler] {}: fetched {} items", slot.plugin_id, count);
+            if let Err(e) = app.emit("items-updated", slot.plugin_id.as_str()) {
+                eprintln!("[scheduler] Failed to emit items-updated: {}", e);
+            }
+        }
+        Err(e) => {
+            let silent = matches!(
+                e,
+                PluginError::NotConfigured(_)
+                    | PluginError::Disabled(_)
+                    | PluginError::Cancelled { .. }
+            ) || slot.cancel.is_cancelled();
+            if !silent {
+                eprintln!("[scheduler] {} poll error: {}", slot.plugin_id, e);
+            }
+        }
+    }
+}
+
+/// Clear the snoozes that came due and announce them again: woken
+/// notifications re-fire their native notification, and a woken item without
+/// one gets a plain reminder.
+fn wake_snoozed(db: &Arc<Mutex<Database>>, app: &AppHandle, now: i64) {
+    let Ok(db_ref) = db.lock() else {
+        return;
+    };
+    let woken = match db_ref.wake_snoozed(now) {
+        Ok(woken) => woken,
+        Err(e) => {
+            eprintln!("[scheduler] Failed to wake snoozed items: {}", e);
+            return;
+        }
+    };
+    if woken.items.is_empty() && woken.notifications.is_empty() {
+        return;
+    }
+
+    for (notif, title) in &woken.notifications {
+        crate::notifications::dispatch(app, &db_ref, notif, title, now);
+    }
+    let thresholds = TierThresholds::load(&db_ref);
+    for item in &woken.items {
+        if woken
+            .notifications
+            .iter()
+            .any(|(n, _)| n.item_id == item.id)
+        {
+            continue;
+        }
+        let urgency = thresholds.urgency(item.priority);
+        if crate::notifications::should_send_notification(&db_ref, urgency, now) {
+            crate::notifications::send_reminder_notification(app, &item.title);
+        }
+    }
+    drop(db_ref);
+
+    if let Err(e) = app.emit("items-updated", "snooze") {
+        eprintln!("[scheduler] Failed to emit items-updated: {}", e);
+    }
+}
+
 /// Spawn a background tokio task that checks plugins every 30 seconds
 /// and polls each one when its configured `poll_interval_secs` has elapsed.
-pub fn start_polling(app: AppHandle, db: Arc<Mutex<Database>>, plugins_dir: PathBuf) {
-    let scheduler = Scheduler::new(plugins_dir);
+/// Due plugins are polled in parallel on blocking threads, at most
+/// `poll_concurrency` at a time; a plugin still being polled is skipped.
+pub fn start_polling(
+    app: AppHandle,
+    db: Arc<Mutex<Database>>,
+    registry: Arc<Mutex<PluginRegistry>>,
+    polls: Arc<ActivePolls>,
+    hosts: Arc<PluginHosts>,
+) {
+    let scheduler = Arc::new(Scheduler::new(registry, Arc::clone(&polls), hosts));
+    let concurrency = match db.lock() {
+        Ok(db_ref) => poll_concurrency(&db_ref),
+        Err(_) => DEFAULT_POLL_CONCURRENCY,
+    };
+    let limit = Arc::new(Semaphore::new(concurrency));
 
     tauri::async_runtime::spawn(async move {
         // Short heartbeat: check which plugins are due every 30s.
@@ -159,8 +716,18 @@ pub fn start_polling(app: AppHandle, db: Arc<Mutex<Database>>, plugins_dir: Path
         loop {
             heartbeat.tick().await;
 
+            if polls.is_shutting_down() {
+                break;
+            }
+
             let now = Utc::now().timestamp();
 
+            wake_snoozed(&db, &app, now);
+            if let Ok(db_ref) = db.lock() {
+                crate::notifications::flush_pending(&app, &db_ref, now);
+                crate::notifications::reescalate_overdue(&app, &db_ref, now);
+            }
+
             // Get enabled plugin configs (short lock, then release).
             let configs = match db.lock() {
                 Ok(db_ref) => db_ref.get_enabled_plugin_configs().unwrap_or_default(),
@@ -170,34 +737,347 @@ pub fn start_polling(app: AppHandle, db: Arc<Mutex<Database>>, plugins_dir: Path
                 }
             };
 
-            // Poll each plugin only if its interval has elapsed since last_poll_at.
-            for config in &configs {
-                let elapsed = match config.last_poll_at {
-                    Some(last) => now - last,
-                    None => i64::MAX, // never polled → poll immediately
-                };
+            let policy = match db.lock() {
+                Ok(db_ref) => BackoffPolicy::load(&db_ref),
+                Err(_) => BackoffPolicy::default(),
+            };
 
-                if elapsed < config.poll_interval_secs {
+            // Poll each plugin only if its interval (or failure backoff) has elapsed.
+            for config in &configs {
+                if !policy.is_due(config, now) {
                     continue;
                 }
+                // Claim the slot now so a queued poll isn't queued again next tick.
+                let Some(slot) = polls.try_begin(&config.plugin_id) else {
+                    continue;
+                };
 
-                match scheduler.poll_plugin(&config.plugin_id, &db, &app) {
-                    Ok(count) => {
-                        println!("[scheduler] {}: fetched {} items", config.plugin_id, count);
-                        if let Err(e) = app.emit("items-updated", config.plugin_id.as_str()) {
-                            eprintln!("[scheduler] Failed to emit items-updated: {}", e);
-                        }
+                let scheduler = Arc::clone(&scheduler);
+                let limit = Arc::clone(&limit);
+                let db = Arc::clone(&db);
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    let Ok(_permit) = limit.acquire_owned().await else {
+                        return;
+                    };
+                    let joined = tauri::async_runtime::spawn_blocking(move || {
+                        run_scheduled_poll(&scheduler, &slot, &db, &app)
+                    })
+                    .await;
+                    if let Err(e) = joined {
+                        eprintln!("[scheduler] poll task failed: {}", e);
                     }
-                    Err(e) => {
-                        let silent = e.contains("no credentials")
-                            || e.contains("not configured")
-                            || e.contains("disabled");
-                        if !silent {
-                            eprintln!("[scheduler] {} poll error: {}", config.plugin_id, e);
-                        }
-                    }
-                }
+                });
             }
         }
     });
 }
+
+/// What the maintenance task deletes, read from app settings (in days; 0
+/// keeps rows forever): `retention_read_item_days`,
+/// `retention_archived_item_days`, `retention_dismissed_notification_days`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Read items not seen upstream for this long.
+    pub read_item_days: i64,
+    /// Items archived (gone upstream) this long ago.
+    pub archived_item_days: i64,
+    /// Dismissed notifications older than this.
+    pub dismissed_notification_days: i64,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            read_item_days: 30,
+            archived_item_days: 14,
+            dismissed_notification_days: 7,
+        }
+    }
+}
+
+impl RetentionPolicy {
+    pub fn load(db: &Database) -> Self {
+        let defaults = Self::default();
+        let read = |key: &str, fallback: i64| {
+            db.get_app_setting(key)
+                .ok()
+                .flatten()
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .filter(|v| *v >= 0)
+                .unwrap_or(fallback)
+        };
+        Self {
+            read_item_days: read("retention_read_item_days", defaults.read_item_days),
+            archived_item_days: read("retention_archived_item_days", defaults.archived_item_days),
+            dismissed_notification_days: read(
+                "retention_dismissed_notification_days",
+                defaults.dismissed_notification_days,
+            ),
+        }
+    }
+
+    /// Timestamp before which rows kept for `days` are deleted; `None` for 0.
+    fn cutoff(days: i64, now: i64) -> Option<i64> {
+        (days > 0).then(|| now.saturating_sub(days.saturating_mul(86_400)))
+    }
+}
+
+/// Share of free pages from which maintenance rebuilds the file. VACUUM
+/// holds the database lock throughout, so small prunes don't warrant it.
+const VACUUM_FREE_PAGE_RATIO: f64 = 0.2;
+
+/// Apply the retention policy, refresh planner statistics (and VACUUM when
+/// enough of the file is free), and log the run to `maintenance_runs`.
+pub fn run_maintenance(db: &Database, now: i64) -> rusqlite::Result<MaintenanceRun> {
+    let policy = RetentionPolicy::load(db);
+    let items_pruned = db.prune_items(
+        RetentionPolicy::cutoff(policy.read_item_days, now),
+        RetentionPolicy::cutoff(policy.archived_item_days, now),
+    )?;
+    let notifications_pruned =
+        match RetentionPolicy::cutoff(policy.dismissed_notification_days, now) {
+            Some(cutoff) => db.prune_dismissed_notifications(cutoff)?,
+            None => 0,
+        };
+
+    let vacuumed = db.free_page_ratio()? >= VACUUM_FREE_PAGE_RATIO;
+    db.optimize(vacuumed)?;
+
+    let run = MaintenanceRun {
+        ran_at: now,
+        items_pruned,
+        notifications_pruned,
+        vacuumed,
+    };
+    db.record_maintenance_run(&run)?;
+    Ok(run)
+}
+
+/// Delay before the first maintenance pass, so it doesn't compete with the
+/// startup polls.
+const MAINTENANCE_START_DELAY: Duration = Duration::from_secs(5 * 60);
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+
+/// Spawn a background task that runs [`run_maintenance`] every few hours.
+pub fn start_maintenance(db: Arc<Mutex<Database>>, polls: Arc<ActivePolls>) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = time::interval_at(
+            time::Instant::now() + MAINTENANCE_START_DELAY,
+            MAINTENANCE_INTERVAL,
+        );
+        loop {
+            ticker.tick().await;
+            if polls.is_shutting_down() {
+                break;
+            }
+
+            let db = Arc::clone(&db);
+            let joined = tauri::async_runtime::spawn_blocking(move || {
+                let db_ref = db.lock().map_err(|e| format!("DB lock error: {}", e))?;
+                run_maintenance(&db_ref, Utc::now().timestamp()).map_err(|e| e.to_string())
+            })
+            .await;
+            match joined {
+                Ok(Ok(run)) => println!(
+                    "[scheduler] maintenance: pruned {} items, {} notifications",
+                    run.items_pruned, run.notifications_pruned
+                ),
+                Ok(Err(e)) => eprintln!("[scheduler] maintenance failed: {}", e),
+                Err(e) => eprintln!("[scheduler] maintenance task failed: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(
+        error_count: i32,
+        last_poll_at: Option<i64>,
+        last_error_at: Option<i64>,
+    ) -> PluginConfig {
+        PluginConfig {
+            plugin_id: "github".to_string(),
+            is_enabled: true,
+            credentials: None,
+            poll_interval_secs: 600,
+            last_poll_at,
+            last_error: None,
+            error_count,
+            settings: None,
+            last_error_at,
+            auto_disabled_at: None,
+            last_error_kind: None,
+            retry_at: None,
+        }
+    }
+
+    #[test]
+    fn retry_delay_doubles_and_caps() {
+        let policy = BackoffPolicy::default();
+        assert_eq!(policy.retry_delay(1), 60);
+        assert_eq!(policy.retry_delay(2), 120);
+        assert_eq!(policy.retry_delay(3), 240);
+        assert_eq!(policy.retry_delay(7), 3600);
+        assert_eq!(policy.retry_delay(1000), 3600);
+    }
+
+    #[test]
+    fn healthy_plugin_follows_poll_interval() {
+        let policy = BackoffPolicy::default();
+        assert!(policy.is_due(&config(0, None, None), 1000));
+        assert!(!policy.is_due(&config(0, Some(1000), None), 1599));
+        assert!(policy.is_due(&config(0, Some(1000), None), 1600));
+    }
+
+    #[test]
+    fn failing_plugin_waits_for_backoff() {
+        let policy = BackoffPolicy::default();
+        // Third failure at t=5000 → wait 240s, regardless of the last good poll.
+        let c = config(3, Some(0), Some(5000));
+        assert!(!policy.is_due(&c, 5239));
+        assert!(policy.is_due(&c, 5240));
+    }
+
+    #[test]
+    fn rate_limited_plugin_waits_for_retry_at() {
+        let policy = BackoffPolicy::default();
+        let mut c = config(0, Some(0), Some(1000));
+        c.retry_at = Some(1300);
+        assert!(!policy.is_due(&c, 1299));
+        assert!(policy.is_due(&c, 1300));
+    }
+
+    #[test]
+    fn plugin_timeout_prefers_plugin_setting() {
+        let db = Database::new_in_memory().unwrap();
+        let mut c = config(0, None, None);
+        assert_eq!(plugin_timeout(&db, Some(&c)), Duration::from_secs(60));
+
+        db.set_app_setting("plugin_timeout_secs", "90").unwrap();
+        assert_eq!(plugin_timeout(&db, Some(&c)), Duration::from_secs(90));
+
+        c.settings = Some(r#"{"timeout_secs":15}"#.to_string());
+        assert_eq!(plugin_timeout(&db, Some(&c)), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn active_polls_cancel_by_plugin_and_on_shutdown() {
+        let polls = Arc::new(ActivePolls::default());
+        let jira = polls.try_begin("jira").unwrap();
+        let github = polls.try_begin("github").unwrap();
+
+        polls.cancel("jira");
+        assert!(jira.cancel.is_cancelled());
+        assert!(!github.cancel.is_cancelled());
+
+        polls.cancel_all();
+        assert!(github.cancel.is_cancelled());
+        assert!(polls.is_shutting_down());
+        // Polls started after shutdown are born cancelled.
+        assert!(polls.try_begin("gmail").unwrap().cancel.is_cancelled());
+    }
+
+    #[test]
+    fn active_polls_reject_overlapping_poll() {
+        let polls = Arc::new(ActivePolls::default());
+        let slot = polls.try_begin("jira").unwrap();
+        assert!(polls.try_begin("jira").is_none());
+        assert!(polls.try_begin("github").is_some());
+
+        drop(slot);
+        assert!(polls.try_begin("jira").is_some());
+    }
+
+    #[test]
+    fn poll_concurrency_falls_back_to_default() {
+        let db = Database::new_in_memory().unwrap();
+        assert_eq!(poll_concurrency(&db), DEFAULT_POLL_CONCURRENCY);
+        db.set_app_setting("poll_concurrency", "0").unwrap();
+        assert_eq!(poll_concurrency(&db), DEFAULT_POLL_CONCURRENCY);
+        db.set_app_setting("poll_concurrency", "5").unwrap();
+        assert_eq!(poll_concurrency(&db), 5);
+    }
+
+    #[test]
+    fn policy_loads_from_settings() {
+        let db = Database::new_in_memory().unwrap();
+        db.set_app_setting("poll_backoff_max_secs", "900").unwrap();
+        db.set_app_setting("poll_max_failures", "0").unwrap(); // invalid → default
+
+        let policy = BackoffPolicy::load(&db);
+        assert_eq!(policy.base_secs, 60);
+        assert_eq!(policy.max_secs, 900);
+        assert_eq!(policy.max_failures, 5);
+    }
+
+    #[test]
+    fn retention_policy_zero_keeps_forever() {
+        let db = Database::new_in_memory().unwrap();
+        db.set_app_setting("retention_read_item_days", "0").unwrap();
+        db.set_app_setting("retention_archived_item_days", "-3")
+            .unwrap();
+        let policy = RetentionPolicy::load(&db);
+        assert_eq!(policy.read_item_days, 0);
+        assert_eq!(policy.archived_item_days, 14);
+        assert_eq!(RetentionPolicy::cutoff(0, 1_000_000), None);
+        assert_eq!(RetentionPolicy::cutoff(1, 1_000_000), Some(913_600));
+    }
+
+    #[test]
+    fn maintenance_logs_each_run() {
+        let db = Database::new_in_memory().unwrap();
+        let first = run_maintenance(&db, 1_000_000).unwrap();
+        assert_eq!(first.items_pruned + first.notifications_pruned, 0);
+        assert!(!first.vacuumed);
+
+        run_maintenance(&db, 2_000_000).unwrap();
+        let runs = db.get_maintenance_runs(10).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].ran_at, 2_000_000);
+    }
+
+    #[test]
+    fn maintenance_vacuums_only_when_much_is_free() {
+        let add_items = |db: &Database, count: usize, is_read: bool| {
+            for i in 0..count {
+                let pi = plugin_runtime::PluginItem {
+                    id: format!("jira-{}-{}", is_read, i),
+                    source: "jira".to_string(),
+                    source_id: format!("{}-{}", is_read, i),
+                    item_type: "ticket".to_string(),
+                    title: "Ticket".to_string(),
+                    summary: Some("lorem ipsum ".repeat(200)),
+                    url: "https://jira.example.com".to_string(),
+                    author: None,
+                    timestamp: 0,
+                    metadata: serde_json::Value::Null,
+                    tags: Vec::new(),
+                    signals: Vec::new(),
+                };
+                let mut item = NexusItem::from((&pi, 0));
+                item.is_read = is_read;
+                db.upsert_item(&item).unwrap();
+            }
+        };
+        let now = 100 * 86_400;
+
+        // One pruned item among many kept ones frees little.
+        let db = Database::new_in_memory().unwrap();
+        add_items(&db, 100, false);
+        add_items(&db, 1, true);
+        let run = run_maintenance(&db, now).unwrap();
+        assert_eq!(run.items_pruned, 1);
+        assert!(!run.vacuumed);
+
+        let db = Database::new_in_memory().unwrap();
+        add_items(&db, 100, true);
+        let run = run_maintenance(&db, now).unwrap();
+        assert_eq!(run.items_pruned, 100);
+        assert!(run.vacuumed);
+        assert_eq!(db.free_page_ratio().unwrap(), 0.0);
+    }
+}
@@ -0,0 +1,254 @@
+//! Heuristic scoring engine — turns raw plugin signals into priority and urgency.
+//!
+//! Plugins report the signals that apply to each item (e.g. `review_requested`,
+//! `deadline_24h`). The score is the sum of the per-source weights stored in
+//! `heuristic_weights`; the urgency tier is derived from the score using the
+//! thresholds in `app_settings` (`urgency_threshold_medium|high|critical`).
+
+use std::collections::HashMap;
+
+use crate::db::Database;
+use crate::notifications;
+
+/// Score boundaries for each urgency tier. A score below `medium` is "low".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TierThresholds {
+    pub medium: i32,
+    pub high: i32,
+    pub critical: i32,
+}
+
+impl Default for TierThresholds {
+    fn default() -> Self {
+        Self {
+            medium: 3,
+            high: 6,
+            critical: 9,
+        }
+    }
+}
+
+impl TierThresholds {
+    /// Load thresholds from app settings, falling back to defaults for missing
+    /// or unparsable values.
+    pub fn load(db: &Database) -> Self {
+        let defaults = Self::default();
+        let read = |key: &str, fallback: i32| {
+            db.get_app_setting(key)
+                .ok()
+                .flatten()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(fallback)
+        };
+        Self {
+            medium: read("urgency_threshold_medium", defaults.medium),
+            high: read("urgency_threshold_high", defaults.high),
+            critical: read("urgency_threshold_critical", defaults.critical),
+        }
+    }
+
+    pub fn urgency(&self, score: i32) -> &'static str {
+        if score >= self.critical {
+            "critical"
+        } else if score >= self.high {
+            "high"
+        } else if score >= self.medium {
+            "medium"
+        } else {
+            "low"
+        }
+    }
+}
+
+/// Per-signal weights for a single source.
+pub type Weights = HashMap<String, i32>;
+
+pub fn load_weights(db: &Database, source: &str) -> rusqlite::Result<Weights> {
+    Ok(db
+        .get_weights(source)?
+        .into_iter()
+        .map(|w| (w.signal, w.weight))
+        .collect())
+}
+
+/// Sum the weights of the given signals. Unknown signals contribute nothing.
+pub fn score(signals: &[String], weights: &Weights) -> i32 {
+    signals
+        .iter()
+        .map(|s| weights.get(s).copied().unwrap_or(0))
+        .sum()
+}
+
+/// The signal that weighs most in the score, used as the notification
+/// reason; ties go to the one listed first.
+pub fn deciding_signal<'s>(signals: &'s [String], weights: &Weights) -> &'s str {
+    signals
+        .iter()
+        .rev()
+        .max_by_key(|s| weights.get(*s).copied().unwrap_or(0))
+        .map_or("", |s| s.as_str())
+}
+
+/// Parse the JSON array stored in `items.signals`.
+pub fn parse_signals(json: Option<&str>) -> Vec<String> {
+    json.and_then(|j| serde_json::from_str(j).ok())
+        .unwrap_or_default()
+}
+
+/// Recompute `priority` for every stored item of `source` from its persisted
+/// signals and the current weights, and apply the new urgency tiers to the
+/// item's notifications as a poll would. Returns the number of items
+/// re-scored.
+pub fn rescore_source(db: &Database, source: &str, now: i64) -> rusqlite::Result<usize> {
+    let weights = load_weights(db, source)?;
+    let thresholds = TierThresholds::load(db);
+    let rows = db.get_item_signals(source)?;
+    for (id, signals, archived) in &rows {
+        let signals = parse_signals(signals.as_deref());
+        let priority = score(&signals, &weights);
+        db.set_item_priority(id, priority)?;
+        // Items gone upstream are no longer polled, so nothing new about them.
+        if !signals.is_empty() && !archived {
+            let reason = deciding_signal(&signals, &weights);
+            notifications::track_urgency(db, id, reason, thresholds.urgency(priority), now)?;
+        }
+    }
+    Ok(rows.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{HeuristicWeight, ItemFlags, ItemQuery, NexusItem};
+
+    fn signals(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn score_sums_known_signals() {
+        let weights: Weights = [("a".to_string(), 3), ("b".to_string(), 4)].into();
+        assert_eq!(score(&signals(&["a", "b"]), &weights), 7);
+        assert_eq!(score(&signals(&["a", "unknown"]), &weights), 3);
+        assert_eq!(score(&[], &weights), 0);
+    }
+
+    #[test]
+    fn deciding_signal_is_the_heaviest() {
+        let weights: Weights = [("a".to_string(), 3), ("b".to_string(), 4)].into();
+        assert_eq!(deciding_signal(&signals(&["a", "b"]), &weights), "b");
+        assert_eq!(deciding_signal(&signals(&["x", "y"]), &weights), "x");
+        assert_eq!(deciding_signal(&[], &weights), "");
+    }
+
+    #[test]
+    fn default_thresholds_match_plugin_tiers() {
+        let t = TierThresholds::default();
+        assert_eq!(t.urgency(0), "low");
+        assert_eq!(t.urgency(2), "low");
+        assert_eq!(t.urgency(3), "medium");
+        assert_eq!(t.urgency(6), "high");
+        assert_eq!(t.urgency(9), "critical");
+    }
+
+    #[test]
+    fn thresholds_load_from_settings() {
+        let db = Database::new_in_memory().unwrap();
+        db.set_app_setting("urgency_threshold_high", "5").unwrap();
+        db.set_app_setting("urgency_threshold_critical", "garbage").unwrap();
+
+        let t = TierThresholds::load(&db);
+        assert_eq!(t.medium, 3);
+        assert_eq!(t.high, 5);
+        assert_eq!(t.critical, 9);
+    }
+
+    #[test]
+    fn parse_signals_tolerates_bad_json() {
+        assert_eq!(parse_signals(Some(r#"["x","y"]"#)), signals(&["x", "y"]));
+        assert!(parse_signals(Some("not json")).is_empty());
+        assert!(parse_signals(None).is_empty());
+    }
+
+    #[test]
+    fn rescore_applies_updated_weights() {
+        let db = Database::new_in_memory().unwrap();
+        db.seed_weights(
+            "jira",
+            &[
+                ("assigned_to_me".to_string(), 3),
+                ("priority_p1_blocker".to_string(), 4),
+            ],
+        )
+        .unwrap();
+
+        let item = NexusItem {
+            id: "jira-TEST-1".to_string(),
+            source: "jira".to_string(),
+            source_id: "TEST-1".to_string(),
+            item_type: "ticket".to_string(),
+            title: "Fix login bug".to_string(),
+            summary: None,
+            url: "https://jira.example.com/browse/TEST-1".to_string(),
+            author: None,
+            timestamp: 1000,
+            priority: 0,
+            metadata: None,
+            tags: None,
+            is_read: false,
+            created_at: 1000,
+            updated_at: 1000,
+            signals: Some(r#"["assigned_to_me","priority_p1_blocker"]"#.to_string()),
+            archived_at: None,
+            snoozed_until: None,
+            flags: ItemFlags::default(),
+        };
+        db.upsert_item(&item).unwrap();
+
+        assert_eq!(rescore_source(&db, "jira", 2000).unwrap(), 1);
+        assert_eq!(
+            db.get_items(&ItemQuery::default()).unwrap().items[0].priority,
+            7
+        );
+        let active_urgency = || {
+            let active = db.get_active_notifications().unwrap();
+            assert_eq!(active.len(), 1);
+            (active[0].reason.clone(), active[0].urgency.clone())
+        };
+        assert_eq!(
+            active_urgency(),
+            ("priority_p1_blocker".into(), "high".into())
+        );
+
+        db.upsert_weight(&HeuristicWeight {
+            id: "jira-priority_p1_blocker".to_string(),
+            source: "jira".to_string(),
+            signal: "priority_p1_blocker".to_string(),
+            weight: 10,
+        })
+        .unwrap();
+        rescore_source(&db, "jira", 2100).unwrap();
+        assert_eq!(
+            db.get_items(&ItemQuery::default()).unwrap().items[0].priority,
+            13
+        );
+        assert_eq!(
+            active_urgency(),
+            ("priority_p1_blocker".into(), "critical".into())
+        );
+
+        // Dropping a tier downgrades the notification instead of adding one.
+        db.upsert_weight(&HeuristicWeight {
+            id: "jira-priority_p1_blocker".to_string(),
+            source: "jira".to_string(),
+            signal: "priority_p1_blocker".to_string(),
+            weight: 0,
+        })
+        .unwrap();
+        rescore_source(&db, "jira", 2200).unwrap();
+        assert_eq!(
+            active_urgency(),
+            ("priority_p1_blocker".into(), "medium".into())
+        );
+    }
+}
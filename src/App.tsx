@@ -1,14 +1,15 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { RefreshCw, Settings as SettingsIcon, Shield } from "lucide-react";
 import "./styles/theme.css";
 
 import { useItems } from "./hooks/useItems";
 import { useNotifications } from "./hooks/useNotifications";
+import { useSavedViews } from "./hooks/useSavedViews";
 import { useKeyboardNavigation } from "./hooks/useKeyboardNavigation";
 import type { NexusItem } from "./types";
 import type { Notification } from "./types";
-import type { PluginConfig } from "./types";
+import type { PluginConfig, PluginManifest, SavedView } from "./types";
 import { timeAgo } from "./utils/time";
 import { Feed } from "./components/Feed";
 import { DetailPanel } from "./components/DetailPanel";
@@ -32,6 +33,7 @@ const SOURCES: { id: Source; label: string; color: string }[] = [
 export default function App() {
   const [view, setView]               = useState<View>("dashboard");
   const [activeSource, setActiveSource] = useState<Source>("all");
+  const [activeViewId, setActiveViewId] = useState<string | null>(null);
   const [unreadOnly, setUnreadOnly]   = useState(false);
   const [urgentOnly, setUrgentOnly]   = useState(false);
   const [todayOnly, setTodayOnly]     = useState(false);
@@ -59,8 +61,32 @@ export default function App() {
   };
 
   const source = activeSource === "all" ? null : activeSource;
-  const { items, loading, error, refresh, refreshAll, markRead } = useItems(source, unreadOnly);
-  const { notifications, dismiss, dismissAll } = useNotifications();
+  const {
+    items, loading, error, refresh, refreshAll, markRead, snooze, setFlags, hasMore, loadMore,
+  } = useItems(source, unreadOnly, activeViewId);
+  const {
+    notifications,
+    dismiss,
+    dismissAll,
+    snooze: snoozeNotification,
+  } = useNotifications();
+  const { views, unreadCounts, saveView, deleteView } = useSavedViews();
+
+  /** Store the sidebar filters as a new saved view and switch to it. */
+  const saveCurrentView = async (name: string) => {
+    try {
+      const view = await saveView({
+        name,
+        query: { sources: source ? [source] : [], unread_only: unreadOnly },
+        max_age_secs: todayOnly ? 86400 : null,
+        position: views.length,
+      });
+      setActiveViewId(view.id);
+      setSelectedItem(null);
+    } catch (e) {
+      console.error("Failed to save view:", e);
+    }
+  };
 
   const now = Math.floor(Date.now() / 1000);
   const dayAgo = now - 86400;
@@ -71,7 +97,7 @@ export default function App() {
     return true;
   });
 
-  const ALL_PLUGINS = ["jira", "github", "gmail"] as const;
+  const [pluginIds, setPluginIds] = useState<string[]>([]);
 
   useEffect(() => {
     loadAllConfigs();
@@ -88,7 +114,7 @@ export default function App() {
     if (source) {
       refresh(source);
     } else {
-      refreshAll([...ALL_PLUGINS]);
+      refreshAll(pluginIds);
     }
   };
 
@@ -110,8 +136,17 @@ export default function App() {
   }, [view]);
 
   async function loadAllConfigs() {
+    let ids: string[] = [];
+    try {
+      const manifests = await invoke<PluginManifest[]>("list_plugins");
+      ids = manifests.map((m) => m.id);
+      setPluginIds(ids);
+    } catch {
+      // no-op
+    }
+
     const configs: PluginConfig[] = [];
-    for (const pluginId of ALL_PLUGINS) {
+    for (const pluginId of ids) {
       try {
         const config = await invoke<PluginConfig | null>("get_plugin_config", { pluginId });
         if (config) configs.push(config);
@@ -130,13 +165,30 @@ export default function App() {
     }
   };
 
+  // Search hits may be outside the current filters; the detail panel shows them anyway.
+  const handleOpenSearchHit = useCallback((item: NexusItem) => {
+    setSelectedItem(item);
+    setView("dashboard");
+  }, []);
+
+  const showSource = (s: Source) => {
+    setActiveSource(s);
+    setActiveViewId(null);
+    setView("dashboard");
+  };
+
   const commands = [
     { id: "dashboard", label: "Go to Dashboard", action: () => setView("dashboard") },
     { id: "settings", label: "Go to Settings", shortcut: "Ctrl+,", action: () => setView("settings") },
-    { id: "jira", label: "Filter: Jira", action: () => { setActiveSource("jira"); setView("dashboard"); } },
-    { id: "gmail", label: "Filter: Gmail", action: () => { setActiveSource("gmail"); setView("dashboard"); } },
-    { id: "github", label: "Filter: GitHub", action: () => { setActiveSource("github"); setView("dashboard"); } },
-    { id: "all", label: "Filter: All Sources", action: () => { setActiveSource("all"); setView("dashboard"); } },
+    { id: "jira", label: "Filter: Jira", action: () => showSource("jira") },
+    { id: "gmail", label: "Filter: Gmail", action: () => showSource("gmail") },
+    { id: "github", label: "Filter: GitHub", action: () => showSource("github") },
+    { id: "all", label: "Filter: All Sources", action: () => showSource("all") },
+    ...views.map((v) => ({
+      id: `view-${v.id}`,
+      label: `View: ${v.name}`,
+      action: () => { setActiveViewId(v.id); setView("dashboard"); },
+    })),
     { id: "refresh", label: "Refresh All", shortcut: "Ctrl+R", action: handleRefresh },
   ];
 
@@ -158,6 +210,7 @@ export default function App() {
         notifications={notifications}
         onDismiss={dismiss}
         onDismissAll={dismissAll}
+        onSnoozeNotification={snoozeNotification}
         onOpenNotificationItem={handleOpenNotificationItem}
         focusMode={focusMode}
         onToggleFocusMode={toggleFocusMode}
@@ -166,11 +219,24 @@ export default function App() {
       {view === "dashboard" ? (
         <div style={{ display: "flex", flex: 1, overflow: "hidden" }}>
           <Sidebar
-            activeSource={activeSource}
+            activeSource={activeViewId ? null : activeSource}
             onSourceChange={(s) => {
               setActiveSource(s);
+              setActiveViewId(null);
+              setSelectedItem(null);
+            }}
+            views={views}
+            unreadCounts={unreadCounts}
+            activeViewId={activeViewId}
+            onViewChange={(id) => {
+              setActiveViewId(id);
               setSelectedItem(null);
             }}
+            onSaveView={saveCurrentView}
+            onDeleteView={(id) => {
+              if (id === activeViewId) setActiveViewId(null);
+              deleteView(id);
+            }}
             unreadOnly={unreadOnly}
             onUnreadOnlyChange={setUnreadOnly}
             urgentOnly={urgentOnly}
@@ -182,6 +248,11 @@ export default function App() {
           <main
             className="feed-panel"
             style={{ flex: 1, minWidth: 320, overflowY: "auto", display: "flex", flexDirection: "column" }}
+            onScroll={(e) => {
+              // Endless scroll: fetch the next page when nearing the bottom.
+              const el = e.currentTarget;
+              if (hasMore && el.scrollHeight - el.scrollTop - el.clientHeight < 400) loadMore();
+            }}
           >
             <Feed
               items={filteredItems}
@@ -192,14 +263,30 @@ export default function App() {
             />
           </main>
 
-          <DetailPanel item={selectedItem} onMarkRead={markRead} />
+          <DetailPanel
+            item={selectedItem}
+            onMarkRead={markRead}
+            onSnooze={(id, until) => {
+              snooze(id, until);
+              setSelectedItem(null);
+            }}
+            onSetFlags={(id, flags) => {
+              setFlags(id, flags);
+              setSelectedItem((prev) => (prev?.id === id ? { ...prev, flags } : prev));
+            }}
+          />
         </div>
       ) : (
         <Settings onBack={() => setView("dashboard")} />
       )}
 
       <StatusBar activePlugins={activePlugins} lastSyncAt={lastSyncAt} />
-      <CommandPalette open={paletteOpen} onClose={() => setPaletteOpen(false)} commands={commands} />
+      <CommandPalette
+        open={paletteOpen}
+        onClose={() => setPaletteOpen(false)}
+        commands={commands}
+        onOpenItem={handleOpenSearchHit}
+      />
     </div>
   );
 }
@@ -214,6 +301,7 @@ function Titlebar({
   notifications,
   onDismiss,
   onDismissAll,
+  onSnoozeNotification,
   onOpenNotificationItem,
   focusMode,
   onToggleFocusMode,
@@ -225,6 +313,7 @@ function Titlebar({
   notifications: Notification[];
   onDismiss: (id: string) => void;
   onDismissAll: () => void;
+  onSnoozeNotification: (id: string, until: number) => void;
   onOpenNotificationItem: (itemId: string) => void;
   focusMode: boolean;
   onToggleFocusMode: () => void;
@@ -274,6 +363,7 @@ function Titlebar({
           notifications={notifications}
           onDismiss={onDismiss}
           onDismissAll={onDismissAll}
+          onSnooze={onSnoozeNotification}
           onOpenItem={onOpenNotificationItem}
         />
         <IconButton
@@ -309,6 +399,12 @@ function Titlebar({
 function Sidebar({
   activeSource,
   onSourceChange,
+  views,
+  unreadCounts,
+  activeViewId,
+  onViewChange,
+  onSaveView,
+  onDeleteView,
   unreadOnly,
   onUnreadOnlyChange,
   urgentOnly,
@@ -316,8 +412,14 @@ function Sidebar({
   todayOnly,
   onTodayOnlyChange,
 }: {
-  activeSource: Source;
+  activeSource: Source | null;
   onSourceChange: (s: Source) => void;
+  views: SavedView[];
+  unreadCounts: Record<string, number>;
+  activeViewId: string | null;
+  onViewChange: (id: string) => void;
+  onSaveView: (name: string) => void;
+  onDeleteView: (id: string) => void;
   unreadOnly: boolean;
   onUnreadOnlyChange: (v: boolean) => void;
   urgentOnly: boolean;
@@ -325,6 +427,14 @@ function Sidebar({
   todayOnly: boolean;
   onTodayOnlyChange: (v: boolean) => void;
 }) {
+  const [newViewName, setNewViewName] = useState<string | null>(null);
+
+  const submitNewView = () => {
+    const name = newViewName?.trim();
+    if (name) onSaveView(name);
+    setNewViewName(null);
+  };
+
   return (
     <aside
       style={{
@@ -413,6 +523,112 @@ function Sidebar({
           </label>
         ))}
       </div>
+
+      <div className="section-header" style={{ marginTop: "var(--sp-2)" }}>
+        Views
+      </div>
+
+      <div style={{ padding: "var(--sp-1) var(--sp-2)" }}>
+        {views.map((v) => {
+          const isActive = activeViewId === v.id;
+          const unread = unreadCounts[v.id] ?? 0;
+          return (
+            <div key={v.id} style={{ display: "flex", alignItems: "center" }}>
+              <button
+                onClick={() => onViewChange(v.id)}
+                aria-label={`Show view ${v.name}`}
+                style={{
+                  display: "flex",
+                  alignItems: "center",
+                  gap: "var(--sp-2)",
+                  flex: 1,
+                  minWidth: 0,
+                  height: 28,
+                  padding: "0 var(--sp-3)",
+                  borderRadius: "var(--radius-md)",
+                  textAlign: "left",
+                  fontSize: 12,
+                  fontFamily: "var(--font-data)",
+                  color: isActive ? "var(--text-primary)" : "var(--text-secondary)",
+                  background: isActive ? "var(--bg-raised)" : "transparent",
+                  transition:
+                    "background var(--transition-fast), color var(--transition-fast)",
+                }}
+              >
+                <span style={{ flex: 1, overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap" }}>
+                  {v.name}
+                </span>
+                {unread > 0 && (
+                  <span
+                    aria-label={`${unread} unread`}
+                    style={{
+                      fontSize: 10,
+                      color: "var(--text-muted)",
+                      background: "var(--bg-void)",
+                      padding: "0 5px",
+                      borderRadius: "var(--radius-sm)",
+                    }}
+                  >
+                    {unread}
+                  </span>
+                )}
+              </button>
+              {isActive && (
+                <button
+                  onClick={() => onDeleteView(v.id)}
+                  aria-label={`Delete view ${v.name}`}
+                  title="Delete view"
+                  style={{ padding: "0 var(--sp-1)", fontSize: 12, color: "var(--text-muted)" }}
+                >
+                  ×
+                </button>
+              )}
+            </div>
+          );
+        })}
+
+        {newViewName === null ? (
+          <button
+            onClick={() => setNewViewName("")}
+            style={{
+              width: "100%",
+              height: 28,
+              padding: "0 var(--sp-3)",
+              textAlign: "left",
+              fontSize: 12,
+              fontFamily: "var(--font-data)",
+              color: "var(--text-muted)",
+            }}
+          >
+            + Save current filters
+          </button>
+        ) : (
+          <input
+            autoFocus
+            value={newViewName}
+            onChange={(e) => setNewViewName(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === "Enter") submitNewView();
+              else if (e.key === "Escape") setNewViewName(null);
+            }}
+            onBlur={submitNewView}
+            placeholder="View name"
+            aria-label="New view name"
+            style={{
+              width: "100%",
+              height: 28,
+              padding: "0 var(--sp-3)",
+              fontSize: 12,
+              fontFamily: "var(--font-data)",
+              color: "var(--text-primary)",
+              background: "var(--bg-void)",
+              border: "1px solid var(--border-mid)",
+              borderRadius: "var(--radius-md)",
+              outline: "none",
+            }}
+          />
+        )}
+      </div>
     </aside>
   );
 }
@@ -1,5 +1,8 @@
 import { useState, useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { Search } from "lucide-react";
+import { SOURCE_COLOR } from "../constants/design";
+import type { NexusItem, SearchHit } from "../types";
 
 interface Command {
   id: string;
@@ -12,45 +15,89 @@ interface Props {
   open: boolean;
   onClose: () => void;
   commands: Command[];
+  /** When set, the palette also full-text searches items and opens the chosen one. */
+  onOpenItem?: (item: NexusItem) => void;
 }
 
-export function CommandPalette({ open, onClose, commands }: Props) {
+/** Search starts once the query has this many characters. */
+const MIN_SEARCH_LENGTH = 2;
+const SEARCH_DEBOUNCE_MS = 150;
+const SEARCH_LIMIT = 8;
+
+type Entry =
+  | { kind: "command"; command: Command }
+  | { kind: "item"; hit: SearchHit };
+
+export function CommandPalette({ open, onClose, commands, onOpenItem }: Props) {
   const [query, setQuery] = useState("");
+  const [hits, setHits] = useState<SearchHit[]>([]);
   const [selectedIndex, setSelectedIndex] = useState(0);
   const inputRef = useRef<HTMLInputElement>(null);
 
   const filtered = commands.filter((cmd) =>
     cmd.label.toLowerCase().includes(query.toLowerCase())
   );
+  const entries: Entry[] = [
+    ...filtered.map((command): Entry => ({ kind: "command", command })),
+    ...hits.map((hit): Entry => ({ kind: "item", hit })),
+  ];
 
   // Reset on open
   useEffect(() => {
     if (open) {
       setQuery("");
+      setHits([]);
       setSelectedIndex(0);
       setTimeout(() => inputRef.current?.focus(), 50);
     }
   }, [open]);
 
+  // Debounced item search; stale responses are dropped.
+  useEffect(() => {
+    const trimmed = query.trim();
+    if (!open || !onOpenItem || trimmed.length < MIN_SEARCH_LENGTH) {
+      setHits([]);
+      return;
+    }
+    let cancelled = false;
+    const timer = setTimeout(() => {
+      invoke<SearchHit[]>("search_items", { query: trimmed, filters: { limit: SEARCH_LIMIT } })
+        .then((result) => { if (!cancelled) setHits(result); })
+        .catch((e) => {
+          if (!cancelled) setHits([]);
+          console.error("Search failed:", e);
+        });
+    }, SEARCH_DEBOUNCE_MS);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [open, query, onOpenItem]);
+
   // Clamp selection when filter changes
   useEffect(() => {
-    setSelectedIndex((i) => Math.min(i, Math.max(0, filtered.length - 1)));
-  }, [filtered.length]);
+    setSelectedIndex((i) => Math.min(i, Math.max(0, entries.length - 1)));
+  }, [entries.length]);
 
   if (!open) return null;
 
+  const run = (entry: Entry) => {
+    if (entry.kind === "command") entry.command.action();
+    else onOpenItem?.(entry.hit.item);
+    onClose();
+  };
+
   const handleKeyDown = (e: React.KeyboardEvent) => {
     if (e.key === "Escape") {
       onClose();
-    } else if (e.key === "ArrowDown") {
+    } else if (e.key === "ArrowDown" && entries.length > 0) {
       e.preventDefault();
-      setSelectedIndex((i) => (i + 1) % filtered.length);
-    } else if (e.key === "ArrowUp") {
+      setSelectedIndex((i) => (i + 1) % entries.length);
+    } else if (e.key === "ArrowUp" && entries.length > 0) {
       e.preventDefault();
-      setSelectedIndex((i) => (i - 1 + filtered.length) % filtered.length);
-    } else if (e.key === "Enter" && filtered[selectedIndex]) {
-      filtered[selectedIndex].action();
-      onClose();
+      setSelectedIndex((i) => (i - 1 + entries.length) % entries.length);
+    } else if (e.key === "Enter" && entries[selectedIndex]) {
+      run(entries[selectedIndex]);
     }
   };
 
@@ -100,7 +147,7 @@ export function CommandPalette({ open, onClose, commands }: Props) {
             type="text"
             value={query}
             onChange={(e) => setQuery(e.target.value)}
-            placeholder="Type a command..."
+            placeholder={onOpenItem ? "Type a command or search items..." : "Type a command..."}
             style={{
               flex: 1,
               background: "none",
@@ -115,7 +162,7 @@ export function CommandPalette({ open, onClose, commands }: Props) {
 
         {/* Results */}
         <div role="listbox" style={{ overflowY: "auto", flex: 1 }}>
-          {filtered.length === 0 ? (
+          {entries.length === 0 ? (
             <div
               style={{
                 padding: "var(--sp-4)",
@@ -125,53 +172,149 @@ export function CommandPalette({ open, onClose, commands }: Props) {
                 color: "var(--text-muted)",
               }}
             >
-              No matching commands
+              {onOpenItem && query.trim().length >= MIN_SEARCH_LENGTH
+                ? "No matching commands or items"
+                : "No matching commands"}
             </div>
           ) : (
-            filtered.map((cmd, i) => (
-              <div
-                key={cmd.id}
-                role="option"
-                aria-selected={i === selectedIndex}
-                onClick={() => { cmd.action(); onClose(); }}
-                style={{
-                  padding: "var(--sp-2) var(--sp-3)",
-                  display: "flex",
-                  alignItems: "center",
-                  justifyContent: "space-between",
-                  cursor: "pointer",
-                  background: i === selectedIndex ? "var(--bg-raised)" : "transparent",
-                  transition: "background var(--transition-fast)",
-                }}
-              >
-                <span
+            entries.map((entry, i) =>
+              entry.kind === "item" ? (
+                <ItemHit
+                  key={`item-${entry.hit.item.id}`}
+                  hit={entry.hit}
+                  selected={i === selectedIndex}
+                  onClick={() => run(entry)}
+                />
+              ) : (
+                <div
+                  key={entry.command.id}
+                  role="option"
+                  aria-selected={i === selectedIndex}
+                  onClick={() => run(entry)}
                   style={{
-                    fontFamily: "var(--font-data)",
-                    fontSize: 13,
-                    color: "var(--text-primary)",
+                    padding: "var(--sp-2) var(--sp-3)",
+                    display: "flex",
+                    alignItems: "center",
+                    justifyContent: "space-between",
+                    cursor: "pointer",
+                    background: i === selectedIndex ? "var(--bg-raised)" : "transparent",
+                    transition: "background var(--transition-fast)",
                   }}
                 >
-                  {cmd.label}
-                </span>
-                {cmd.shortcut && (
                   <span
                     style={{
                       fontFamily: "var(--font-data)",
-                      fontSize: 10,
-                      color: "var(--text-muted)",
-                      background: "var(--bg-void)",
-                      padding: "2px 6px",
-                      borderRadius: "var(--radius-sm)",
+                      fontSize: 13,
+                      color: "var(--text-primary)",
                     }}
                   >
-                    {cmd.shortcut}
+                    {entry.command.label}
                   </span>
-                )}
-              </div>
-            ))
+                  {entry.command.shortcut && (
+                    <span
+                      style={{
+                        fontFamily: "var(--font-data)",
+                        fontSize: 10,
+                        color: "var(--text-muted)",
+                        background: "var(--bg-void)",
+                        padding: "2px 6px",
+                        borderRadius: "var(--radius-sm)",
+                      }}
+                    >
+                      {entry.command.shortcut}
+                    </span>
+                  )}
+                </div>
+              )
+            )
           )}
         </div>
       </div>
     </div>
   );
 }
+
+/* ── Search results ───────────────────────────────────────── */
+
+const MARK_START = "\u0002";
+const MARK_END = "\u0003";
+
+/** Render `search_items` highlight markers as <mark> without parsing HTML. */
+function Highlighted({ text }: { text: string }) {
+  const parts: React.ReactNode[] = [];
+  let rest = text;
+  let key = 0;
+  while (rest.length > 0) {
+    const start = rest.indexOf(MARK_START);
+    if (start === -1) {
+      parts.push(rest);
+      break;
+    }
+    const end = rest.indexOf(MARK_END, start);
+    if (start > 0) parts.push(rest.slice(0, start));
+    const stop = end === -1 ? rest.length : end;
+    parts.push(
+      <mark key={key++} style={{ background: "none", color: "var(--accent-primary)", fontWeight: 600 }}>
+        {rest.slice(start + 1, stop)}
+      </mark>
+    );
+    rest = rest.slice(stop + 1);
+  }
+  return <>{parts}</>;
+}
+
+function ItemHit({
+  hit,
+  selected,
+  onClick,
+}: {
+  hit: SearchHit;
+  selected: boolean;
+  onClick: () => void;
+}) {
+  const { item } = hit;
+  return (
+    <div
+      role="option"
+      aria-selected={selected}
+      onClick={onClick}
+      style={{
+        padding: "var(--sp-2) var(--sp-3)",
+        display: "flex",
+        flexDirection: "column",
+        gap: 2,
+        cursor: "pointer",
+        borderLeft: `2px solid ${SOURCE_COLOR[item.source] ?? "var(--border-mid)"}`,
+        background: selected ? "var(--bg-raised)" : "transparent",
+        transition: "background var(--transition-fast)",
+      }}
+    >
+      <span
+        style={{
+          fontFamily: "var(--font-data)",
+          fontSize: 13,
+          color: "var(--text-primary)",
+          overflow: "hidden",
+          textOverflow: "ellipsis",
+          whiteSpace: "nowrap",
+        }}
+      >
+        <Highlighted text={hit.title_highlight} />
+      </span>
+      {hit.snippet && hit.snippet !== hit.title_highlight && (
+        <span
+          style={{
+            fontFamily: "var(--font-data)",
+            fontSize: 11,
+            color: "var(--text-muted)",
+            overflow: "hidden",
+            textOverflow: "ellipsis",
+            whiteSpace: "nowrap",
+          }}
+        >
+          <Highlighted text={hit.snippet} />
+        </span>
+      )}
+    </div>
+  );
+}
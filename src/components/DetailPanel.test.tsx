@@ -61,4 +61,27 @@ describe("DetailPanel", () => {
     expect(screen.getByText("Implement caching layer")).toBeInTheDocument();
     expect(screen.getByText("Add Redis caching for API responses")).toBeInTheDocument();
   });
+
+  it("toggles star and saves the note on blur via onSetFlags", () => {
+    const handleSetFlags = vi.fn();
+    const item = makeItem({ id: "item-7" });
+    render(<DetailPanel item={item} onMarkRead={() => {}} onSetFlags={handleSetFlags} />);
+
+    fireEvent.click(screen.getByLabelText("Star"));
+    expect(handleSetFlags).toHaveBeenCalledWith("item-7", { ...item.flags, starred: true });
+
+    const note = screen.getByLabelText("Personal note");
+    fireEvent.change(note, { target: { value: "check with infra" } });
+    fireEvent.blur(note);
+    expect(handleSetFlags).toHaveBeenLastCalledWith("item-7", {
+      ...item.flags,
+      note: "check with infra",
+    });
+  });
+
+  it("hides flag controls without onSetFlags", () => {
+    render(<DetailPanel item={makeItem()} onMarkRead={() => {}} />);
+    expect(screen.queryByLabelText("Star")).not.toBeInTheDocument();
+    expect(screen.queryByLabelText("Personal note")).not.toBeInTheDocument();
+  });
 });
@@ -1,10 +1,11 @@
-import { useState } from "react";
-import { ExternalLink, Check } from "lucide-react";
+import { useState, useEffect } from "react";
+import { ExternalLink, Check, AlarmClock, Star, Pin } from "lucide-react";
 import { openUrl } from "@tauri-apps/plugin-opener";
-import type { NexusItem } from "../types";
+import type { ItemFlags, NexusItem } from "../types";
 import { SOURCE_COLOR, URGENCY_COLOR } from "../constants/design";
 import { priorityToUrgency } from "../utils/urgency";
 import { safeParseJson } from "../utils/json";
+import { snoozeOptions } from "../utils/time";
 
 /** Colorize a due-date string based on how close it is. */
 function dueDateColor(duedate: string): string | undefined {
@@ -61,10 +62,24 @@ function MetaRow({ label, value, color }: { label: string; value: string; color?
 interface Props {
   item: NexusItem | null;
   onMarkRead: (id: string, read: boolean) => void;
+  /** Hide the item until `until` (Unix seconds). Omit to hide the snooze actions. */
+  onSnooze?: (id: string, until: number) => void;
+  /** Replace the item's user flags. Omit to hide star, pin, note and labels. */
+  onSetFlags?: (id: string, flags: ItemFlags) => void;
 }
 
-export function DetailPanel({ item, onMarkRead }: Props) {
+export function DetailPanel({ item, onMarkRead, onSnooze, onSetFlags }: Props) {
   const [openError, setOpenError] = useState<string | null>(null);
+  // Note and labels are edited locally and saved on blur.
+  const [noteDraft, setNoteDraft]     = useState("");
+  const [labelsDraft, setLabelsDraft] = useState("");
+  const savedNote   = item?.flags.note ?? "";
+  const savedLabels = item?.flags.labels.join(", ") ?? "";
+
+  useEffect(() => {
+    setNoteDraft(savedNote);
+    setLabelsDraft(savedLabels);
+  }, [item?.id, savedNote, savedLabels]);
 
   /* ── Empty state ── */
   if (!item) {
@@ -95,6 +110,22 @@ export function DetailPanel({ item, onMarkRead }: Props) {
   const urgency     = meta.priority ? priorityToUrgency(meta.priority) : "low";
   const assignee    = meta.assignee ?? item.author ?? null;
 
+  const updateFlags = (changes: Partial<ItemFlags>) =>
+    onSetFlags?.(item.id, { ...item.flags, ...changes });
+
+  const saveNote = () => {
+    if (noteDraft !== savedNote) {
+      updateFlags({ note: noteDraft || null });
+    }
+  };
+
+  const saveLabels = () => {
+    const labels = labelsDraft.split(",").map((l) => l.trim()).filter(Boolean);
+    if (labels.join(",") !== item.flags.labels.join(",")) {
+      updateFlags({ labels });
+    }
+  };
+
   const handleOpen = async () => {
     try {
       setOpenError(null);
@@ -227,6 +258,39 @@ export function DetailPanel({ item, onMarkRead }: Props) {
         </div>
       )}
 
+      {/* ── Note & labels ── */}
+      {onSetFlags && (
+        <div
+          style={{
+            padding: "var(--sp-3) var(--sp-4)",
+            borderBottom: "1px solid var(--border-dim)",
+            display: "flex",
+            flexDirection: "column",
+            gap: "var(--sp-2)",
+          }}
+        >
+          <div className="section-header">Note</div>
+          <textarea
+            value={noteDraft}
+            onChange={(e) => setNoteDraft(e.target.value)}
+            onBlur={saveNote}
+            placeholder="Only you can see this"
+            aria-label="Personal note"
+            rows={3}
+            style={{ resize: "vertical", fontSize: 12 }}
+          />
+          <input
+            value={labelsDraft}
+            onChange={(e) => setLabelsDraft(e.target.value)}
+            onBlur={saveLabels}
+            onKeyDown={(e) => e.key === "Enter" && e.currentTarget.blur()}
+            placeholder="Labels, comma separated"
+            aria-label="Labels"
+            style={{ fontSize: 12 }}
+          />
+        </div>
+      )}
+
       {/* ── Actions (pinned to bottom) ── */}
       <div
         style={{
@@ -273,6 +337,48 @@ export function DetailPanel({ item, onMarkRead }: Props) {
           <Check size={13} />
           {item.is_read ? "Mark unread" : "Mark read"}
         </button>
+
+        {onSetFlags && (
+          <div style={{ display: "flex", gap: "var(--sp-1)" }}>
+            <button
+              className="btn-ghost"
+              onClick={() => updateFlags({ starred: !item.flags.starred })}
+              aria-label={item.flags.starred ? "Unstar" : "Star"}
+              aria-pressed={item.flags.starred}
+              style={{ flex: 1 }}
+            >
+              <Star size={13} fill={item.flags.starred ? "currentColor" : "none"} />
+              {item.flags.starred ? "Starred" : "Star"}
+            </button>
+            <button
+              className="btn-ghost"
+              onClick={() => updateFlags({ pinned: !item.flags.pinned })}
+              aria-label={item.flags.pinned ? "Unpin" : "Pin"}
+              aria-pressed={item.flags.pinned}
+              style={{ flex: 1 }}
+            >
+              <Pin size={13} fill={item.flags.pinned ? "currentColor" : "none"} />
+              {item.flags.pinned ? "Pinned" : "Pin"}
+            </button>
+          </div>
+        )}
+
+        {onSnooze && (
+          <div style={{ display: "flex", alignItems: "center", gap: "var(--sp-1)" }}>
+            <AlarmClock size={13} style={{ color: "var(--text-muted)", flexShrink: 0 }} />
+            {snoozeOptions().map((option) => (
+              <button
+                key={option.label}
+                className="btn-ghost"
+                onClick={() => onSnooze(item.id, option.until)}
+                aria-label={`Snooze for ${option.label}`}
+                style={{ flex: 1, fontSize: 11, padding: "2px 4px" }}
+              >
+                {option.label}
+              </button>
+            ))}
+          </div>
+        )}
       </div>
     </aside>
   );
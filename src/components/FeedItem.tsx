@@ -1,3 +1,4 @@
+import { Pin, Star } from "lucide-react";
 import type { NexusItem } from "../types";
 import { SOURCE_COLOR, URGENCY_COLOR, URGENCY_BG } from "../constants/design";
 import { priorityToUrgency } from "../utils/urgency";
@@ -88,9 +89,12 @@ export function FeedItem({ item, isSelected, onClick }: Props) {
           )}
         </div>
 
-        {/* Timestamp */}
+        {/* Flags · timestamp */}
         <span
           style={{
+            display: "flex",
+            alignItems: "center",
+            gap: "var(--sp-1)",
             fontFamily: "var(--font-data)",
             fontSize: 10,
             color: "var(--text-muted)",
@@ -98,6 +102,10 @@ export function FeedItem({ item, isSelected, onClick }: Props) {
             marginLeft: "var(--sp-2)",
           }}
         >
+          {item.flags.pinned && <Pin size={10} aria-label="Pinned" />}
+          {item.flags.starred && (
+            <Star size={10} fill="var(--accent-primary)" color="var(--accent-primary)" aria-label="Starred" />
+          )}
           {timeAgo(item.timestamp)}
         </span>
       </div>
@@ -1,17 +1,19 @@
 import { useState, useRef, useEffect } from "react";
-import { Bell, X } from "lucide-react";
+import { AlarmClock, Bell, X } from "lucide-react";
 import type { Notification } from "../types";
 import { URGENCY_COLOR, humanizeReason } from "../constants/design";
-import { timeAgo } from "../utils/time";
+import { snoozeOptions, timeAgo } from "../utils/time";
 
 interface Props {
   notifications: Notification[];
   onDismiss: (id: string) => void;
   onDismissAll: () => void;
   onOpenItem: (itemId: string) => void;
+  /** Hide a notification until `until` (Unix seconds). */
+  onSnooze?: (id: string, until: number) => void;
 }
 
-export function NotificationCenter({ notifications, onDismiss, onDismissAll, onOpenItem }: Props) {
+export function NotificationCenter({ notifications, onDismiss, onDismissAll, onOpenItem, onSnooze }: Props) {
   const [open, setOpen] = useState(false);
   const ref = useRef<HTMLDivElement>(null);
 
@@ -203,6 +205,30 @@ export function NotificationCenter({ notifications, onDismiss, onDismissAll, onO
                   </div>
                 </div>
 
+                {/* Snooze until tomorrow morning */}
+                {onSnooze && (
+                  <button
+                    aria-label="Snooze notification until tomorrow 9:00"
+                    title="Snooze until tomorrow 9:00"
+                    onClick={(e) => { e.stopPropagation(); onSnooze(n.id, snoozeOptions()[1].until); }}
+                    style={{
+                      display: "flex",
+                      alignItems: "center",
+                      justifyContent: "center",
+                      width: 20,
+                      height: 20,
+                      borderRadius: "var(--radius-sm)",
+                      color: "var(--text-muted)",
+                      background: "none",
+                      border: "none",
+                      cursor: "pointer",
+                      flexShrink: 0,
+                    }}
+                  >
+                    <AlarmClock size={12} />
+                  </button>
+                )}
+
                 {/* Dismiss button */}
                 <button
                   aria-label="Dismiss notification"
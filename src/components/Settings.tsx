@@ -1,7 +1,17 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import { Save, ArrowLeft, Shield } from "lucide-react";
-import type { PluginConfig, JiraCredentials, GitHubCredentials, GmailCredentials } from "../types";
+import { Save, ArrowLeft, Shield, Filter, Trash2, Plus } from "lucide-react";
+import type {
+  PluginConfig,
+  PluginErrorKind,
+  PluginManifest,
+  CredentialField,
+  NotificationRule,
+  RuleAction,
+  QuietSchedule,
+  QuietWindow,
+  Weekday,
+} from "../types";
 import { timeAgo } from "../utils/time";
 
 /* ── Sub-components ──────────────────────────────────────── */
@@ -76,6 +86,7 @@ function PluginCard({
   label,
   lastPoll,
   lastError,
+  lastErrorKind,
   children,
   onSave,
   saving,
@@ -85,6 +96,7 @@ function PluginCard({
   label: string;
   lastPoll: number | null;
   lastError: string | null;
+  lastErrorKind: PluginErrorKind | null;
   children: React.ReactNode;
   onSave: () => void;
   saving: boolean;
@@ -171,6 +183,19 @@ function PluginCard({
           >
             Last error: {lastError}
           </p>
+          {lastErrorKind === "auth" && (
+            <p
+              style={{
+                fontFamily: "var(--font-data)",
+                fontSize: 11,
+                color: "var(--urgency-high)",
+                lineHeight: 1.5,
+                marginTop: "var(--sp-1)",
+              }}
+            >
+              The credentials were rejected and syncing is paused. Update them below and save to resume.
+            </p>
+          )}
         </div>
       )}
 
@@ -210,136 +235,90 @@ function PluginCard({
   );
 }
 
-/* ── Jira section ────────────────────────────────────────── */
+/* ── Plugin section ──────────────────────────────────────── */
 
-function JiraSection() {
-  const [baseUrl, setBaseUrl]       = useState("");
-  const [email, setEmail]           = useState("");
-  const [apiToken, setApiToken]     = useState("");
-  const [pollInterval, setPollInterval] = useState(600);
-  const [saving, setSaving]         = useState(false);
-  const [message, setMessage]       = useState<{ text: string; ok: boolean } | null>(null);
-  const [lastPoll, setLastPoll]     = useState<number | null>(null);
-  const [lastError, setLastError]   = useState<string | null>(null);
+type FormValues = Record<string, string>;
 
-  useEffect(() => { loadConfig(); }, []);
-
-  async function loadConfig() {
-    try {
-      const config = await invoke<PluginConfig | null>("get_plugin_config", { pluginId: "jira" });
-      if (config?.credentials) {
-        const creds: JiraCredentials = JSON.parse(config.credentials);
-        setBaseUrl(creds.baseUrl ?? "");
-        setEmail(creds.email ?? "");
-        setApiToken(creds.apiToken ?? "");
-      }
-      if (config) {
-        setPollInterval(config.poll_interval_secs);
-        setLastPoll(config.last_poll_at);
-        setLastError(config.last_error);
-      }
-    } catch (e) {
-      console.error("Failed to load Jira config:", e);
-    }
+/** Credentials JSON → form strings (`list` fields become comma-separated). */
+function toFormValues(fields: CredentialField[], credentials: string | null): FormValues {
+  const parsed: Record<string, unknown> = credentials ? JSON.parse(credentials) : {};
+  const values: FormValues = {};
+  for (const field of fields) {
+    const raw = parsed[field.key];
+    values[field.key] = Array.isArray(raw) ? raw.join(", ") : typeof raw === "string" ? raw : "";
   }
+  return values;
+}
 
-  async function saveConfig() {
-    setSaving(true);
-    setMessage(null);
-    try {
-      const credentials: JiraCredentials = { baseUrl: baseUrl.trim(), email: email.trim(), apiToken };
-      await invoke("save_plugin_config", {
-        config: {
-          plugin_id: "jira",
-          is_enabled: true,
-          credentials: JSON.stringify(credentials),
-          poll_interval_secs: pollInterval,
-          last_poll_at: lastPoll,
-          last_error: null,
-          error_count: 0,
-          settings: null,
-        } satisfies PluginConfig,
-      });
-      setLastError(null);
-      setMessage({ text: "Credentials saved. Syncing now...", ok: true });
-    } catch {
-      setMessage({ text: "Couldn't save settings. Please check your connection and try again.", ok: false });
-    } finally {
-      setSaving(false);
-    }
+/** Form strings → credentials object (`list` fields become arrays). */
+function toCredentials(fields: CredentialField[], values: FormValues): Record<string, unknown> {
+  const credentials: Record<string, unknown> = {};
+  for (const field of fields) {
+    const value = values[field.key] ?? "";
+    credentials[field.key] =
+      field.type === "list"
+        ? value.split(",").map((s) => s.trim()).filter(Boolean)
+        : field.type === "password"
+          ? value
+          : value.trim();
   }
-
-  return (
-    <PluginCard
-      accentVar="--source-jira"
-      label="Jira Plugin"
-      lastPoll={lastPoll}
-      lastError={lastError}
-      onSave={saveConfig}
-      saving={saving}
-      message={message}
-    >
-      <FormField label="Base URL" type="text" value={baseUrl} onChange={setBaseUrl} placeholder="https://company.atlassian.net" />
-      <FormField label="Email" type="email" value={email} onChange={setEmail} placeholder="you@company.com" />
-      <FormField label="API Token" type="password" value={apiToken} onChange={setApiToken} placeholder="••••••••••••••••••••" />
-      <FormField
-        label="Poll Interval (seconds)"
-        type="number"
-        value={String(pollInterval)}
-        onChange={(v) => setPollInterval(Math.max(60, Number(v)))}
-        placeholder="600"
-      />
-    </PluginCard>
-  );
+  return credentials;
 }
 
-/* ── GitHub section ──────────────────────────────────────── */
-
-function GitHubSection() {
-  const [token, setToken]           = useState("");
-  const [pollInterval, setPollInterval] = useState(600);
+function PluginSection({ manifest }: { manifest: PluginManifest }) {
+  const [values, setValues]         = useState<FormValues>({});
+  const [pollInterval, setPollInterval] = useState(manifest.defaultPollIntervalSecs);
   const [saving, setSaving]         = useState(false);
   const [message, setMessage]       = useState<{ text: string; ok: boolean } | null>(null);
   const [lastPoll, setLastPoll]     = useState<number | null>(null);
   const [lastError, setLastError]   = useState<string | null>(null);
+  const [lastErrorKind, setLastErrorKind] = useState<PluginErrorKind | null>(null);
 
-  useEffect(() => { loadConfig(); }, []);
+  useEffect(() => { loadConfig(); }, [manifest.id]);
 
   async function loadConfig() {
     try {
-      const config = await invoke<PluginConfig | null>("get_plugin_config", { pluginId: "github" });
-      if (config?.credentials) {
-        const creds: GitHubCredentials = JSON.parse(config.credentials);
-        setToken(creds.token ?? "");
-      }
+      const config = await invoke<PluginConfig | null>("get_plugin_config", { pluginId: manifest.id });
+      setValues(toFormValues(manifest.credentials, config?.credentials ?? null));
       if (config) {
         setPollInterval(config.poll_interval_secs);
         setLastPoll(config.last_poll_at);
         setLastError(config.last_error);
+        setLastErrorKind(config.last_error_kind);
       }
     } catch (e) {
-      console.error("Failed to load GitHub config:", e);
+      console.error(`Failed to load ${manifest.name} config:`, e);
     }
   }
 
   async function saveConfig() {
+    const missing = manifest.credentials.find((f) => !f.optional && !(values[f.key] ?? "").trim());
+    if (missing) {
+      setMessage({ text: `${missing.label} is required.`, ok: false });
+      return;
+    }
+
     setSaving(true);
     setMessage(null);
     try {
-      const credentials: GitHubCredentials = { token: token.trim() };
       await invoke("save_plugin_config", {
         config: {
-          plugin_id: "github",
+          plugin_id: manifest.id,
           is_enabled: true,
-          credentials: JSON.stringify(credentials),
+          credentials: JSON.stringify(toCredentials(manifest.credentials, values)),
           poll_interval_secs: pollInterval,
           last_poll_at: lastPoll,
           last_error: null,
           error_count: 0,
           settings: null,
+          last_error_at: null,
+          auto_disabled_at: null,
+          last_error_kind: null,
+          retry_at: null,
         } satisfies PluginConfig,
       });
       setLastError(null);
+      setLastErrorKind(null);
       setMessage({ text: "Credentials saved. Syncing now...", ok: true });
     } catch {
       setMessage({ text: "Couldn't save settings. Please check your connection and try again.", ok: false });
@@ -350,167 +329,177 @@ function GitHubSection() {
 
   return (
     <PluginCard
-      accentVar="--source-github"
-      label="GitHub Plugin"
+      accentVar={`--source-${manifest.id}, var(--accent-primary)`}
+      label={`${manifest.name} Plugin`}
       lastPoll={lastPoll}
       lastError={lastError}
+      lastErrorKind={lastErrorKind}
       onSave={saveConfig}
       saving={saving}
       message={message}
     >
-      <FormField
-        label="Personal Access Token"
-        type="password"
-        value={token}
-        onChange={setToken}
-        placeholder="ghp_xxxxxxxxxxxxxxxxxxxx"
-        hint="Requires scopes: repo, read:user, notifications"
-      />
+      {manifest.credentials.map((field) => (
+        <FormField
+          key={field.key}
+          label={field.label}
+          type={field.type === "list" ? "text" : field.type}
+          value={values[field.key] ?? ""}
+          onChange={(v) => setValues((prev) => ({ ...prev, [field.key]: v }))}
+          placeholder={field.placeholder ?? ""}
+          hint={field.hint ?? undefined}
+        />
+      ))}
       <FormField
         label="Poll Interval (seconds)"
         type="number"
         value={String(pollInterval)}
         onChange={(v) => setPollInterval(Math.max(60, Number(v)))}
-        placeholder="600"
+        placeholder={String(manifest.defaultPollIntervalSecs)}
       />
     </PluginCard>
   );
 }
 
-/* ── Gmail section ───────────────────────────────────────── */
-
-function GmailSection() {
-  const [clientId, setClientId]         = useState("");
-  const [clientSecret, setClientSecret] = useState("");
-  const [refreshToken, setRefreshToken] = useState("");
-  const [vipSenders, setVipSenders]     = useState("");
-  const [pollInterval, setPollInterval] = useState(600);
-  const [saving, setSaving]             = useState(false);
-  const [message, setMessage]           = useState<{ text: string; ok: boolean } | null>(null);
-  const [lastPoll, setLastPoll]         = useState<number | null>(null);
-  const [lastError, setLastError]       = useState<string | null>(null);
-
-  useEffect(() => { loadConfig(); }, []);
+/* ── Preferences section ─────────────────────────────────── */
 
-  async function loadConfig() {
-    try {
-      const config = await invoke<PluginConfig | null>("get_plugin_config", { pluginId: "gmail" });
-      if (config?.credentials) {
-        const creds: GmailCredentials = JSON.parse(config.credentials);
-        setClientId(creds.clientId ?? "");
-        setClientSecret(creds.clientSecret ?? "");
-        setRefreshToken(creds.refreshToken ?? "");
-        setVipSenders(
-          Array.isArray(creds.vipSenders)
-            ? creds.vipSenders.join(", ")
-            : (creds.vipSenders ?? ""),
-        );
-      }
-      if (config) {
-        setPollInterval(config.poll_interval_secs);
-        setLastPoll(config.last_poll_at);
-        setLastError(config.last_error);
-      }
-    } catch (e) {
-      console.error("Failed to load Gmail config:", e);
-    }
+const WEEKDAYS: Weekday[] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+const prefInputStyle: React.CSSProperties = {
+  flex: 1,
+  minWidth: 0,
+  padding: "7px 10px",
+  background: "var(--bg-base)",
+  border: "1px solid var(--border-mid)",
+  borderRadius: "var(--radius-md)",
+  color: "var(--text-primary)",
+  fontFamily: "var(--font-data)",
+  fontSize: 12,
+  outline: "none",
+};
+
+function QuietHoursFields({
+  schedule,
+  onChange,
+}: {
+  schedule: QuietSchedule;
+  onChange: (schedule: QuietSchedule) => void;
+}) {
+  function updateWindow(index: number, patch: Partial<QuietWindow>) {
+    onChange({
+      ...schedule,
+      windows: schedule.windows.map((w, i) => (i === index ? { ...w, ...patch } : w)),
+    });
   }
 
-  async function saveConfig() {
-    setSaving(true);
-    setMessage(null);
-    try {
-      // Store vipSenders as a JSON array, parsed from the comma-separated input
-      const vipArray = vipSenders
-        .split(",")
-        .map((s) => s.trim().toLowerCase())
-        .filter(Boolean);
-
-      const credentials = {
-        clientId: clientId.trim(),
-        clientSecret: clientSecret.trim(),
-        refreshToken: refreshToken.trim(),
-        vipSenders: vipArray,
-      };
-      await invoke("save_plugin_config", {
-        config: {
-          plugin_id: "gmail",
-          is_enabled: true,
-          credentials: JSON.stringify(credentials),
-          poll_interval_secs: pollInterval,
-          last_poll_at: lastPoll,
-          last_error: null,
-          error_count: 0,
-          settings: null,
-        } satisfies PluginConfig,
-      });
-      setLastError(null);
-      setMessage({ text: "Credentials saved. Syncing now...", ok: true });
-    } catch {
-      setMessage({ text: "Couldn't save settings. Please check your connection and try again.", ok: false });
-    } finally {
-      setSaving(false);
-    }
+  function toggleDay(index: number, day: Weekday) {
+    const days = schedule.windows[index].days;
+    updateWindow(index, {
+      days: days.includes(day) ? days.filter((d) => d !== day) : WEEKDAYS.filter((d) => d === day || days.includes(d)),
+    });
   }
 
   return (
-    <PluginCard
-      accentVar="--source-gmail"
-      label="Gmail Plugin"
-      lastPoll={lastPoll}
-      lastError={lastError}
-      onSave={saveConfig}
-      saving={saving}
-      message={message}
-    >
-      <FormField
-        label="Client ID"
-        type="text"
-        value={clientId}
-        onChange={setClientId}
-        placeholder="xxxx.apps.googleusercontent.com"
-        hint="From Google Cloud Console → Credentials → OAuth 2.0 Client ID"
-      />
-      <FormField
-        label="Client Secret"
-        type="password"
-        value={clientSecret}
-        onChange={setClientSecret}
-        placeholder="••••••••••••••••••••"
-      />
-      <FormField
-        label="Refresh Token"
-        type="password"
-        value={refreshToken}
-        onChange={setRefreshToken}
-        placeholder="1//xxxx..."
-        hint="Generate at developers.google.com/oauthplayground (scope: gmail.readonly)"
-      />
-      <FormField
-        label="VIP Senders (comma-separated emails)"
-        type="text"
-        value={vipSenders}
-        onChange={setVipSenders}
-        placeholder="boss@company.com, cto@company.com"
-        hint="Emails from these senders get +3 urgency weight"
-      />
-      <FormField
-        label="Poll Interval (seconds)"
-        type="number"
-        value={String(pollInterval)}
-        onChange={(v) => setPollInterval(Math.max(60, Number(v)))}
-        placeholder="600"
+    <div style={{ display: "flex", flexDirection: "column", gap: "var(--sp-2)" }}>
+      <input
+        placeholder={`Time zone (${Intl.DateTimeFormat().resolvedOptions().timeZone})`}
+        value={schedule.timezone ?? ""}
+        onChange={(e) => onChange({ ...schedule, timezone: e.target.value.trim() || null })}
+        style={prefInputStyle}
       />
-    </PluginCard>
+
+      {schedule.windows.map((window, i) => (
+        <div key={i} style={{ display: "flex", flexDirection: "column", gap: 4 }}>
+          <div style={{ display: "flex", alignItems: "center", gap: "var(--sp-2)" }}>
+            <input
+              type="time"
+              value={window.start}
+              onChange={(e) => updateWindow(i, { start: e.target.value })}
+              style={prefInputStyle}
+            />
+            <span style={{ fontFamily: "var(--font-data)", fontSize: 11, color: "var(--text-muted)" }}>to</span>
+            <input
+              type="time"
+              value={window.end}
+              onChange={(e) => updateWindow(i, { end: e.target.value })}
+              style={prefInputStyle}
+            />
+            <button
+              className="btn-ghost"
+              aria-label="Remove window"
+              onClick={() => onChange({ ...schedule, windows: schedule.windows.filter((_, j) => j !== i) })}
+            >
+              <Trash2 size={12} />
+            </button>
+          </div>
+          <div style={{ display: "flex", gap: 4 }}>
+            {WEEKDAYS.map((day) => (
+              <label key={day} style={{ fontFamily: "var(--font-data)", fontSize: 10, color: "var(--text-muted)" }}>
+                <input type="checkbox" checked={window.days.includes(day)} onChange={() => toggleDay(i, day)} />
+                {day}
+              </label>
+            ))}
+          </div>
+        </div>
+      ))}
+
+      {schedule.days_off.map((range, i) => (
+        <div key={i} style={{ display: "flex", alignItems: "center", gap: "var(--sp-2)" }}>
+          <span style={{ fontFamily: "var(--font-data)", fontSize: 11, color: "var(--text-muted)" }}>Off</span>
+          <input
+            type="date"
+            value={range.start}
+            onChange={(e) => onChange({
+              ...schedule,
+              days_off: schedule.days_off.map((r, j) => (j === i ? { ...r, start: e.target.value } : r)),
+            })}
+            style={prefInputStyle}
+          />
+          <span style={{ fontFamily: "var(--font-data)", fontSize: 11, color: "var(--text-muted)" }}>to</span>
+          <input
+            type="date"
+            value={range.end}
+            onChange={(e) => onChange({
+              ...schedule,
+              days_off: schedule.days_off.map((r, j) => (j === i ? { ...r, end: e.target.value } : r)),
+            })}
+            style={prefInputStyle}
+          />
+          <button
+            className="btn-ghost"
+            aria-label="Remove days off"
+            onClick={() => onChange({ ...schedule, days_off: schedule.days_off.filter((_, j) => j !== i) })}
+          >
+            <Trash2 size={12} />
+          </button>
+        </div>
+      ))}
+
+      <div style={{ display: "flex", gap: "var(--sp-2)" }}>
+        <button
+          className="btn-ghost"
+          onClick={() => onChange({ ...schedule, windows: [...schedule.windows, { days: [], start: "22:00", end: "07:00" }] })}
+        >
+          <Plus size={12} />
+          Window
+        </button>
+        <button
+          className="btn-ghost"
+          onClick={() => {
+            const today = new Date().toISOString().slice(0, 10);
+            onChange({ ...schedule, days_off: [...schedule.days_off, { start: today, end: today }] });
+          }}
+        >
+          <Plus size={12} />
+          Days off
+        </button>
+      </div>
+    </div>
   );
 }
 
-/* ── Preferences section ─────────────────────────────────── */
-
 function PreferencesSection() {
   const [focusThreshold, setFocusThreshold] = useState("high");
-  const [quietStart, setQuietStart] = useState("");
-  const [quietEnd, setQuietEnd] = useState("");
+  const [schedule, setSchedule] = useState<QuietSchedule>({ timezone: null, windows: [], days_off: [] });
   const [saving, setSaving] = useState(false);
   const [message, setMessage] = useState<{ text: string; ok: boolean } | null>(null);
 
@@ -523,11 +512,7 @@ function PreferencesSection() {
       const threshold = await invoke<string | null>("get_app_setting", { key: "focus_mode_threshold" });
       if (threshold) setFocusThreshold(threshold);
 
-      const start = await invoke<string | null>("get_app_setting", { key: "quiet_hours_start" });
-      if (start) setQuietStart(start);
-
-      const end = await invoke<string | null>("get_app_setting", { key: "quiet_hours_end" });
-      if (end) setQuietEnd(end);
+      setSchedule(await invoke<QuietSchedule>("get_quiet_hours"));
     } catch (e) {
       console.error("Failed to load preferences:", e);
     }
@@ -538,15 +523,10 @@ function PreferencesSection() {
     setMessage(null);
     try {
       await invoke("set_app_setting", { key: "focus_mode_threshold", value: focusThreshold });
-      if (quietStart) {
-        await invoke("set_app_setting", { key: "quiet_hours_start", value: quietStart });
-      }
-      if (quietEnd) {
-        await invoke("set_app_setting", { key: "quiet_hours_end", value: quietEnd });
-      }
+      await invoke("set_quiet_hours", { schedule });
       setMessage({ text: "Preferences saved.", ok: true });
-    } catch {
-      setMessage({ text: "Couldn't save settings. Please check your connection and try again.", ok: false });
+    } catch (e) {
+      setMessage({ text: typeof e === "string" ? e : "Couldn't save settings. Please try again.", ok: false });
     } finally {
       setSaving(false);
     }
@@ -653,43 +633,10 @@ function PreferencesSection() {
           >
             Quiet Hours
           </label>
-          <div style={{ display: "flex", alignItems: "center", gap: "var(--sp-2)" }}>
-            <input
-              type="time"
-              value={quietStart}
-              onChange={(e) => setQuietStart(e.target.value)}
-              style={{
-                flex: 1,
-                padding: "7px 10px",
-                background: "var(--bg-base)",
-                border: "1px solid var(--border-mid)",
-                borderRadius: "var(--radius-md)",
-                color: "var(--text-primary)",
-                fontFamily: "var(--font-data)",
-                fontSize: 12,
-                outline: "none",
-              }}
-            />
-            <span style={{ fontFamily: "var(--font-data)", fontSize: 11, color: "var(--text-muted)" }}>to</span>
-            <input
-              type="time"
-              value={quietEnd}
-              onChange={(e) => setQuietEnd(e.target.value)}
-              style={{
-                flex: 1,
-                padding: "7px 10px",
-                background: "var(--bg-base)",
-                border: "1px solid var(--border-mid)",
-                borderRadius: "var(--radius-md)",
-                color: "var(--text-primary)",
-                fontFamily: "var(--font-data)",
-                fontSize: 12,
-                outline: "none",
-              }}
-            />
-          </div>
+          <QuietHoursFields schedule={schedule} onChange={setSchedule} />
           <p style={{ marginTop: 4, fontSize: 10, color: "var(--text-muted)", fontFamily: "var(--font-data)" }}>
-            Suppress ALL native notifications during this time window. Leave empty to disable.
+            Suppress ALL native notifications during these windows and days off. A window ending before it starts
+            runs past midnight, and one with the same start and end lasts all day; no days selected means every day.
           </p>
         </div>
       </div>
@@ -729,6 +676,223 @@ function PreferencesSection() {
   );
 }
 
+/* ── Notification rules section ──────────────────────────── */
+
+const RULE_ACTIONS: { value: RuleAction["type"]; label: string }[] = [
+  { value: "always_notify", label: "Always notify" },
+  { value: "never_notify", label: "Never notify" },
+  { value: "override_urgency", label: "Set urgency" },
+  { value: "bypass_quiet_hours", label: "Bypass quiet hours" },
+];
+
+function splitList(value: string): string[] {
+  return value.split(",").map((v) => v.trim()).filter(Boolean);
+}
+
+function describeRule(rule: NotificationRule): string {
+  const { sources = [], signals = [], authors = [], tags = [] } = rule.conditions;
+  const parts = [
+    sources.length ? `from ${sources.join("/")}` : "",
+    signals.length ? `on ${signals.join("/")}` : "",
+    authors.length ? `by ${authors.join("/")}` : "",
+    tags.length ? `tagged ${tags.join("/")}` : "",
+  ].filter(Boolean);
+  const action = rule.action.type === "override_urgency"
+    ? `urgency → ${rule.action.urgency}`
+    : RULE_ACTIONS.find((a) => a.value === rule.action.type)?.label ?? rule.action.type;
+  return `${parts.length ? parts.join(", ") : "Everything"}: ${action}`;
+}
+
+function NotificationRulesSection() {
+  const [rules, setRules] = useState<NotificationRule[]>([]);
+  const [name, setName] = useState("");
+  const [sources, setSources] = useState("");
+  const [signals, setSignals] = useState("");
+  const [actionType, setActionType] = useState<RuleAction["type"]>("always_notify");
+  const [urgency, setUrgency] = useState<"low" | "medium" | "high" | "critical">("high");
+  const [message, setMessage] = useState<{ text: string; ok: boolean } | null>(null);
+
+  useEffect(() => {
+    loadRules();
+  }, []);
+
+  async function loadRules() {
+    try {
+      setRules(await invoke<NotificationRule[]>("get_notification_rules"));
+    } catch (e) {
+      console.error("Failed to load notification rules:", e);
+    }
+  }
+
+  async function saveRule(rule: Partial<NotificationRule>) {
+    setMessage(null);
+    try {
+      await invoke<NotificationRule>("save_notification_rule", { rule });
+      await loadRules();
+      return true;
+    } catch (e) {
+      setMessage({ text: String(e), ok: false });
+      return false;
+    }
+  }
+
+  async function addRule() {
+    const action: RuleAction = actionType === "override_urgency"
+      ? { type: actionType, urgency }
+      : { type: actionType };
+    const saved = await saveRule({
+      name,
+      position: rules.length,
+      conditions: { sources: splitList(sources), signals: splitList(signals) },
+      action,
+    });
+    if (saved) {
+      setName("");
+      setSources("");
+      setSignals("");
+    }
+  }
+
+  async function deleteRule(ruleId: string) {
+    setRules((prev) => prev.filter((r) => r.id !== ruleId));
+    try {
+      await invoke("delete_notification_rule", { ruleId });
+    } catch (e) {
+      console.error("Failed to delete notification rule:", e);
+      loadRules(); // rollback
+    }
+  }
+
+  return (
+    <div
+      style={{
+        maxWidth: 480,
+        background: "var(--bg-surface)",
+        border: "1px solid var(--border-dim)",
+        borderRadius: "var(--radius-md)",
+        overflow: "hidden",
+        marginBottom: "var(--sp-4)",
+      }}
+    >
+      {/* Header */}
+      <div
+        style={{
+          padding: "var(--sp-3) var(--sp-4)",
+          borderBottom: "1px solid var(--border-dim)",
+          display: "flex",
+          alignItems: "center",
+          gap: "var(--sp-2)",
+        }}
+      >
+        <Filter size={14} style={{ color: "var(--accent-primary)" }} />
+        <span
+          style={{
+            fontFamily: "var(--font-display)",
+            fontSize: 13,
+            fontWeight: 700,
+            color: "var(--accent-primary)",
+          }}
+        >
+          Notification Rules
+        </span>
+      </div>
+
+      {/* Rules, first match wins */}
+      <div style={{ padding: "var(--sp-4)", display: "flex", flexDirection: "column", gap: "var(--sp-2)" }}>
+        {rules.length === 0 && (
+          <p style={{ fontSize: 11, color: "var(--text-muted)", fontFamily: "var(--font-data)" }}>
+            No rules yet. Rules are checked in order; the first match decides.
+          </p>
+        )}
+        {rules.map((rule) => (
+          <div key={rule.id} style={{ display: "flex", alignItems: "center", gap: "var(--sp-2)" }}>
+            <input
+              type="checkbox"
+              checked={rule.is_enabled}
+              aria-label={`Enable ${rule.name}`}
+              onChange={(e) => saveRule({ ...rule, is_enabled: e.target.checked })}
+            />
+            <div style={{ flex: 1, minWidth: 0 }}>
+              <div style={{ fontSize: 12, color: "var(--text-primary)" }}>{rule.name}</div>
+              <div style={{ fontSize: 10, color: "var(--text-muted)", fontFamily: "var(--font-data)" }}>
+                {describeRule(rule)}
+              </div>
+            </div>
+            <button className="btn-ghost" onClick={() => deleteRule(rule.id)} aria-label={`Delete ${rule.name}`}>
+              <Trash2 size={12} />
+            </button>
+          </div>
+        ))}
+      </div>
+
+      {/* Add a rule */}
+      <div
+        style={{
+          padding: "var(--sp-3) var(--sp-4)",
+          borderTop: "1px solid var(--border-dim)",
+          display: "flex",
+          flexDirection: "column",
+          gap: "var(--sp-2)",
+        }}
+      >
+        <input placeholder="Rule name" value={name} onChange={(e) => setName(e.target.value)} style={prefInputStyle} />
+        <div style={{ display: "flex", gap: "var(--sp-2)" }}>
+          <input
+            placeholder="Sources (github, jira)"
+            value={sources}
+            onChange={(e) => setSources(e.target.value)}
+            style={prefInputStyle}
+          />
+          <input
+            placeholder="Signals (ci_failed)"
+            value={signals}
+            onChange={(e) => setSignals(e.target.value)}
+            style={prefInputStyle}
+          />
+        </div>
+        <div style={{ display: "flex", alignItems: "center", gap: "var(--sp-2)" }}>
+          <select
+            value={actionType}
+            onChange={(e) => setActionType(e.target.value as RuleAction["type"])}
+            style={{ ...prefInputStyle, cursor: "pointer" }}
+          >
+            {RULE_ACTIONS.map((a) => (
+              <option key={a.value} value={a.value}>{a.label}</option>
+            ))}
+          </select>
+          {actionType === "override_urgency" && (
+            <select
+              value={urgency}
+              onChange={(e) => setUrgency(e.target.value as typeof urgency)}
+              style={{ ...prefInputStyle, cursor: "pointer" }}
+            >
+              <option value="low">Low</option>
+              <option value="medium">Medium</option>
+              <option value="high">High</option>
+              <option value="critical">Critical</option>
+            </select>
+          )}
+          <button className="btn-primary" onClick={addRule} disabled={!name.trim()}>
+            <Plus size={12} />
+            Add
+          </button>
+        </div>
+        {message && (
+          <p
+            style={{
+              fontFamily: "var(--font-data)",
+              fontSize: 11,
+              color: message.ok ? "var(--accent-primary)" : "var(--urgency-high)",
+            }}
+          >
+            {message.text}
+          </p>
+        )}
+      </div>
+    </div>
+  );
+}
+
 /* ── Settings ────────────────────────────────────────────── */
 
 interface SettingsProps {
@@ -736,6 +900,14 @@ interface SettingsProps {
 }
 
 export function Settings({ onBack }: SettingsProps) {
+  const [plugins, setPlugins] = useState<PluginManifest[]>([]);
+
+  useEffect(() => {
+    invoke<PluginManifest[]>("list_plugins")
+      .then(setPlugins)
+      .catch((e) => console.error("Failed to list plugins:", e));
+  }, []);
+
   return (
     <div
       style={{
@@ -774,10 +946,11 @@ export function Settings({ onBack }: SettingsProps) {
         </h2>
       </div>
 
-      <JiraSection />
-      <GitHubSection />
-      <GmailSection />
+      {plugins.map((manifest) => (
+        <PluginSection key={manifest.id} manifest={manifest} />
+      ))}
       <PreferencesSection />
+      <NotificationRulesSection />
     </div>
   );
 }
@@ -1,9 +1,9 @@
 import { renderHook, act, waitFor } from "@testing-library/react";
 import { vi } from "vitest";
-import { useItems } from "./useItems";
+import { PAGE_SIZE, useItems } from "./useItems";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { makeItem } from "../test/fixtures";
+import { makeItem, makePage } from "../test/fixtures";
 
 describe("useItems", () => {
   beforeEach(() => {
@@ -14,7 +14,7 @@ describe("useItems", () => {
 
   it("fetches items on mount via invoke('get_items')", async () => {
     const items = [makeItem({ id: "a" })];
-    vi.mocked(invoke).mockResolvedValue(items);
+    vi.mocked(invoke).mockResolvedValue(makePage(items));
 
     const { result } = renderHook(() => useItems(null, false));
 
@@ -24,9 +24,47 @@ describe("useItems", () => {
     expect(result.current.loading).toBe(false);
     expect(result.current.error).toBeNull();
     expect(vi.mocked(invoke)).toHaveBeenCalledWith("get_items", {
-      source: null,
-      unreadOnly: false,
-      limit: 100,
+      query: { sources: [], unread_only: false, limit: PAGE_SIZE },
+    });
+    expect(result.current.hasMore).toBe(false);
+  });
+
+  it("loadMore() appends the next page using the cursor", async () => {
+    const first = makeItem({ id: "a" });
+    const second = makeItem({ id: "b" });
+    vi.mocked(invoke).mockResolvedValueOnce(makePage([first], "timestamp:100:a"));
+
+    const { result } = renderHook(() => useItems("jira", true));
+
+    await waitFor(() => {
+      expect(result.current.hasMore).toBe(true);
+    });
+
+    vi.mocked(invoke).mockResolvedValueOnce(makePage([second]));
+    await act(async () => {
+      await result.current.loadMore();
+    });
+
+    expect(vi.mocked(invoke)).toHaveBeenLastCalledWith("get_items", {
+      query: { sources: ["jira"], unread_only: true, limit: PAGE_SIZE, cursor: "timestamp:100:a" },
+    });
+    expect(result.current.items.map((i) => i.id)).toEqual(["a", "b"]);
+    expect(result.current.hasMore).toBe(false);
+  });
+
+  it("evaluates a saved view via invoke('get_view_items')", async () => {
+    const items = [makeItem({ id: "v" })];
+    vi.mocked(invoke).mockResolvedValue(makePage(items));
+
+    const { result } = renderHook(() => useItems("jira", true, "view-1"));
+
+    await waitFor(() => {
+      expect(result.current.items).toEqual(items);
+    });
+    expect(vi.mocked(invoke)).toHaveBeenCalledWith("get_view_items", {
+      viewId: "view-1",
+      limit: PAGE_SIZE,
+      cursor: null,
     });
   });
 
@@ -44,7 +82,7 @@ describe("useItems", () => {
 
   it("refresh() calls invoke('refresh_plugin') then refetches", async () => {
     const items = [makeItem()];
-    vi.mocked(invoke).mockResolvedValue(items);
+    vi.mocked(invoke).mockResolvedValue(makePage(items));
 
     const { result } = renderHook(() => useItems(null, false));
 
@@ -54,7 +92,7 @@ describe("useItems", () => {
     });
 
     vi.mocked(invoke).mockClear();
-    vi.mocked(invoke).mockResolvedValue(items);
+    vi.mocked(invoke).mockResolvedValue(makePage(items));
 
     await act(async () => {
       await result.current.refresh("jira");
@@ -68,7 +106,7 @@ describe("useItems", () => {
   it("markRead() optimistically updates state", async () => {
     const item = makeItem({ id: "x1", is_read: false });
     // First call: get_items returns unread item. Subsequent calls resolve.
-    vi.mocked(invoke).mockResolvedValue([item]);
+    vi.mocked(invoke).mockResolvedValue(makePage([item]));
 
     const { result } = renderHook(() => useItems(null, false));
 
@@ -79,7 +117,7 @@ describe("useItems", () => {
     // Make mark_read hang so we can check optimistic state
     vi.mocked(invoke).mockImplementation((cmd) => {
       if (cmd === "mark_read") return new Promise(() => {}); // never resolves
-      return Promise.resolve([item]);
+      return Promise.resolve(makePage([item]));
     });
 
     act(() => {
@@ -94,7 +132,7 @@ describe("useItems", () => {
 
   it("markRead() rolls back on invoke failure", async () => {
     const item = makeItem({ id: "x2", is_read: false });
-    vi.mocked(invoke).mockResolvedValue([item]);
+    vi.mocked(invoke).mockResolvedValue(makePage([item]));
 
     const { result } = renderHook(() => useItems(null, false));
 
@@ -105,7 +143,7 @@ describe("useItems", () => {
     // Make mark_read reject
     vi.mocked(invoke).mockImplementation((cmd) => {
       if (cmd === "mark_read") return Promise.reject(new Error("write failed"));
-      return Promise.resolve([item]);
+      return Promise.resolve(makePage([item]));
     });
 
     await act(async () => {
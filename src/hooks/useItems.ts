@@ -1,6 +1,10 @@
-import { useState, useEffect, useCallback } from "react";
+import { useState, useEffect, useCallback, useMemo, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
+import type { ItemPage, ItemQuery, PollFailure } from "../types";
+
+/** Items per `get_items` page. */
+export const PAGE_SIZE = 50;
 
 export interface NexusItem {
   id: string;
@@ -18,23 +22,57 @@ export interface NexusItem {
   is_read: boolean;
   created_at: number;
   updated_at: number;
+  signals: string | null;  // JSON array string
+  archived_at: number | null; // set when the item disappeared upstream
+  snoozed_until: number | null; // hidden from the feed until then
+  flags: ItemFlags;        // user-owned; re-polls never change it
+}
+
+/** What the user has set on an item, via the `set_item_flags` command. */
+export interface ItemFlags {
+  starred: boolean;
+  pinned: boolean; // listed first in the feed
+  note: string | null;
+  labels: string[];
 }
 
-export function useItems(source: string | null, unreadOnly: boolean) {
+/**
+ * Feed items, one page at a time. With `viewId` set the saved view is
+ * evaluated server-side and `source` / `unreadOnly` are ignored.
+ */
+export function useItems(source: string | null, unreadOnly: boolean, viewId: string | null = null) {
   const [items, setItems]   = useState<NexusItem[]>([]);
   const [loading, setLoading] = useState(false);
   const [error, setError]   = useState<string | null>(null);
+  const [nextCursor, setNextCursor] = useState<string | null>(null);
+  const [loadingMore, setLoadingMore] = useState(false);
+  // Items currently loaded, so a background re-fetch keeps the pages scrolled in.
+  const loadedCount = useRef(0);
+  const loadingMoreRef = useRef(false);
+
+  const baseQuery = useMemo<ItemQuery>(
+    () => ({ sources: source ? [source] : [], unread_only: unreadOnly }),
+    [source, unreadOnly],
+  );
+
+  const fetchPage = useCallback(
+    (limit: number, cursor: string | null) =>
+      viewId
+        ? invoke<ItemPage>("get_view_items", { viewId, limit, cursor })
+        : invoke<ItemPage>("get_items", {
+            query: cursor ? { ...baseQuery, limit, cursor } : { ...baseQuery, limit },
+          }),
+    [baseQuery, viewId],
+  );
 
   const fetchItems = useCallback(async () => {
     setLoading(true);
     setError(null);
     try {
-      const result = await invoke<NexusItem[]>("get_items", {
-        source,
-        unreadOnly,
-        limit: 100,
-      });
-      setItems(result);
+      const page = await fetchPage(Math.max(PAGE_SIZE, loadedCount.current), null);
+      setItems(page.items);
+      setNextCursor(page.next_cursor);
+      loadedCount.current = page.items.length;
     } catch (e) {
       const msg = String(e).toLowerCase();
       if (msg.includes("network") || msg.includes("fetch")) {
@@ -47,13 +85,32 @@ export function useItems(source: string | null, unreadOnly: boolean) {
     } finally {
       setLoading(false);
     }
-  }, [source, unreadOnly]);
+  }, [fetchPage]);
 
-  // Initial fetch and re-fetch on filter changes.
+  // Initial fetch and re-fetch on filter changes (back to the first page).
   useEffect(() => {
+    loadedCount.current = 0;
     fetchItems();
   }, [fetchItems]);
 
+  /** Append the next page, if any. Concurrent calls are ignored. */
+  const loadMore = useCallback(async () => {
+    if (nextCursor === null || loadingMoreRef.current) return;
+    loadingMoreRef.current = true;
+    setLoadingMore(true);
+    try {
+      const page = await fetchPage(PAGE_SIZE, nextCursor);
+      setItems((prev) => [...prev, ...page.items]);
+      setNextCursor(page.next_cursor);
+      loadedCount.current += page.items.length;
+    } catch (e) {
+      console.error("loadMore failed:", e);
+    } finally {
+      loadingMoreRef.current = false;
+      setLoadingMore(false);
+    }
+  }, [fetchPage, nextCursor]);
+
   // Re-fetch whenever the scheduler emits items-updated.
   useEffect(() => {
     const unlisten = listen("items-updated", () => {
@@ -64,6 +121,30 @@ export function useItems(source: string | null, unreadOnly: boolean) {
     };
   }, [fetchItems]);
 
+  // Surface background poll failures instead of leaving them on the dev console.
+  useEffect(() => {
+    const unlisten = listen<PollFailure>("items-poll-failed", (event) => {
+      const { plugin_id, kind, retry_at } = event.payload;
+      if (kind === "auth") {
+        setError(`${plugin_id} rejected its credentials. Re-enter them in Settings.`);
+      } else if (kind === "rate_limited") {
+        const wait = retry_at ? Math.max(0, retry_at - Math.floor(Date.now() / 1000)) : null;
+        setError(
+          wait !== null
+            ? `${plugin_id} is rate limited. Retrying in ${wait}s.`
+            : `${plugin_id} is rate limited. Retrying later.`,
+        );
+      } else if (kind === "network") {
+        setError(`Couldn't reach ${plugin_id}. Check your connection.`);
+      } else {
+        setError(`${plugin_id} failed to sync. Check its settings.`);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   /** Trigger a single plugin poll then refresh the list. */
   const refresh = useCallback(async (pluginId: string) => {
     setLoading(true);
@@ -125,5 +206,42 @@ export function useItems(source: string | null, unreadOnly: boolean) {
     }
   }, []);
 
-  return { items, loading, error, refresh, refreshAll, markRead };
+  /** Optimistically replace an item's flags, then persist via IPC. */
+  const setFlags = useCallback(async (itemId: string, flags: ItemFlags) => {
+    setItems((prev) =>
+      prev.map((item) => (item.id === itemId ? { ...item, flags } : item))
+    );
+    try {
+      await invoke("set_item_flags", { itemId, flags });
+    } catch (e) {
+      console.error("setFlags failed:", e);
+    }
+    // Re-read so pinned items move and the note / labels come back normalised.
+    fetchItems();
+  }, [fetchItems]);
+
+  /** Hide an item until `until` (Unix seconds); the scheduler brings it back. */
+  const snooze = useCallback(async (itemId: string, until: number) => {
+    setItems((prev) => prev.filter((item) => item.id !== itemId));
+    try {
+      await invoke("snooze_item", { itemId, until });
+    } catch (e) {
+      console.error("snooze failed:", e);
+      fetchItems(); // rollback
+    }
+  }, [fetchItems]);
+
+  return {
+    items,
+    loading,
+    error,
+    refresh,
+    refreshAll,
+    markRead,
+    snooze,
+    setFlags,
+    hasMore: nextCursor !== null,
+    loadingMore,
+    loadMore,
+  };
 }
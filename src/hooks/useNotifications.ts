@@ -9,6 +9,9 @@ export interface Notification {
   urgency: string;
   is_dismissed: boolean;
   created_at: number;
+  snoozed_until: number | null; // hidden until then, then notified again
+  escalated_at: number | null;  // last time a critical reminder re-fired
+  delivered_at: number | null;  // null while held for a batch or digest
 }
 
 export function useNotifications() {
@@ -57,5 +60,15 @@ export function useNotifications() {
     }
   }, [fetchNotifications]);
 
-  return { notifications, dismiss, dismissAll, refresh: fetchNotifications };
+  const snooze = useCallback(async (notifId: string, until: number) => {
+    setNotifications((prev) => prev.filter((n) => n.id !== notifId));
+    try {
+      await invoke("snooze_notification", { notifId, until });
+    } catch (e) {
+      console.error("Failed to snooze notification:", e);
+      fetchNotifications(); // rollback
+    }
+  }, [fetchNotifications]);
+
+  return { notifications, dismiss, dismissAll, snooze, refresh: fetchNotifications };
 }
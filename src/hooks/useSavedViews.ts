@@ -0,0 +1,55 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { SavedView } from "../types";
+
+export function useSavedViews() {
+  const [views, setViews] = useState<SavedView[]>([]);
+  const [unreadCounts, setUnreadCounts] = useState<Record<string, number>>({});
+
+  const fetchViews = useCallback(async () => {
+    try {
+      const [list, counts] = await Promise.all([
+        invoke<SavedView[]>("get_views"),
+        invoke<Record<string, number>>("get_view_unread_counts"),
+      ]);
+      setViews(list);
+      setUnreadCounts(counts);
+    } catch (e) {
+      console.error("Failed to fetch saved views:", e);
+    }
+  }, []);
+
+  useEffect(() => {
+    fetchViews();
+  }, [fetchViews]);
+
+  // New items change the unread badges.
+  useEffect(() => {
+    const unlisten = listen("items-updated", () => {
+      fetchViews();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [fetchViews]);
+
+  /** Create (no `id`) or update a view; resolves to the stored view. */
+  const saveView = useCallback(async (view: Partial<SavedView> & Pick<SavedView, "name">) => {
+    const saved = await invoke<SavedView>("save_view", { view });
+    await fetchViews();
+    return saved;
+  }, [fetchViews]);
+
+  const deleteView = useCallback(async (viewId: string) => {
+    setViews((prev) => prev.filter((v) => v.id !== viewId));
+    try {
+      await invoke("delete_view", { viewId });
+    } catch (e) {
+      console.error("Failed to delete view:", e);
+      fetchViews(); // rollback
+    }
+  }, [fetchViews]);
+
+  return { views, unreadCounts, saveView, deleteView, refresh: fetchViews };
+}
@@ -1,4 +1,5 @@
 import type { NexusItem } from "../hooks/useItems";
+import type { ItemPage } from "../types";
 
 const now = Math.floor(Date.now() / 1000);
 
@@ -25,6 +26,15 @@ export function makeItem(overrides: Partial<NexusItem> = {}): NexusItem {
     is_read: false,
     created_at: now - 7200,
     updated_at: now - 3600,
+    signals: JSON.stringify(["assigned_to_me"]),
+    archived_at: null,
+    snoozed_until: null,
+    flags: { starred: false, pinned: false, note: null, labels: [] },
     ...overrides,
   };
 }
+
+/** A `get_items` response page. */
+export function makePage(items: NexusItem[], next_cursor: string | null = null): ItemPage {
+  return { items, next_cursor };
+}
@@ -1,6 +1,8 @@
 // Shared TypeScript types for Nexus Hub frontend
 
-export type { NexusItem } from "../hooks/useItems";
+import type { NexusItem } from "../hooks/useItems";
+
+export type { NexusItem, ItemFlags } from "../hooks/useItems";
 export type { Notification } from "../hooks/useNotifications";
 
 export interface PluginConfig {
@@ -12,21 +14,198 @@ export interface PluginConfig {
   last_error: string | null;
   error_count: number;
   settings: string | null;
+  last_error_at: number | null;
+  auto_disabled_at: number | null;
+  last_error_kind: PluginErrorKind | null;
+  retry_at: number | null;
+}
+
+/** Failure categories reported by the scheduler (`PluginError::kind`). */
+export type PluginErrorKind =
+  | "auth"
+  | "rate_limited"
+  | "network"
+  | "bad_output"
+  | "timeout"
+  | "not_configured"
+  | "disabled"
+  | "cancelled"
+  | "failed";
+
+/** Payload of the `items-poll-failed` event emitted by the scheduler. */
+export interface PollFailure {
+  plugin_id: string;
+  error: string;
+  kind: PluginErrorKind;
+  error_count: number;
+  failed_at: number;
+  /** Set for rate-limited plugins: when the next attempt happens. */
+  retry_at: number | null;
+}
+
+/** One database maintenance pass, from the `get_maintenance_runs` command. */
+export interface MaintenanceRun {
+  ran_at: number;
+  items_pruned: number;
+  notifications_pruned: number;
+  vacuumed: boolean;
+}
+
+/** Sort order of the `get_items` command; always newest/highest first. */
+export type ItemSort = "timestamp" | "priority" | "updated_at";
+
+/** Argument of the `get_items` command. Every field is optional. */
+export interface ItemQuery {
+  /** List filters match any of their values; empty = no filter. */
+  sources?: string[];
+  item_types?: string[];
+  tags?: string[];
+  /** Signal names, as reported by the plugin. */
+  signals?: string[];
+  /** Case-insensitive exact match. */
+  author?: string | null;
+  /** `timestamp >= since` and `timestamp < until`, Unix seconds. */
+  since?: number | null;
+  until?: number | null;
+  min_priority?: number | null;
+  /** Metadata values by key (`a.b` for nested keys); all must match. */
+  metadata?: Record<string, string | number | boolean | null>;
+  unread_only?: boolean;
+  include_archived?: boolean;
+  include_snoozed?: boolean;
+  starred_only?: boolean;
+  pinned_only?: boolean;
+  /** User labels (`ItemFlags.labels`). */
+  labels?: string[];
+  /** Pinned items always come first. */
+  sort?: ItemSort;
+  limit?: number | null;
+  /** `next_cursor` of the previous page. */
+  cursor?: string | null;
+}
+
+/** Result of the `get_items` command; `next_cursor` is null on the last page. */
+export interface ItemPage {
+  items: NexusItem[];
+  next_cursor: string | null;
+}
+
+/** A named item filter from the `get_views` command, shown in the sidebar. */
+export interface SavedView {
+  /** Empty when creating a view with `save_view`. */
+  id: string;
+  name: string;
+  /** Stored without `cursor` and `limit`. */
+  query: ItemQuery;
+  /** Relative bounds resolved when the view is evaluated: only items at least / at most this old. */
+  min_age_secs: number | null;
+  max_age_secs: number | null;
+  position: number;
+  created_at: number;
+  updated_at: number;
+}
+
+export type Weekday = "Mon" | "Tue" | "Wed" | "Thu" | "Fri" | "Sat" | "Sun";
+
+/** A daily quiet window; one not ending after it starts runs past midnight. */
+export interface QuietWindow {
+  /** Empty = every day. */
+  days: Weekday[];
+  /** `HH:MM` */
+  start: string;
+  /** `HH:MM`, exclusive; `24:00` is midnight. */
+  end: string;
+}
+
+/** Quiet-hours schedule of the `get_quiet_hours` / `set_quiet_hours` commands. */
+export interface QuietSchedule {
+  /** IANA time zone; null = the system's. */
+  timezone: string | null;
+  windows: QuietWindow[];
+  /** Holidays and time off (`YYYY-MM-DD`, both ends included). */
+  days_off: { start: string; end: string }[];
+}
+
+/** What a notification rule matches; every given condition must hold. */
+export interface RuleConditions {
+  /** List conditions match any of their values; empty = anything. */
+  sources?: string[];
+  item_types?: string[];
+  /** The item's signals, or those in the notification's reason. */
+  signals?: string[];
+  /** Case-insensitive. */
+  authors?: string[];
+  tags?: string[];
+  /** Item metadata values by key (`a.b` for nested keys). */
+  metadata?: Record<string, string | number | boolean | null>;
+}
+
+/** What a matching notification rule does. */
+export type RuleAction =
+  | { type: "always_notify" }
+  | { type: "never_notify" }
+  | { type: "override_urgency"; urgency: "low" | "medium" | "high" | "critical" }
+  | { type: "bypass_quiet_hours" };
+
+/** A routing rule from the `get_notification_rules` command, applied in `position` order. */
+export interface NotificationRule {
+  /** Empty when creating a rule with `save_notification_rule`. */
+  id: string;
+  name: string;
+  is_enabled: boolean;
+  position: number;
+  conditions: RuleConditions;
+  action: RuleAction;
+  created_at: number;
+  updated_at: number;
+}
+
+/** Optional filters of the `search_items` command. */
+export interface SearchFilters {
+  /** Empty or omitted = all sources. */
+  sources?: string[];
+  unread_only?: boolean;
+  include_archived?: boolean;
+  limit?: number | null;
+}
+
+/**
+ * Result item of the `search_items` command. Matched terms in
+ * `title_highlight` and `snippet` are wrapped in `\u0002` … `\u0003`.
+ */
+export interface SearchHit {
+  item: NexusItem;
+  title_highlight: string;
+  snippet: string;
+  /** Higher is a better match. */
+  score: number;
 }
 
-export interface JiraCredentials {
-  baseUrl: string;
-  email: string;
-  apiToken: string;
+/** Result of the `test_plugin_connection` command. */
+export interface ConnectionStatus {
+  ok: boolean;
+  status: number;
+  error: string | null;
 }
 
-export interface GitHubCredentials {
-  token: string;
+/** One input of a plugin's credentials form, from its manifest. */
+export interface CredentialField {
+  key: string;
+  label: string;
+  /** `list` is a comma-separated input stored as a JSON array. */
+  type: "text" | "password" | "email" | "url" | "list";
+  placeholder: string | null;
+  hint: string | null;
+  optional: boolean;
 }
 
-export interface GmailCredentials {
-  clientId: string;
-  clientSecret: string;
-  refreshToken: string;
-  vipSenders: string[]; // stored as JSON array; UI converts to/from comma-separated string
+/** Result item of the `list_plugins` command (`{id}.manifest.json`). */
+export interface PluginManifest {
+  id: string;
+  name: string;
+  version: string;
+  credentials: CredentialField[];
+  defaultPollIntervalSecs: number;
+  signals: { name: string; weight: number; description: string | null }[];
+  permissions: { net: string[]; netFromCredentials: string[]; env: string[] };
 }
@@ -0,0 +1,20 @@
+import { snoozeOptions } from "./time";
+
+describe("snoozeOptions", () => {
+  it("offers an hour, tomorrow morning and next Monday morning", () => {
+    const wednesday = new Date(2026, 9, 14, 15, 30); // Wed 14 Oct 2026, 15:30 local
+    const at = (d: Date) => Math.floor(d.getTime() / 1000);
+
+    expect(snoozeOptions(wednesday)).toEqual([
+      { label: "1 hour", until: at(wednesday) + 3600 },
+      { label: "Tomorrow 9:00", until: at(new Date(2026, 9, 15, 9)) },
+      { label: "Monday 9:00", until: at(new Date(2026, 9, 19, 9)) },
+    ]);
+  });
+
+  it("skips to the following Monday on a Monday", () => {
+    const monday = new Date(2026, 9, 19, 8, 0);
+    const [, , nextMonday] = snoozeOptions(monday);
+    expect(nextMonday.until).toBe(Math.floor(new Date(2026, 9, 26, 9).getTime() / 1000));
+  });
+});
@@ -6,3 +6,21 @@ export function timeAgo(ts: number): string {
   if (diff < 86400) return `${Math.floor(diff / 3600)}h ago`;
   return `${Math.floor(diff / 86400)}d ago`;
 }
+
+/** Snooze choices offered in the UI, as Unix seconds (local time). */
+export function snoozeOptions(now: Date = new Date()): { label: string; until: number }[] {
+  const at = (d: Date) => Math.floor(d.getTime() / 1000);
+  const nineAm = (daysAhead: number) => {
+    const d = new Date(now);
+    d.setDate(now.getDate() + daysAhead);
+    d.setHours(9, 0, 0, 0);
+    return d;
+  };
+  // Days until next Monday: 1 on Sunday, 7 on Monday.
+  const toMonday = (8 - now.getDay()) % 7 || 7;
+  return [
+    { label: "1 hour", until: at(now) + 3600 },
+    { label: "Tomorrow 9:00", until: at(nineAm(1)) },
+    { label: "Monday 9:00", until: at(nineAm(toMonday)) },
+  ];
+}